@@ -0,0 +1,358 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+// Substrate
+use sp_core::{H160, H256};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{Database, DatabaseIter, DbHash};
+
+/// Number of blocks grouped under a single key prefix of the address index.
+pub const ADDRESS_INDEX_BUCKET_SIZE: u64 = 1000;
+
+/// Key prefixes of the address index column.
+mod prefixes {
+	pub const TRANSACTIONS: u8 = 0;
+	pub const BUCKETS: u8 = 1;
+	pub const CONTRACT_CREATIONS: u8 = 2;
+	pub const INDEXED_BLOCKS: u8 = 3;
+	pub const BACKFILLED: u8 = 4;
+}
+
+/// An Ethereum transaction sent or received by an address.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct AddressTransaction<Hash> {
+	pub block_number: u64,
+	/// Substrate hash of the block, to tell apart the transactions of retracted blocks.
+	pub block_hash: Hash,
+	pub ethereum_index: u32,
+	pub ethereum_transaction_hash: H256,
+}
+
+/// An Ethereum transaction creating a contract.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct ContractCreation<Hash> {
+	pub transaction: AddressTransaction<Hash>,
+	pub creator: H160,
+}
+
+/// Addresses involved in an Ethereum transaction.
+#[derive(Debug)]
+pub struct IndexedTransaction {
+	pub hash: H256,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub contract_address: Option<H160>,
+}
+
+/// Ethereum transactions of a block to add to the index, in block order.
+#[derive(Debug)]
+pub struct AddressIndexCommitment<Block: BlockT> {
+	pub block_hash: Block::Hash,
+	pub block_number: u64,
+	pub transactions: Vec<IndexedTransaction>,
+}
+
+/// Index of the Ethereum transactions by sender, receiver and created contract, filled by the
+/// mapping sync worker along with the transaction metadata.
+///
+/// Each transaction has its own entry per address, keyed by the bucket of
+/// [`ADDRESS_INDEX_BUCKET_SIZE`] blocks, the block and the index of the transaction, next to a
+/// marker of each bucket holding a transaction of the address. Indexing a block only adds its own
+/// entries. Transactions of retracted blocks are kept: readers are responsible for dropping them.
+///
+/// Blocks synced before the index existed are indexed by a backfill, see [`Self::backfilled`].
+pub struct AddressIndexDb<Block: BlockT> {
+	pub(crate) db: Arc<dyn Database<DbHash>>,
+	pub(crate) db_iter: Arc<dyn DatabaseIter>,
+	pub(crate) write_lock: Arc<Mutex<()>>,
+	pub(crate) _marker: PhantomData<Block>,
+}
+
+fn bucket(block_number: u64) -> u64 {
+	block_number / ADDRESS_INDEX_BUCKET_SIZE
+}
+
+/// Appends `number` to `prefix`. Numbers of the keys are big endian, so that the entries are
+/// iterated in block order.
+fn with_number(mut prefix: Vec<u8>, number: u64) -> Vec<u8> {
+	prefix.extend_from_slice(&number.to_be_bytes());
+	prefix
+}
+
+impl<Block: BlockT> AddressIndexDb<Block> {
+	fn get<T: Decode + Default>(&self, key: &[u8]) -> Result<T, String> {
+		match self.db.get(crate::columns::ADDRESS_INDEX, key) {
+			Some(raw) => Ok(T::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?),
+			None => Ok(T::default()),
+		}
+	}
+
+	/// Decoded values of the entries whose key starts with `prefix`, in key order.
+	fn values<T: Decode>(&self, prefix: &[u8]) -> Result<Vec<T>, String> {
+		self.db_iter
+			.iter_with_prefix(crate::columns::ADDRESS_INDEX, prefix)
+			.map(|(_, value)| T::decode(&mut &value[..]).map_err(|e| format!("{:?}", e)))
+			.collect()
+	}
+
+	fn transactions_prefix(address: &H160, bucket: u64) -> Vec<u8> {
+		with_number((prefixes::TRANSACTIONS, address).encode(), bucket)
+	}
+
+	fn transaction_key(address: &H160, transaction: &AddressTransaction<Block::Hash>) -> Vec<u8> {
+		let mut key = with_number(
+			Self::transactions_prefix(address, bucket(transaction.block_number)),
+			transaction.block_number,
+		);
+		key.extend_from_slice(&transaction.block_hash.encode());
+		key.extend_from_slice(&transaction.ethereum_index.to_be_bytes());
+		key
+	}
+
+	fn buckets_prefix(address: &H160) -> Vec<u8> {
+		(prefixes::BUCKETS, address).encode()
+	}
+
+	fn contract_creations_prefix(address: &H160) -> Vec<u8> {
+		(prefixes::CONTRACT_CREATIONS, address).encode()
+	}
+
+	fn indexed_block_key(block_number: u64, block_hash: &Block::Hash) -> Vec<u8> {
+		let mut key = with_number(vec![prefixes::INDEXED_BLOCKS], block_number);
+		key.extend_from_slice(&block_hash.encode());
+		key
+	}
+
+	/// Buckets with a transaction of `address`, ascending.
+	pub fn buckets(&self, address: &H160) -> Result<Vec<u64>, String> {
+		self.values(&Self::buckets_prefix(address))
+	}
+
+	/// Transactions of `address` in the blocks of `bucket`, ascending.
+	pub fn transactions(
+		&self,
+		address: &H160,
+		bucket: u64,
+	) -> Result<Vec<AddressTransaction<Block::Hash>>, String> {
+		self.values(&Self::transactions_prefix(address, bucket))
+	}
+
+	/// Transactions that created the contract `address`, one per fork.
+	pub fn contract_creations(
+		&self,
+		address: &H160,
+	) -> Result<Vec<ContractCreation<Block::Hash>>, String> {
+		self.values(&Self::contract_creations_prefix(address))
+	}
+
+	/// Returns true if the block `block_hash` at `block_number` has been indexed.
+	pub fn is_block_indexed(&self, block_number: u64, block_hash: &Block::Hash) -> bool {
+		self.db.contains(
+			crate::columns::ADDRESS_INDEX,
+			&Self::indexed_block_key(block_number, block_hash),
+		)
+	}
+
+	/// Number of the first block not yet checked by the backfill of the index, the canonical
+	/// blocks below it are indexed.
+	pub fn backfilled(&self) -> Result<u64, String> {
+		self.get(&[prefixes::BACKFILLED])
+	}
+
+	pub fn set_backfilled(&self, block_number: u64) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+		transaction.set(
+			crate::columns::ADDRESS_INDEX,
+			&[prefixes::BACKFILLED],
+			&block_number.encode(),
+		);
+		self.db.commit(transaction).map_err(|e| format!("{:?}", e))
+	}
+
+	pub fn write_block(&self, commitment: AddressIndexCommitment<Block>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		if self.is_block_indexed(commitment.block_number, &commitment.block_hash) {
+			return Ok(());
+		}
+
+		let bucket = bucket(commitment.block_number);
+		let mut transaction = sp_database::Transaction::new();
+		for (index, indexed) in commitment.transactions.into_iter().enumerate() {
+			let address_transaction = AddressTransaction {
+				block_number: commitment.block_number,
+				block_hash: commitment.block_hash,
+				ethereum_index: index as u32,
+				ethereum_transaction_hash: indexed.hash,
+			};
+			let value = address_transaction.encode();
+			let addresses = std::iter::once(indexed.from)
+				.chain(indexed.to)
+				.chain(indexed.contract_address);
+			for address in addresses {
+				transaction.set(
+					crate::columns::ADDRESS_INDEX,
+					&Self::transaction_key(&address, &address_transaction),
+					&value,
+				);
+				transaction.set(
+					crate::columns::ADDRESS_INDEX,
+					&with_number(Self::buckets_prefix(&address), bucket),
+					&bucket.encode(),
+				);
+			}
+			if let Some(contract_address) = indexed.contract_address {
+				let mut key = Self::contract_creations_prefix(&contract_address);
+				key.extend_from_slice(&commitment.block_hash.encode());
+				transaction.set(
+					crate::columns::ADDRESS_INDEX,
+					&key,
+					&ContractCreation {
+						transaction: address_transaction,
+						creator: indexed.from,
+					}
+					.encode(),
+				);
+			}
+		}
+		transaction.set(
+			crate::columns::ADDRESS_INDEX,
+			&Self::indexed_block_key(commitment.block_number, &commitment.block_hash),
+			&(commitment.block_number, commitment.block_hash).encode(),
+		);
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+	use tempfile::tempdir;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	#[test]
+	fn address_index_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+		let backend = crate::Backend::<OpaqueBlock>::new(
+			client,
+			&crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("a temporary db was created");
+		let address_index = backend.address_index();
+
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let contract = H160::repeat_byte(0xcc);
+		let commitment = |number: u64, transactions| AddressIndexCommitment::<OpaqueBlock> {
+			block_hash: H256::from_low_u64_be(number),
+			block_number: number,
+			transactions,
+		};
+		let address_transaction = |number: u64, index: u32, hash: u8| AddressTransaction {
+			block_number: number,
+			block_hash: H256::from_low_u64_be(number),
+			ethereum_index: index,
+			ethereum_transaction_hash: H256::repeat_byte(hash),
+		};
+
+		let block_1 = || {
+			commitment(
+				1,
+				vec![
+					IndexedTransaction {
+						hash: H256::repeat_byte(1),
+						from: alice,
+						to: Some(bob),
+						contract_address: None,
+					},
+					IndexedTransaction {
+						hash: H256::repeat_byte(2),
+						from: alice,
+						to: None,
+						contract_address: Some(contract),
+					},
+				],
+			)
+		};
+		address_index.write_block(block_1()).unwrap();
+		// Writing a block twice does not duplicate its entries.
+		address_index.write_block(block_1()).unwrap();
+		address_index
+			.write_block(commitment(
+				ADDRESS_INDEX_BUCKET_SIZE + 1,
+				vec![IndexedTransaction {
+					hash: H256::repeat_byte(3),
+					from: bob,
+					to: Some(contract),
+					contract_address: None,
+				}],
+			))
+			.unwrap();
+
+		assert_eq!(address_index.buckets(&alice).unwrap(), vec![0]);
+		assert_eq!(address_index.buckets(&contract).unwrap(), vec![0, 1]);
+		assert_eq!(
+			address_index.transactions(&alice, 0).unwrap(),
+			vec![address_transaction(1, 0, 1), address_transaction(1, 1, 2)]
+		);
+		assert_eq!(
+			address_index.transactions(&bob, 1).unwrap(),
+			vec![address_transaction(ADDRESS_INDEX_BUCKET_SIZE + 1, 0, 3)]
+		);
+		assert_eq!(address_index.transactions(&alice, 1).unwrap(), vec![]);
+		assert_eq!(
+			address_index.contract_creations(&contract).unwrap(),
+			vec![ContractCreation {
+				transaction: address_transaction(1, 1, 2),
+				creator: alice,
+			}]
+		);
+		assert_eq!(address_index.contract_creations(&bob).unwrap(), vec![]);
+
+		assert!(address_index.is_block_indexed(1, &H256::from_low_u64_be(1)));
+		assert!(!address_index.is_block_indexed(2, &H256::from_low_u64_be(2)));
+
+		assert_eq!(address_index.backfilled().unwrap(), 0);
+		address_index.set_backfilled(2).unwrap();
+		assert_eq!(address_index.backfilled().unwrap(), 2);
+	}
+}
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{Database, DbHash};
+
+/// Key of the range of block numbers stored in the fee history column.
+const BOUNDS_KEY: &[u8] = b"BOUNDS";
+
+/// Fee history of a block, as served by `eth_feeHistory`.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct FeeHistoryEntry<Hash> {
+	/// Hash of the block, to tell apart the entries of retracted blocks.
+	pub block_hash: Hash,
+	pub base_fee: u64,
+	/// Gas used and gas limit of the block, their ratio is the gas used ratio.
+	pub gas_used: u64,
+	pub gas_limit: u64,
+	/// Effective priority fees at each cached reward percentile.
+	pub rewards: Vec<u64>,
+	/// Effective priority fees sampled by the gas price oracle, ascending and not filtered by its
+	/// ignore price, which can change between runs.
+	pub lowest_rewards: Vec<u64>,
+}
+
+/// Fee history of recent blocks keyed by block number, filled by the fee history cache task.
+pub struct FeeHistoryDb<Block: BlockT> {
+	pub(crate) db: Arc<dyn Database<DbHash>>,
+	pub(crate) write_lock: Arc<Mutex<()>>,
+	pub(crate) _marker: PhantomData<Block>,
+}
+
+impl<Block: BlockT> FeeHistoryDb<Block> {
+	/// Lowest and highest block numbers with a stored entry.
+	fn bounds(&self) -> Result<Option<(u64, u64)>, String> {
+		match self.db.get(crate::columns::FEE_HISTORY, BOUNDS_KEY) {
+			Some(raw) => Ok(Some(
+				Decode::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	pub fn entry(&self, block_number: u64) -> Result<Option<FeeHistoryEntry<Block::Hash>>, String> {
+		match self
+			.db
+			.get(crate::columns::FEE_HISTORY, &block_number.encode())
+		{
+			Some(raw) => Ok(Some(
+				FeeHistoryEntry::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	/// Stores the entry of `block_number`, replacing the one of a retracted block if any, and
+	/// removes the entries of the blocks below `retain_from`.
+	pub fn write_entry(
+		&self,
+		block_number: u64,
+		entry: FeeHistoryEntry<Block::Hash>,
+		retain_from: u64,
+	) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+		transaction.set(
+			crate::columns::FEE_HISTORY,
+			&block_number.encode(),
+			&entry.encode(),
+		);
+
+		let (lowest, highest) = match self.bounds()? {
+			Some((lowest, highest)) => {
+				let highest = highest.max(block_number);
+				for number in lowest..retain_from.min(highest.saturating_add(1)) {
+					if number != block_number {
+						transaction.remove(crate::columns::FEE_HISTORY, &number.encode());
+					}
+				}
+				(lowest.max(retain_from).min(block_number), highest)
+			}
+			None => (block_number, block_number),
+		};
+		transaction.set(
+			crate::columns::FEE_HISTORY,
+			BOUNDS_KEY,
+			&(lowest, highest).encode(),
+		);
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use sp_core::H256;
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+	use tempfile::tempdir;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	fn entry(block_number: u64) -> FeeHistoryEntry<H256> {
+		FeeHistoryEntry {
+			block_hash: H256::from_low_u64_be(block_number),
+			base_fee: 1_000_000_000,
+			gas_used: 21_000,
+			gas_limit: 75_000_000,
+			rewards: vec![block_number; 201],
+			lowest_rewards: vec![block_number],
+		}
+	}
+
+	#[test]
+	fn fee_history_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+		let backend = crate::Backend::<OpaqueBlock>::new(
+			client,
+			&crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("a temporary db was created");
+		let fee_history = backend.fee_history();
+
+		// Keep the last 10 blocks.
+		for number in 1..=20 {
+			fee_history
+				.write_entry(number, entry(number), number.saturating_sub(9))
+				.unwrap();
+		}
+		assert_eq!(fee_history.entry(10).unwrap(), None);
+		assert_eq!(fee_history.entry(11).unwrap(), Some(entry(11)));
+		assert_eq!(fee_history.entry(20).unwrap(), Some(entry(20)));
+
+		// A block of a new fork replaces the entry of the retracted one.
+		let mut forked = entry(20);
+		forked.block_hash = H256::repeat_byte(0xff);
+		fee_history.write_entry(20, forked.clone(), 11).unwrap();
+		assert_eq!(fee_history.entry(20).unwrap(), Some(forked));
+		assert_eq!(fee_history.entry(11).unwrap(), Some(entry(11)));
+
+		// Entries of a skipped range are removed as well.
+		fee_history.write_entry(100, entry(100), 91).unwrap();
+		assert!((11..=20).all(|number| fee_history.entry(number).unwrap().is_none()));
+		assert_eq!(fee_history.entry(100).unwrap(), Some(entry(100)));
+	}
+}
@@ -16,8 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod address_index;
+mod fee_history;
+mod log_index;
 #[cfg(feature = "parity-db")]
 mod parity_db_adapter;
+mod trace_index;
 mod upgrade;
 mod utils;
 
@@ -37,10 +41,30 @@ use sp_runtime::traits::Block as BlockT;
 // Frontier
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA_CACHE};
 
+pub use self::{
+	address_index::{
+		AddressIndexCommitment, AddressIndexDb, AddressTransaction, ContractCreation,
+		IndexedTransaction, ADDRESS_INDEX_BUCKET_SIZE,
+	},
+	fee_history::{FeeHistoryDb, FeeHistoryEntry},
+	log_index::{IndexedLog, LogIndexCommitment, LogIndexDb, LOG_INDEX_BUCKET_SIZE},
+	trace_index::{TraceIndexCommitment, TraceIndexDb, TRACE_INDEX_BUCKET_SIZE},
+};
+
 const DB_HASH_LEN: usize = 32;
 /// Hash type that this backend uses for the database.
 pub type DbHash = [u8; DB_HASH_LEN];
 
+/// Iteration over the entries of a column in key order, which [`Database`] does not provide.
+pub(crate) trait DatabaseIter: Send + Sync {
+	/// Key and value of the entries of `col` whose key starts with `prefix`, in key order.
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a>;
+}
+
 /// Database settings.
 pub struct DatabaseSettings {
 	/// Where to find the database.
@@ -48,12 +72,16 @@ pub struct DatabaseSettings {
 }
 
 pub(crate) mod columns {
-	pub const NUM_COLUMNS: u32 = 4;
+	pub const NUM_COLUMNS: u32 = 8;
 
 	pub const META: u32 = 0;
 	pub const BLOCK_MAPPING: u32 = 1;
 	pub const TRANSACTION_MAPPING: u32 = 2;
 	pub const SYNCED_MAPPING: u32 = 3;
+	pub const LOG_INDEX: u32 = 4;
+	pub const FEE_HISTORY: u32 = 5;
+	pub const TRACE_INDEX: u32 = 6;
+	pub const ADDRESS_INDEX: u32 = 7;
 }
 
 pub mod static_keys {
@@ -63,6 +91,10 @@ pub mod static_keys {
 pub struct Backend<Block: BlockT> {
 	meta: Arc<MetaDb<Block>>,
 	mapping: Arc<MappingDb<Block>>,
+	log_index: Arc<LogIndexDb<Block>>,
+	fee_history: Arc<FeeHistoryDb<Block>>,
+	trace_index: Arc<TraceIndexDb<Block>>,
+	address_index: Arc<AddressIndexDb<Block>>,
 }
 
 /// Returns the frontier database directory.
@@ -106,7 +138,7 @@ impl<Block: BlockT> Backend<Block> {
 		client: Arc<C>,
 		config: &DatabaseSettings,
 	) -> Result<Self, String> {
-		let db = utils::open_database::<Block, C>(client, config)?;
+		let (db, db_iter) = utils::open_database::<Block, C>(client, config)?;
 
 		Ok(Self {
 			mapping: Arc::new(MappingDb {
@@ -118,6 +150,28 @@ impl<Block: BlockT> Backend<Block> {
 				db: db.clone(),
 				_marker: PhantomData,
 			}),
+			log_index: Arc::new(LogIndexDb {
+				db: db.clone(),
+				db_iter: db_iter.clone(),
+				write_lock: Arc::new(Mutex::new(())),
+				_marker: PhantomData,
+			}),
+			fee_history: Arc::new(FeeHistoryDb {
+				db: db.clone(),
+				write_lock: Arc::new(Mutex::new(())),
+				_marker: PhantomData,
+			}),
+			trace_index: Arc::new(TraceIndexDb {
+				db: db.clone(),
+				write_lock: Arc::new(Mutex::new(())),
+				_marker: PhantomData,
+			}),
+			address_index: Arc::new(AddressIndexDb {
+				db,
+				db_iter,
+				write_lock: Arc::new(Mutex::new(())),
+				_marker: PhantomData,
+			}),
 		})
 	}
 
@@ -128,6 +182,22 @@ impl<Block: BlockT> Backend<Block> {
 	pub fn meta(&self) -> &Arc<MetaDb<Block>> {
 		&self.meta
 	}
+
+	pub fn log_index(&self) -> &Arc<LogIndexDb<Block>> {
+		&self.log_index
+	}
+
+	pub fn fee_history(&self) -> &Arc<FeeHistoryDb<Block>> {
+		&self.fee_history
+	}
+
+	pub fn trace_index(&self) -> &Arc<TraceIndexDb<Block>> {
+		&self.trace_index
+	}
+
+	pub fn address_index(&self) -> &Arc<AddressIndexDb<Block>> {
+		&self.address_index
+	}
 }
 
 pub struct MetaDb<Block: BlockT> {
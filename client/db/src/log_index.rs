@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+// Substrate
+use sp_core::{H160, H256};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{Database, DatabaseIter, DbHash};
+
+/// Number of blocks grouped under a single key prefix of the log index.
+pub const LOG_INDEX_BUCKET_SIZE: u64 = 1000;
+
+/// Key prefixes of the log index column.
+mod prefixes {
+	pub const INDEXED_BLOCKS: u8 = 0;
+	pub const ADDRESS: u8 = 1;
+	pub const TOPIC: u8 = 2;
+	pub const BACKFILLED: u8 = 3;
+}
+
+/// Position of an indexed log in the chain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Encode, Decode)]
+pub struct IndexedLog<Hash> {
+	pub block_number: u64,
+	pub block_hash: Hash,
+	/// Index of the log in the block.
+	pub log_index: u32,
+}
+
+/// Logs of a block to add to the index, in block order.
+#[derive(Debug)]
+pub struct LogIndexCommitment<Block: BlockT> {
+	pub block_hash: Block::Hash,
+	pub block_number: u64,
+	/// Address and topics of each log.
+	pub logs: Vec<(H160, Vec<H256>)>,
+}
+
+/// Index of Ethereum logs by address and topic, filled by the mapping sync worker.
+///
+/// Each log has its own entry per address and topic, keyed by the bucket of
+/// [`LOG_INDEX_BUCKET_SIZE`] blocks, the block and the index of the log. Indexing a block only
+/// adds its own entries, and a range of blocks is read by iterating the buckets it spans.
+///
+/// Blocks of every fork are indexed: readers are responsible for dropping the entries of non
+/// canonical blocks. Blocks synced before the index existed are indexed by a backfill, ranges
+/// that are not indexed yet are answered by scanning the blocks, see `eth_getLogs`.
+pub struct LogIndexDb<Block: BlockT> {
+	pub(crate) db: Arc<dyn Database<DbHash>>,
+	pub(crate) db_iter: Arc<dyn DatabaseIter>,
+	pub(crate) write_lock: Arc<Mutex<()>>,
+	pub(crate) _marker: PhantomData<Block>,
+}
+
+fn bucket(block_number: u64) -> u64 {
+	block_number / LOG_INDEX_BUCKET_SIZE
+}
+
+/// Appends `bucket` to `prefix`. Numbers of the keys are big endian, so that the entries of a
+/// bucket are iterated in block order.
+fn with_bucket(mut prefix: Vec<u8>, bucket: u64) -> Vec<u8> {
+	prefix.extend_from_slice(&bucket.to_be_bytes());
+	prefix
+}
+
+impl<Block: BlockT> LogIndexDb<Block> {
+	fn get<T: Decode + Default>(&self, key: &[u8]) -> Result<T, String> {
+		match self.db.get(crate::columns::LOG_INDEX, key) {
+			Some(raw) => Ok(T::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?),
+			None => Ok(T::default()),
+		}
+	}
+
+	/// Decoded values of the entries whose key starts with `prefix`, in key order.
+	fn values<'a, T: Decode + 'a>(
+		&'a self,
+		prefix: &'a [u8],
+	) -> impl Iterator<Item = Result<T, String>> + 'a {
+		self.db_iter
+			.iter_with_prefix(crate::columns::LOG_INDEX, prefix)
+			.map(|(_, value)| T::decode(&mut &value[..]).map_err(|e| format!("{:?}", e)))
+	}
+
+	fn indexed_blocks_prefix(bucket: u64) -> Vec<u8> {
+		with_bucket(vec![prefixes::INDEXED_BLOCKS], bucket)
+	}
+
+	fn indexed_block_key(block_number: u64, block_hash: &Block::Hash) -> Vec<u8> {
+		let mut key = Self::indexed_blocks_prefix(bucket(block_number));
+		key.extend_from_slice(&block_number.to_be_bytes());
+		key.extend_from_slice(&block_hash.encode());
+		key
+	}
+
+	fn address_prefix(address: &H160, bucket: u64) -> Vec<u8> {
+		with_bucket((prefixes::ADDRESS, address).encode(), bucket)
+	}
+
+	fn topic_prefix(position: u8, topic: &H256, bucket: u64) -> Vec<u8> {
+		with_bucket((prefixes::TOPIC, position, topic).encode(), bucket)
+	}
+
+	fn log_key(mut prefix: Vec<u8>, log: &IndexedLog<Block::Hash>) -> Vec<u8> {
+		prefix.extend_from_slice(&log.block_number.to_be_bytes());
+		prefix.extend_from_slice(&log.block_hash.encode());
+		prefix.extend_from_slice(&log.log_index.to_be_bytes());
+		prefix
+	}
+
+	/// Returns true if the block `block_hash` at `block_number` has been indexed.
+	pub fn is_block_indexed(&self, block_number: u64, block_hash: &Block::Hash) -> bool {
+		self.db.contains(
+			crate::columns::LOG_INDEX,
+			&Self::indexed_block_key(block_number, block_hash),
+		)
+	}
+
+	/// Returns true if the canonical block of each height in `from..=to` has been indexed, as
+	/// given by `canonical_hash`. The blocks of retracted forks do not count.
+	pub fn is_indexed(
+		&self,
+		from: u64,
+		to: u64,
+		canonical_hash: impl Fn(u64) -> Result<Option<Block::Hash>, String>,
+	) -> Result<bool, String> {
+		for bucket in bucket(from)..=bucket(to) {
+			let mut blocks = BTreeSet::new();
+			for block in self.values::<(u64, Block::Hash)>(&Self::indexed_blocks_prefix(bucket)) {
+				let (number, hash) = block?;
+				if (from..=to).contains(&number) {
+					blocks.insert((number, hash));
+				}
+			}
+			let first = from.max(bucket * LOG_INDEX_BUCKET_SIZE);
+			let last = to.min((bucket + 1) * LOG_INDEX_BUCKET_SIZE - 1);
+			for number in first..=last {
+				match canonical_hash(number)? {
+					Some(hash) if blocks.contains(&(number, hash)) => (),
+					_ => return Ok(false),
+				}
+			}
+		}
+		Ok(true)
+	}
+
+	fn logs_in_range(
+		&self,
+		prefix: impl Fn(u64) -> Vec<u8>,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<IndexedLog<Block::Hash>>, String> {
+		let mut logs = Vec::new();
+		for bucket in bucket(from)..=bucket(to) {
+			for log in self.values::<IndexedLog<Block::Hash>>(&prefix(bucket)) {
+				let log = log?;
+				if log.block_number > to {
+					break;
+				}
+				if log.block_number >= from {
+					logs.push(log);
+				}
+			}
+		}
+		Ok(logs)
+	}
+
+	/// Indexed logs emitted by `address` in blocks `from..=to`.
+	pub fn address_logs(
+		&self,
+		address: &H160,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<IndexedLog<Block::Hash>>, String> {
+		self.logs_in_range(|bucket| Self::address_prefix(address, bucket), from, to)
+	}
+
+	/// Indexed logs with `topic` at `position` in blocks `from..=to`.
+	pub fn topic_logs(
+		&self,
+		position: u8,
+		topic: &H256,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<IndexedLog<Block::Hash>>, String> {
+		self.logs_in_range(
+			|bucket| Self::topic_prefix(position, topic, bucket),
+			from,
+			to,
+		)
+	}
+
+	/// Number of the first block not yet checked by the backfill of the index, the canonical
+	/// blocks below it are indexed.
+	pub fn backfilled(&self) -> Result<u64, String> {
+		self.get(&[prefixes::BACKFILLED])
+	}
+
+	pub fn set_backfilled(&self, block_number: u64) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+		transaction.set(
+			crate::columns::LOG_INDEX,
+			&[prefixes::BACKFILLED],
+			&block_number.encode(),
+		);
+		self.db.commit(transaction).map_err(|e| format!("{:?}", e))
+	}
+
+	pub fn write_logs(&self, commitment: LogIndexCommitment<Block>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		if self.is_block_indexed(commitment.block_number, &commitment.block_hash) {
+			return Ok(());
+		}
+
+		let bucket = bucket(commitment.block_number);
+		let mut transaction = sp_database::Transaction::new();
+		for (log_index, (address, topics)) in commitment.logs.iter().enumerate() {
+			let log = IndexedLog {
+				block_number: commitment.block_number,
+				block_hash: commitment.block_hash,
+				log_index: log_index as u32,
+			};
+			let value = log.encode();
+			let prefixes = std::iter::once(Self::address_prefix(address, bucket)).chain(
+				topics
+					.iter()
+					.enumerate()
+					.map(|(position, topic)| Self::topic_prefix(position as u8, topic, bucket)),
+			);
+			for prefix in prefixes {
+				transaction.set(
+					crate::columns::LOG_INDEX,
+					&Self::log_key(prefix, &log),
+					&value,
+				);
+			}
+		}
+		transaction.set(
+			crate::columns::LOG_INDEX,
+			&Self::indexed_block_key(commitment.block_number, &commitment.block_hash),
+			&(commitment.block_number, commitment.block_hash).encode(),
+		);
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+	use tempfile::tempdir;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	fn commitment(
+		number: u64,
+		hash: H256,
+		logs: Vec<(H160, Vec<H256>)>,
+	) -> LogIndexCommitment<OpaqueBlock> {
+		LogIndexCommitment {
+			block_hash: hash,
+			block_number: number,
+			logs,
+		}
+	}
+
+	#[test]
+	fn log_index_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+		let backend = crate::Backend::<OpaqueBlock>::new(
+			client,
+			&crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("a temporary db was created");
+		let log_index = backend.log_index();
+
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let transfer = H256::repeat_byte(0x01);
+
+		for number in 0..=LOG_INDEX_BUCKET_SIZE {
+			let logs = match number {
+				1 => vec![(alice, vec![transfer]), (bob, vec![transfer])],
+				LOG_INDEX_BUCKET_SIZE => vec![(alice, vec![])],
+				_ => vec![],
+			};
+			log_index
+				.write_logs(commitment(number, H256::from_low_u64_be(number), logs))
+				.unwrap();
+		}
+		// Writing a block twice does not duplicate its entries.
+		log_index
+			.write_logs(commitment(
+				1,
+				H256::from_low_u64_be(1),
+				vec![(alice, vec![])],
+			))
+			.unwrap();
+
+		let canonical = |number: u64| Ok(Some(H256::from_low_u64_be(number)));
+		assert!(log_index
+			.is_indexed(0, LOG_INDEX_BUCKET_SIZE, canonical)
+			.unwrap());
+		assert!(!log_index
+			.is_indexed(0, LOG_INDEX_BUCKET_SIZE + 1, canonical)
+			.unwrap());
+		// A height whose indexed block was retracted is not covered.
+		let retracted = |number: u64| match number {
+			1 => Ok(Some(H256::repeat_byte(0xff))),
+			_ => canonical(number),
+		};
+		assert!(!log_index
+			.is_indexed(0, LOG_INDEX_BUCKET_SIZE, retracted)
+			.unwrap());
+
+		let at = |number: u64, log_index: u32| IndexedLog {
+			block_number: number,
+			block_hash: H256::from_low_u64_be(number),
+			log_index,
+		};
+		assert_eq!(
+			log_index
+				.address_logs(&alice, 0, LOG_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![at(1, 0), at(LOG_INDEX_BUCKET_SIZE, 0)]
+		);
+		assert_eq!(
+			log_index
+				.address_logs(&alice, 2, LOG_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![at(LOG_INDEX_BUCKET_SIZE, 0)]
+		);
+		assert_eq!(
+			log_index
+				.topic_logs(0, &transfer, 0, LOG_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![at(1, 0), at(1, 1)]
+		);
+		assert!(log_index
+			.topic_logs(1, &transfer, 0, LOG_INDEX_BUCKET_SIZE)
+			.unwrap()
+			.is_empty());
+
+		assert!(log_index.is_block_indexed(1, &H256::from_low_u64_be(1)));
+		assert!(!log_index.is_block_indexed(1, &H256::repeat_byte(0xff)));
+		assert_eq!(log_index.backfilled().unwrap(), 0);
+		log_index.set_backfilled(10).unwrap();
+		assert_eq!(log_index.backfilled().unwrap(), 10);
+	}
+}
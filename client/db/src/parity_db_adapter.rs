@@ -18,7 +18,7 @@
 
 use sp_database::{error::DatabaseError, Change, ColumnId, Transaction};
 
-use crate::Database;
+use crate::{Database, DatabaseIter};
 
 fn handle_err<T>(result: parity_db::Result<T>) -> T {
 	match result {
@@ -65,3 +65,18 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 		let _prefix = key.drain(0..key.len() - crate::DB_HASH_LEN);
 	}
 }
+
+impl DatabaseIter for DbAdapter {
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a> {
+		let mut iter = handle_err(self.0.iter(col as u8));
+		handle_err(iter.seek(prefix));
+		Box::new(
+			std::iter::from_fn(move || handle_err(iter.next()))
+				.take_while(move |(key, _)| key.starts_with(prefix)),
+		)
+	}
+}
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+	collections::{btree_map::Entry, BTreeMap, BTreeSet},
+	marker::PhantomData,
+	sync::Arc,
+};
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+// Substrate
+use sp_core::H160;
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{Database, DbHash};
+
+/// Number of blocks grouped under a single key of the trace index.
+pub const TRACE_INDEX_BUCKET_SIZE: u64 = 1000;
+
+/// Key prefixes of the trace index column.
+mod prefixes {
+	pub const INDEXED_BLOCKS: u8 = 0;
+	pub const FROM_ADDRESS: u8 = 1;
+	pub const TO_ADDRESS: u8 = 2;
+	pub const TIP: u8 = 3;
+}
+
+/// Addresses appearing in the traces of a block to add to the index.
+#[derive(Debug)]
+pub struct TraceIndexCommitment<Block: BlockT> {
+	pub block_hash: Block::Hash,
+	pub block_number: u64,
+	/// Senders of the traced calls, creates and self-destructs.
+	pub from: BTreeSet<H160>,
+	/// Receivers of the traced calls, created contracts and refund addresses of self-destructs.
+	pub to: BTreeSet<H160>,
+}
+
+/// Index of the blocks whose call traces involve an address, filled by the trace index worker.
+///
+/// Entries are grouped in buckets of [`TRACE_INDEX_BUCKET_SIZE`] blocks. Blocks of retracted forks
+/// are kept: readers are responsible for dropping the entries of non canonical blocks.
+pub struct TraceIndexDb<Block: BlockT> {
+	pub(crate) db: Arc<dyn Database<DbHash>>,
+	pub(crate) write_lock: Arc<Mutex<()>>,
+	pub(crate) _marker: PhantomData<Block>,
+}
+
+fn bucket(block_number: u64) -> u64 {
+	block_number / TRACE_INDEX_BUCKET_SIZE
+}
+
+impl<Block: BlockT> TraceIndexDb<Block> {
+	fn get<T: Decode + Default>(&self, key: &[u8]) -> Result<T, String> {
+		match self.db.get(crate::columns::TRACE_INDEX, key) {
+			Some(raw) => Ok(T::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?),
+			None => Ok(T::default()),
+		}
+	}
+
+	fn indexed_blocks_key(bucket: u64) -> Vec<u8> {
+		(prefixes::INDEXED_BLOCKS, bucket).encode()
+	}
+
+	fn address_key(prefix: u8, address: &H160, bucket: u64) -> Vec<u8> {
+		(prefix, address, bucket).encode()
+	}
+
+	fn blocks_in_range(
+		&self,
+		key: impl Fn(u64) -> Vec<u8>,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<(u64, Block::Hash)>, String> {
+		let mut blocks = Vec::new();
+		for bucket in bucket(from)..=bucket(to) {
+			blocks.extend(
+				self.get::<Vec<(u64, Block::Hash)>>(&key(bucket))?
+					.into_iter()
+					.filter(|(number, _)| (from..=to).contains(number)),
+			);
+		}
+		Ok(blocks)
+	}
+
+	/// Indexed blocks of heights `from..=to`, including the ones of retracted forks.
+	pub fn indexed_blocks(&self, from: u64, to: u64) -> Result<Vec<(u64, Block::Hash)>, String> {
+		self.blocks_in_range(Self::indexed_blocks_key, from, to)
+	}
+
+	/// Indexed blocks of heights `from..=to` with a trace sent by `address`.
+	pub fn from_address_blocks(
+		&self,
+		address: &H160,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<(u64, Block::Hash)>, String> {
+		self.blocks_in_range(
+			|bucket| Self::address_key(prefixes::FROM_ADDRESS, address, bucket),
+			from,
+			to,
+		)
+	}
+
+	/// Indexed blocks of heights `from..=to` with a trace received by `address`.
+	pub fn to_address_blocks(
+		&self,
+		address: &H160,
+		from: u64,
+		to: u64,
+	) -> Result<Vec<(u64, Block::Hash)>, String> {
+		self.blocks_in_range(
+			|bucket| Self::address_key(prefixes::TO_ADDRESS, address, bucket),
+			from,
+			to,
+		)
+	}
+
+	/// Last block written to the index.
+	pub fn tip(&self) -> Result<Option<(u64, Block::Hash)>, String> {
+		self.get(&[prefixes::TIP])
+	}
+
+	pub fn write_block(&self, commitment: TraceIndexCommitment<Block>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let bucket = bucket(commitment.block_number);
+		let block = (commitment.block_number, commitment.block_hash);
+
+		let mut entries: BTreeMap<Vec<u8>, Vec<(u64, Block::Hash)>> = BTreeMap::new();
+		let keys = std::iter::once(Self::indexed_blocks_key(bucket))
+			.chain(
+				commitment
+					.from
+					.iter()
+					.map(|address| Self::address_key(prefixes::FROM_ADDRESS, address, bucket)),
+			)
+			.chain(
+				commitment
+					.to
+					.iter()
+					.map(|address| Self::address_key(prefixes::TO_ADDRESS, address, bucket)),
+			);
+		for key in keys {
+			let blocks = match entries.entry(key) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => {
+					let existing = self.get(entry.key())?;
+					entry.insert(existing)
+				}
+			};
+			if !blocks.contains(&block) {
+				blocks.push(block);
+			}
+		}
+
+		let mut transaction = sp_database::Transaction::new();
+		for (key, blocks) in entries {
+			transaction.set(crate::columns::TRACE_INDEX, &key, &blocks.encode());
+		}
+		transaction.set(
+			crate::columns::TRACE_INDEX,
+			&[prefixes::TIP],
+			&Some(block).encode(),
+		);
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use sp_core::H256;
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+	use tempfile::tempdir;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	fn commitment(
+		number: u64,
+		hash: H256,
+		from: Vec<H160>,
+		to: Vec<H160>,
+	) -> TraceIndexCommitment<OpaqueBlock> {
+		TraceIndexCommitment {
+			block_hash: hash,
+			block_number: number,
+			from: from.into_iter().collect(),
+			to: to.into_iter().collect(),
+		}
+	}
+
+	#[test]
+	fn trace_index_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+		let backend = crate::Backend::<OpaqueBlock>::new(
+			client,
+			&crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("a temporary db was created");
+		let trace_index = backend.trace_index();
+		assert_eq!(trace_index.tip().unwrap(), None);
+
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let block = |number: u64| (number, H256::from_low_u64_be(number));
+
+		for number in 0..=TRACE_INDEX_BUCKET_SIZE {
+			let (from, to) = match number {
+				1 => (vec![alice], vec![bob]),
+				TRACE_INDEX_BUCKET_SIZE => (vec![bob], vec![alice]),
+				_ => (vec![], vec![]),
+			};
+			let (number, hash) = block(number);
+			trace_index
+				.write_block(commitment(number, hash, from, to))
+				.unwrap();
+		}
+		// Writing a block twice does not duplicate its entries.
+		trace_index
+			.write_block(commitment(1, block(1).1, vec![alice], vec![]))
+			.unwrap();
+		// A block of a retracted fork.
+		let forked = H256::repeat_byte(0xff);
+		trace_index
+			.write_block(commitment(2, forked, vec![alice], vec![]))
+			.unwrap();
+
+		assert_eq!(trace_index.tip().unwrap(), Some((2, forked)));
+		assert_eq!(
+			trace_index.indexed_blocks(0, 2).unwrap(),
+			vec![block(0), block(1), block(2), (2, forked)]
+		);
+		assert_eq!(
+			trace_index
+				.from_address_blocks(&alice, 0, TRACE_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![block(1), (2, forked)]
+		);
+		assert_eq!(
+			trace_index
+				.to_address_blocks(&alice, 0, TRACE_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![block(TRACE_INDEX_BUCKET_SIZE)]
+		);
+		assert_eq!(
+			trace_index
+				.to_address_blocks(&bob, 2, TRACE_INDEX_BUCKET_SIZE)
+				.unwrap(),
+			vec![]
+		);
+	}
+}
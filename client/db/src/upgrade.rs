@@ -34,11 +34,15 @@ use std::{
 const VERSION_FILE_NAME: &str = "db_version";
 
 /// Current db version.
-const CURRENT_VERSION: u32 = 2;
+const CURRENT_VERSION: u32 = 6;
 
 /// Number of columns in each version.
 const _V1_NUM_COLUMNS: u32 = 4;
 const V2_NUM_COLUMNS: u32 = 4;
+const V3_NUM_COLUMNS: u32 = 5;
+const V4_NUM_COLUMNS: u32 = 6;
+const V5_NUM_COLUMNS: u32 = 7;
+const V6_NUM_COLUMNS: u32 = 8;
 
 /// Database upgrade errors.
 #[derive(Debug)]
@@ -121,8 +125,23 @@ where
 				log::info!("✔️ Successful Frontier DB migration from version 1 to version 2 ({:?} entries).", summary.success);
 			}
 		}
-		CURRENT_VERSION => (),
-		_ => return Err(UpgradeError::FutureDatabaseVersion(db_version)),
+		version if version > CURRENT_VERSION => {
+			return Err(UpgradeError::FutureDatabaseVersion(db_version))
+		}
+		_ => (),
+	}
+	// Each following version only adds a column.
+	if db_version < 3 {
+		migrate_2_to_3(db_path, source)?;
+	}
+	if db_version < 4 {
+		migrate_3_to_4(db_path, source)?;
+	}
+	if db_version < 5 {
+		migrate_4_to_5(db_path, source)?;
+	}
+	if db_version < 6 {
+		migrate_5_to_6(db_path, source)?;
 	}
 	update_version(db_path)?;
 	Ok(())
@@ -227,8 +246,7 @@ where
 		Ok(())
 	};
 
-	let db_cfg = kvdb_rocksdb::DatabaseConfig::with_columns(V2_NUM_COLUMNS);
-	let db = kvdb_rocksdb::Database::open(&db_cfg, db_path)?;
+	let db = open_rocks_db(db_path, V2_NUM_COLUMNS)?;
 
 	// Get all the block hashes we need to update
 	let ethereum_hashes: Vec<_> = db
@@ -302,8 +320,7 @@ where
 		Ok(())
 	};
 
-	let mut db_cfg = parity_db::Options::with_columns(db_path, V2_NUM_COLUMNS as u8);
-	db_cfg.columns[crate::columns::BLOCK_MAPPING as usize].btree_index = true;
+	let db_cfg = parity_db_options(db_path, V2_NUM_COLUMNS)?;
 
 	let db = parity_db::Db::open_or_create(&db_cfg)
 		.map_err(|_| io::Error::new(ErrorKind::Other, "Failed to open db"))?;
@@ -328,6 +345,103 @@ where
 	Ok(res)
 }
 
+/// Opens the rocks db at `db_path` with the columns of a version. The columns of the following
+/// versions may already exist if an upgrade was interrupted, in which case all are opened.
+fn open_rocks_db(db_path: &Path, num_columns: u32) -> io::Result<kvdb_rocksdb::Database> {
+	let db_cfg = kvdb_rocksdb::DatabaseConfig::with_columns(num_columns);
+	kvdb_rocksdb::Database::open(&db_cfg, db_path).or_else(|_| {
+		let db_cfg = kvdb_rocksdb::DatabaseConfig::with_columns(crate::columns::NUM_COLUMNS);
+		kvdb_rocksdb::Database::open(&db_cfg, db_path)
+	})
+}
+
+/// Adds a column to the rocks db at `db_path`, from the columns of the previous version.
+fn add_rocks_db_column(db_path: &Path, num_columns: u32) -> UpgradeResult<()> {
+	let mut db = open_rocks_db(db_path, num_columns - 1)?;
+	if db.num_columns() < num_columns {
+		db.add_column()?;
+	}
+	Ok(())
+}
+
+/// Returns the options of the parity db at `db_path`, read from its metadata to open it with the
+/// columns it already has, or the columns of the given version if it does not exist yet.
+fn parity_db_options(db_path: &Path, num_columns: u32) -> UpgradeResult<parity_db::Options> {
+	let metadata = parity_db::Options::load_metadata(db_path)
+		.map_err(|_| io::Error::new(ErrorKind::Other, "Failed to load metadata"))?;
+	let mut db_cfg = parity_db::Options::with_columns(db_path, num_columns as u8);
+	match metadata {
+		Some(metadata) => db_cfg.columns = metadata.columns,
+		None => db_cfg.columns[crate::columns::BLOCK_MAPPING as usize].btree_index = true,
+	}
+	Ok(db_cfg)
+}
+
+/// Adds a column to the parity db at `db_path`, from the columns of the previous version.
+fn add_parity_db_column(db_path: &Path, num_columns: u32, btree_index: bool) -> UpgradeResult<()> {
+	let mut db_cfg = parity_db_options(db_path, num_columns - 1)?;
+	if db_cfg.columns.len() < num_columns as usize {
+		let column = parity_db::ColumnOptions {
+			btree_index,
+			..Default::default()
+		};
+		parity_db::Db::add_column(&mut db_cfg, column)
+			.map_err(|_| io::Error::new(ErrorKind::Other, "Failed to add column"))?;
+	}
+	debug_assert!(db_cfg.columns.len() >= num_columns as usize);
+	Ok(())
+}
+
+/// Migration from version2 to version3:
+/// - Add the log index column. Blocks synced before the migration are indexed by the log index
+///   backfill.
+pub(crate) fn migrate_2_to_3(db_path: &Path, source: &DatabaseSource) -> UpgradeResult<()> {
+	match source {
+		DatabaseSource::ParityDb { .. } => add_parity_db_column(db_path, V3_NUM_COLUMNS, true)?,
+		DatabaseSource::RocksDb { .. } => add_rocks_db_column(db_path, V3_NUM_COLUMNS)?,
+		_ => panic!("DatabaseSource required for upgrade ParityDb | RocksDb"),
+	}
+	log::info!("✔️ Successful Frontier DB migration from version 2 to version 3.");
+	Ok(())
+}
+
+/// Migration from version3 to version4:
+/// - Add the fee history column. It is backfilled by the fee history cache task.
+pub(crate) fn migrate_3_to_4(db_path: &Path, source: &DatabaseSource) -> UpgradeResult<()> {
+	match source {
+		DatabaseSource::ParityDb { .. } => add_parity_db_column(db_path, V4_NUM_COLUMNS, false)?,
+		DatabaseSource::RocksDb { .. } => add_rocks_db_column(db_path, V4_NUM_COLUMNS)?,
+		_ => panic!("DatabaseSource required for upgrade ParityDb | RocksDb"),
+	}
+	log::info!("✔️ Successful Frontier DB migration from version 3 to version 4.");
+	Ok(())
+}
+
+/// Migration from version4 to version5:
+/// - Add the trace index column. It is filled by the trace index worker, if enabled.
+pub(crate) fn migrate_4_to_5(db_path: &Path, source: &DatabaseSource) -> UpgradeResult<()> {
+	match source {
+		DatabaseSource::ParityDb { .. } => add_parity_db_column(db_path, V5_NUM_COLUMNS, false)?,
+		DatabaseSource::RocksDb { .. } => add_rocks_db_column(db_path, V5_NUM_COLUMNS)?,
+		_ => panic!("DatabaseSource required for upgrade ParityDb | RocksDb"),
+	}
+	log::info!("✔️ Successful Frontier DB migration from version 4 to version 5.");
+	Ok(())
+}
+
+/// Migration from version5 to version6:
+/// - Add the address index column. Blocks synced before the migration are indexed by the address
+///   index backfill.
+pub(crate) fn migrate_5_to_6(db_path: &Path, source: &DatabaseSource) -> UpgradeResult<()> {
+	match source {
+		DatabaseSource::ParityDb { .. } => add_parity_db_column(db_path, V6_NUM_COLUMNS, true)?,
+		DatabaseSource::RocksDb { .. } => add_rocks_db_column(db_path, V6_NUM_COLUMNS)?,
+		_ => panic!("DatabaseSource required for upgrade ParityDb | RocksDb"),
+	}
+	log::info!("✔️ Successful Frontier DB migration from version 5 to version 6.");
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use futures::executor;
@@ -365,6 +479,27 @@ mod tests {
 		)?))
 	}
 
+	/// Open a database with the columns of versions 1 and 2.
+	fn open_v2_database(
+		setting: &crate::DatabaseSettings,
+	) -> Arc<dyn crate::Database<crate::DbHash>> {
+		match &setting.source {
+			sc_client_db::DatabaseSource::RocksDb { path, .. } => {
+				let db_cfg = kvdb_rocksdb::DatabaseConfig::with_columns(super::V2_NUM_COLUMNS);
+				let db = kvdb_rocksdb::Database::open(&db_cfg, path).expect("rocksdb was opened");
+				sp_database::as_database(db)
+			}
+			sc_client_db::DatabaseSource::ParityDb { path } => {
+				let mut db_cfg =
+					parity_db::Options::with_columns(path, super::V2_NUM_COLUMNS as u8);
+				db_cfg.columns[crate::columns::BLOCK_MAPPING as usize].btree_index = true;
+				let db = parity_db::Db::open_or_create(&db_cfg).expect("paritydb was opened");
+				Arc::new(crate::parity_db_adapter::DbAdapter(db))
+			}
+			_ => unreachable!(),
+		}
+	}
+
 	#[test]
 	fn upgrade_1_to_2_works() {
 		let tmp_1 = tempdir().expect("create a temporary directory");
@@ -512,7 +647,126 @@ mod tests {
 			}
 
 			// Upgrade db version file
-			assert_eq!(super::current_version(&path).expect("version"), 2u32);
+			assert_eq!(super::current_version(&path).expect("version"), 6u32);
+		}
+	}
+
+	#[test]
+	fn upgrade_2_to_3_works() {
+		let tmp_1 = tempdir().expect("create a temporary directory");
+		let tmp_2 = tempdir().expect("create a temporary directory");
+
+		let settings = vec![
+			// Rocks db
+			crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp_1.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+			// Parity db
+			crate::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::ParityDb {
+					path: tmp_2.path().to_owned(),
+				},
+			},
+		];
+
+		for setting in settings {
+			let (client, _) = TestClientBuilder::new()
+				.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+				None,
+			);
+			let client = Arc::new(client);
+			let path = setting.source.path().unwrap();
+
+			let ethereum_hash = H256::random();
+			let substrate_hash = H256::random();
+			{
+				let db = open_v2_database(&setting);
+				let mut transaction = sp_database::Transaction::new();
+				transaction.set(
+					crate::columns::BLOCK_MAPPING,
+					&ethereum_hash.encode(),
+					&vec![substrate_hash].encode(),
+				);
+				let _ = db.commit(transaction);
+			}
+
+			// Writes version 2 to file.
+			let _ = std::fs::create_dir_all(&path).expect("db path created");
+			let mut version_path = path.to_owned();
+			version_path.push("db_version");
+			let mut version_file =
+				std::fs::File::create(version_path).expect("db version file path created");
+			version_file
+				.write_all(format!("{}", 2).as_bytes())
+				.expect("write version 2");
+
+			// Upgrade database from version 2 to 6
+			super::upgrade_db::<OpaqueBlock, _>(client.clone(), &path, &setting.source)
+				.expect("upgrade succeeded");
+			assert_eq!(super::current_version(&path).expect("version"), 6u32);
+
+			// Existing data is kept and the added columns are usable.
+			let backend =
+				open_frontier_backend(client, &setting).expect("a temporary db was created");
+			assert_eq!(
+				backend.mapping().block_hash(&ethereum_hash).unwrap(),
+				Some(vec![substrate_hash])
+			);
+			backend
+				.log_index()
+				.write_logs(crate::LogIndexCommitment {
+					block_hash: substrate_hash,
+					block_number: 1,
+					logs: vec![],
+				})
+				.unwrap();
+			let canonical = |_| Ok(Some(substrate_hash));
+			assert!(backend.log_index().is_indexed(1, 1, canonical).unwrap());
+			assert!(!backend.log_index().is_indexed(0, 1, canonical).unwrap());
+			let entry = crate::FeeHistoryEntry {
+				block_hash: substrate_hash,
+				base_fee: 1,
+				gas_used: 0,
+				gas_limit: 1,
+				rewards: vec![],
+				lowest_rewards: vec![],
+			};
+			backend
+				.fee_history()
+				.write_entry(1, entry.clone(), 0)
+				.unwrap();
+			assert_eq!(backend.fee_history().entry(1).unwrap(), Some(entry));
+			backend
+				.trace_index()
+				.write_block(crate::TraceIndexCommitment {
+					block_hash: substrate_hash,
+					block_number: 1,
+					from: Default::default(),
+					to: Default::default(),
+				})
+				.unwrap();
+			assert_eq!(
+				backend.trace_index().tip().unwrap(),
+				Some((1, substrate_hash))
+			);
+			let address = sp_core::H160::repeat_byte(0xaa);
+			backend
+				.address_index()
+				.write_block(crate::AddressIndexCommitment {
+					block_hash: substrate_hash,
+					block_number: 1,
+					transactions: vec![crate::IndexedTransaction {
+						hash: H256::repeat_byte(1),
+						from: address,
+						to: None,
+						contract_address: None,
+					}],
+				})
+				.unwrap();
+			assert_eq!(backend.address_index().buckets(&address).unwrap(), vec![0]);
 		}
 	}
 
@@ -540,6 +794,6 @@ mod tests {
 
 		let mut s = String::new();
 		file.read_to_string(&mut s).expect("read file contents");
-		assert_eq!(s.parse::<u32>().expect("parse file contents"), 2u32);
+		assert_eq!(s.parse::<u32>().expect("parse file contents"), 6u32);
 	}
 }
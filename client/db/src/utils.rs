@@ -20,16 +20,19 @@ use std::{path::Path, sync::Arc};
 
 use sp_runtime::traits::Block as BlockT;
 
-use crate::{Database, DatabaseSettings, DatabaseSource, DbHash};
+use crate::{Database, DatabaseIter, DatabaseSettings, DatabaseSource, DbHash};
 
-pub fn open_database<Block: BlockT, C>(
+/// Opened database, along with the iteration of its columns.
+type OpenedDatabase = (Arc<dyn Database<DbHash>>, Arc<dyn DatabaseIter>);
+
+pub(crate) fn open_database<Block: BlockT, C>(
 	client: Arc<C>,
 	config: &DatabaseSettings,
-) -> Result<Arc<dyn Database<DbHash>>, String>
+) -> Result<OpenedDatabase, String>
 where
 	C: sp_blockchain::HeaderBackend<Block> + Send + Sync,
 {
-	let db: Arc<dyn Database<DbHash>> = match &config.source {
+	let db = match &config.source {
 		DatabaseSource::ParityDb { path } => {
 			open_parity_db::<Block, C>(client, path, &config.source)?
 		}
@@ -58,7 +61,7 @@ fn open_kvdb_rocksdb<Block: BlockT, C>(
 	path: &Path,
 	create: bool,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String>
+) -> Result<OpenedDatabase, String>
 where
 	C: sp_blockchain::HeaderBackend<Block> + Send + Sync,
 {
@@ -76,7 +79,61 @@ where
 	// write database version only after the database is succesfully opened
 	#[cfg(not(test))]
 	crate::upgrade::update_version(path).map_err(|_| "Cannot update db version".to_string())?;
-	return Ok(sp_database::as_database(db));
+	let db = Arc::new(db);
+	Ok((
+		sp_database::as_database(SharedRocksDb(db.clone())),
+		Arc::new(SharedRocksDb(db)),
+	))
+}
+
+/// Rocksdb database shared by the `sp_database` adapter and the iteration of the indexes.
+#[cfg(feature = "kvdb-rocksdb")]
+struct SharedRocksDb(Arc<kvdb_rocksdb::Database>);
+
+#[cfg(feature = "kvdb-rocksdb")]
+impl kvdb::KeyValueDB for SharedRocksDb {
+	fn get(&self, col: u32, key: &[u8]) -> std::io::Result<Option<kvdb::DBValue>> {
+		kvdb::KeyValueDB::get(&*self.0, col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> std::io::Result<Option<kvdb::DBValue>> {
+		kvdb::KeyValueDB::get_by_prefix(&*self.0, col, prefix)
+	}
+
+	fn write(&self, transaction: kvdb::DBTransaction) -> std::io::Result<()> {
+		kvdb::KeyValueDB::write(&*self.0, transaction)
+	}
+
+	fn iter<'a>(
+		&'a self,
+		col: u32,
+	) -> Box<dyn Iterator<Item = std::io::Result<kvdb::DBKeyValue>> + 'a> {
+		kvdb::KeyValueDB::iter(&*self.0, col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = std::io::Result<kvdb::DBKeyValue>> + 'a> {
+		kvdb::KeyValueDB::iter_with_prefix(&*self.0, col, prefix)
+	}
+}
+
+#[cfg(feature = "kvdb-rocksdb")]
+impl DatabaseIter for SharedRocksDb {
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a> {
+		Box::new(
+			kvdb::KeyValueDB::iter_with_prefix(&*self.0, col, prefix).map(|entry| match entry {
+				Ok((key, value)) => (key.to_vec(), value),
+				Err(e) => panic!("Critical database error: {:?}", e),
+			}),
+		)
+	}
 }
 
 #[cfg(not(feature = "kvdb-rocksdb"))]
@@ -85,7 +142,7 @@ fn open_kvdb_rocksdb<Block: BlockT, C>(
 	_path: &Path,
 	_create: bool,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String>
+) -> Result<OpenedDatabase, String>
 where
 	C: sp_blockchain::HeaderBackend<Block> + Send + Sync,
 {
@@ -97,7 +154,7 @@ fn open_parity_db<Block: BlockT, C>(
 	client: Arc<C>,
 	path: &Path,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String>
+) -> Result<OpenedDatabase, String>
 where
 	C: sp_blockchain::HeaderBackend<Block> + Send + Sync,
 {
@@ -109,12 +166,16 @@ where
 	}
 	let mut config = parity_db::Options::with_columns(path, crate::columns::NUM_COLUMNS as u8);
 	config.columns[crate::columns::BLOCK_MAPPING as usize].btree_index = true;
+	// The indexes are read by prefix iteration.
+	config.columns[crate::columns::LOG_INDEX as usize].btree_index = true;
+	config.columns[crate::columns::ADDRESS_INDEX as usize].btree_index = true;
 
 	let db = parity_db::Db::open_or_create(&config).map_err(|err| format!("{}", err))?;
 	// write database version only after the database is succesfully opened
 	#[cfg(not(test))]
 	crate::upgrade::update_version(path).map_err(|_| "Cannot update db version".to_string())?;
-	Ok(Arc::new(crate::parity_db_adapter::DbAdapter(db)))
+	let db = Arc::new(crate::parity_db_adapter::DbAdapter(db));
+	Ok((db.clone(), db))
 }
 
 #[cfg(not(feature = "parity-db"))]
@@ -122,7 +183,7 @@ fn open_parity_db<Block: BlockT, C>(
 	_client: Arc<C>,
 	_path: &Path,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String>
+) -> Result<OpenedDatabase, String>
 where
 	C: sp_blockchain::HeaderBackend<Block> + Send + Sync,
 {
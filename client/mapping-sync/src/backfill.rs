@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, pin::Pin, sync::Arc, time::Duration};
+
+use futures::{
+	prelude::*,
+	task::{Context, Poll},
+};
+use futures_timer::Delay;
+use log::debug;
+// Substrate
+use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+};
+// Frontier
+use fc_rpc::OverrideHandle;
+
+/// Indexes the canonical blocks synced before the log and address indexes of the frontier db
+/// existed, from the genesis up to the best block at the time the backfill reaches it.
+pub struct IndexBackfillWorker<Block: BlockT, C, BE> {
+	timeout: Duration,
+	inner_delay: Option<Delay>,
+
+	client: Arc<C>,
+	overrides: Arc<OverrideHandle<Block>>,
+	frontier_backend: Arc<fc_db::Backend<Block>>,
+
+	have_next: bool,
+	batch_size: u64,
+	_marker: PhantomData<BE>,
+}
+
+impl<Block: BlockT, C, BE> Unpin for IndexBackfillWorker<Block, C, BE> {}
+
+impl<Block: BlockT, C, BE> IndexBackfillWorker<Block, C, BE> {
+	pub fn new(
+		timeout: Duration,
+		client: Arc<C>,
+		overrides: Arc<OverrideHandle<Block>>,
+		frontier_backend: Arc<fc_db::Backend<Block>>,
+		batch_size: u64,
+	) -> Self {
+		Self {
+			timeout,
+			inner_delay: None,
+
+			client,
+			overrides,
+			frontier_backend,
+
+			have_next: true,
+			batch_size,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<Block, C, BE> Stream for IndexBackfillWorker<Block, C, BE>
+where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + Send + Sync + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	type Item = ();
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<()>> {
+		if !self.have_next {
+			return Poll::Ready(None);
+		}
+
+		// Leaves room to the block import between two batches.
+		let timeout = self.timeout;
+		let inner_delay = self.inner_delay.get_or_insert_with(|| Delay::new(timeout));
+		if Future::poll(Pin::new(inner_delay), cx).is_pending() {
+			return Poll::Pending;
+		}
+		self.inner_delay = None;
+
+		match backfill_blocks::<Block, C, BE>(
+			self.client.as_ref(),
+			self.overrides.as_ref(),
+			self.frontier_backend.as_ref(),
+			self.batch_size,
+		) {
+			Ok(have_next) => {
+				self.have_next = have_next;
+			}
+			Err(e) => {
+				debug!(target: "index-backfill", "Backfill failed with error {:?}, retrying.", e);
+			}
+		}
+		Poll::Ready(Some(()))
+	}
+}
+
+fn backfill_block<Block, C, BE>(
+	client: &C,
+	overrides: &OverrideHandle<Block>,
+	backend: &fc_db::Backend<Block>,
+	number: u64,
+	log_index: bool,
+	address_index: bool,
+) -> Result<(), String>
+where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + Send + Sync + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let hash = match client
+		.hash(number.unique_saturated_into())
+		.map_err(|e| format!("{:?}", e))?
+	{
+		Some(hash) => hash,
+		None => return Ok(()),
+	};
+	let log_index = log_index && !backend.log_index().is_block_indexed(number, &hash);
+	let address_index = address_index && !backend.address_index().is_block_indexed(number, &hash);
+	if !log_index && !address_index {
+		return Ok(());
+	}
+
+	let header = client
+		.header(BlockId::Hash(hash))
+		.map_err(|e| format!("{:?}", e))?
+		.ok_or_else(|| format!("Header not found for block {}", number))?;
+	let statuses = if fp_consensus::find_log(header.digest()).is_ok() {
+		// Without the state of the block, it is left out of the indexes and the queries fall
+		// back to scanning it.
+		match crate::transaction_statuses::<Block, C, BE>(client, overrides, hash) {
+			Some(statuses) => statuses,
+			None => {
+				debug!(
+					target: "index-backfill",
+					"Transaction statuses of block {:?} not available, not indexing it.",
+					hash,
+				);
+				return Ok(());
+			}
+		}
+	} else {
+		Vec::new()
+	};
+	if address_index {
+		crate::sync_address_index(backend, &header, &statuses)?;
+	}
+	if log_index {
+		crate::sync_log_index(backend, &header, statuses)?;
+	}
+	Ok(())
+}
+
+/// Backfills up to `batch_size` blocks of each index, returning whether more blocks are waiting.
+fn backfill_blocks<Block, C, BE>(
+	client: &C,
+	overrides: &OverrideHandle<Block>,
+	backend: &fc_db::Backend<Block>,
+	batch_size: u64,
+) -> Result<bool, String>
+where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + Send + Sync + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let best: u64 = client.info().best_number.unique_saturated_into();
+	let log_index_next = backend.log_index().backfilled()?;
+	let address_index_next = backend.address_index().backfilled()?;
+	let next = log_index_next.min(address_index_next);
+	if next > best {
+		return Ok(false);
+	}
+
+	let last = best.min(next.saturating_add(batch_size.max(1) - 1));
+	for number in next..=last {
+		backfill_block::<Block, C, BE>(
+			client,
+			overrides,
+			backend,
+			number,
+			number >= log_index_next,
+			number >= address_index_next,
+		)?;
+	}
+	if log_index_next <= last {
+		backend.log_index().set_backfilled(last + 1)?;
+	}
+	if address_index_next <= last {
+		backend.address_index().set_backfilled(last + 1)?;
+	}
+	Ok(last < best)
+}
@@ -18,28 +18,125 @@
 
 #![allow(clippy::too_many_arguments)]
 
+mod backfill;
+mod trace;
 mod worker;
 
-pub use worker::{MappingSyncWorker, SyncStrategy};
+pub use self::{
+	backfill::IndexBackfillWorker,
+	trace::TraceIndexWorker,
+	worker::{MappingSyncWorker, SyncStrategy},
+};
 
+use log::debug;
 // Substrate
-use sc_client_api::BlockOf;
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockOf,
+};
 use sp_api::{ApiExt, ProvideRuntimeApi};
-use sp_blockchain::HeaderBackend;
+use sp_blockchain::{Backend as _, HeaderBackend};
+use sp_core::H256;
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, Header as HeaderT, Zero},
+	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT, UniqueSaturatedInto, Zero},
 };
 // Frontier
+use fc_rpc::{frontier_backend_client, OverrideHandle};
 use fp_consensus::FindLogError;
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+
+/// Reads the transaction statuses of a block from its state through the storage override of its
+/// schema, `None` if the state is not available, e.g. pruned.
+pub(crate) fn transaction_statuses<Block, C, BE>(
+	client: &C,
+	overrides: &OverrideHandle<Block>,
+	hash: Block::Hash,
+) -> Option<Vec<TransactionStatus>>
+where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + Send + Sync + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let id = BlockId::Hash(hash);
+	let schema = frontier_backend_client::onchain_storage_schema::<Block, C, BE>(client, id);
+	overrides
+		.schemas
+		.get(&schema)
+		.unwrap_or(&overrides.fallback)
+		.current_transaction_statuses(&id)
+}
 
-pub fn sync_block<Block: BlockT>(
+fn sync_log_index<Block: BlockT>(
 	backend: &fc_db::Backend<Block>,
 	header: &Block::Header,
+	statuses: Vec<TransactionStatus>,
 ) -> Result<(), String> {
+	let logs = statuses
+		.into_iter()
+		.flat_map(|status| status.logs)
+		.map(|log| (log.address, log.topics))
+		.collect();
+	backend.log_index().write_logs(fc_db::LogIndexCommitment {
+		block_hash: header.hash(),
+		block_number: UniqueSaturatedInto::<u64>::unique_saturated_into(*header.number()),
+		logs,
+	})
+}
+
+fn sync_address_index<Block: BlockT>(
+	backend: &fc_db::Backend<Block>,
+	header: &Block::Header,
+	statuses: &[TransactionStatus],
+) -> Result<(), String> {
+	let transactions = statuses
+		.iter()
+		.map(|status| fc_db::IndexedTransaction {
+			hash: status.transaction_hash,
+			from: status.from,
+			to: status.to,
+			contract_address: status.contract_address,
+		})
+		.collect();
+	backend
+		.address_index()
+		.write_block(fc_db::AddressIndexCommitment {
+			block_hash: header.hash(),
+			block_number: UniqueSaturatedInto::<u64>::unique_saturated_into(*header.number()),
+			transactions,
+		})
+}
+
+pub fn sync_block<Block, C, BE>(
+	client: &C,
+	overrides: &OverrideHandle<Block>,
+	backend: &fc_db::Backend<Block>,
+	header: &Block::Header,
+) -> Result<(), String>
+where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + Send + Sync + 'static,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
 	match fp_consensus::find_log(header.digest()) {
 		Ok(log) => {
+			// Index the transactions and logs first, so that a block is never marked as synced
+			// without them. Without the state of the block, it is left out of the indexes and
+			// the queries fall back to scanning it.
+			match transaction_statuses::<Block, C, BE>(client, overrides, header.hash()) {
+				Some(statuses) => {
+					sync_address_index(backend, header, &statuses)?;
+					sync_log_index(backend, header, statuses)?;
+				}
+				None => debug!(
+					target: "mapping-sync",
+					"Transaction statuses of block {:?} not available, not indexing it.",
+					header.hash(),
+				),
+			}
+
 			let post_hashes = log.into_hashes();
 
 			let mapping_commitment = fc_db::MappingCommitment {
@@ -52,6 +149,7 @@ pub fn sync_block<Block: BlockT>(
 			Ok(())
 		}
 		Err(FindLogError::NotFound) => {
+			sync_log_index(backend, header, Vec::new())?;
 			backend.mapping().write_none(header.hash())?;
 
 			Ok(())
@@ -71,6 +169,8 @@ where
 {
 	let id = BlockId::Hash(header.hash());
 
+	sync_log_index(backend, header, Vec::new())?;
+
 	if let Some(api_version) = client
 		.runtime_api()
 		.api_version::<dyn EthereumRuntimeRPCApi<Block>>(&id)
@@ -106,22 +206,29 @@ where
 	Ok(())
 }
 
-pub fn sync_one_block<Block: BlockT, C, B>(
+pub fn sync_one_block<Block, C, BE>(
 	client: &C,
-	substrate_backend: &B,
+	substrate_backend: &BE,
+	overrides: &OverrideHandle<Block>,
 	frontier_backend: &fc_db::Backend<Block>,
 	sync_from: <Block::Header as HeaderT>::Number,
 	strategy: SyncStrategy,
 ) -> Result<bool, String>
 where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
 	C: ProvideRuntimeApi<Block> + Send + Sync + HeaderBackend<Block> + BlockOf,
+	C: StorageProvider<Block, BE> + 'static,
 	C::Api: EthereumRuntimeRPCApi<Block>,
-	B: sp_blockchain::HeaderBackend<Block> + sp_blockchain::Backend<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
 {
 	let mut current_syncing_tips = frontier_backend.meta().current_syncing_tips()?;
 
 	if current_syncing_tips.is_empty() {
-		let mut leaves = substrate_backend.leaves().map_err(|e| format!("{:?}", e))?;
+		let mut leaves = substrate_backend
+			.blockchain()
+			.leaves()
+			.map_err(|e| format!("{:?}", e))?;
 		if leaves.is_empty() {
 			return Ok(false);
 		}
@@ -130,9 +237,12 @@ where
 
 	let mut operating_header = None;
 	while let Some(checking_tip) = current_syncing_tips.pop() {
-		if let Some(checking_header) =
-			fetch_header(substrate_backend, frontier_backend, checking_tip, sync_from)?
-		{
+		if let Some(checking_header) = fetch_header(
+			substrate_backend.blockchain(),
+			frontier_backend,
+			checking_tip,
+			sync_from,
+		)? {
 			operating_header = Some(checking_header);
 			break;
 		}
@@ -160,7 +270,7 @@ where
 		{
 			return Ok(false);
 		}
-		sync_block(frontier_backend, &operating_header)?;
+		sync_block::<Block, C, BE>(client, overrides, frontier_backend, &operating_header)?;
 
 		current_syncing_tips.push(*operating_header.parent_hash());
 		frontier_backend
@@ -170,18 +280,22 @@ where
 	}
 }
 
-pub fn sync_blocks<Block: BlockT, C, B>(
+pub fn sync_blocks<Block, C, BE>(
 	client: &C,
-	substrate_backend: &B,
+	substrate_backend: &BE,
+	overrides: &OverrideHandle<Block>,
 	frontier_backend: &fc_db::Backend<Block>,
 	limit: usize,
 	sync_from: <Block::Header as HeaderT>::Number,
 	strategy: SyncStrategy,
 ) -> Result<bool, String>
 where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
 	C: ProvideRuntimeApi<Block> + Send + Sync + HeaderBackend<Block> + BlockOf,
+	C: StorageProvider<Block, BE> + 'static,
 	C::Api: EthereumRuntimeRPCApi<Block>,
-	B: sp_blockchain::HeaderBackend<Block> + sp_blockchain::Backend<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
 {
 	let mut synced_any = false;
 
@@ -190,6 +304,7 @@ where
 			|| sync_one_block(
 				client,
 				substrate_backend,
+				overrides,
 				frontier_backend,
 				sync_from,
 				strategy,
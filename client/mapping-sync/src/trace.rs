@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, pin::Pin, sync::Arc, time::Duration};
+
+use futures::{
+	prelude::*,
+	task::{Context, Poll},
+};
+use futures_timer::Delay;
+use log::debug;
+// Substrate
+use sc_client_api::{BlockBackend, ImportNotifications};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::H160;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+};
+// Frontier
+use fp_evm::{CallFrame, TracerKind, TransactionTrace};
+use fp_rpc::DebugRuntimeApi;
+
+/// Fills the trace index of the frontier db by replaying the canonical blocks with a call tracer.
+pub struct TraceIndexWorker<Block: BlockT, C> {
+	import_notifications: ImportNotifications<Block>,
+	timeout: Duration,
+	inner_delay: Option<Delay>,
+
+	client: Arc<C>,
+	frontier_backend: Arc<fc_db::Backend<Block>>,
+
+	have_next: bool,
+	batch_size: u64,
+	sync_from: <Block::Header as HeaderT>::Number,
+}
+
+impl<Block: BlockT, C> Unpin for TraceIndexWorker<Block, C> {}
+
+impl<Block: BlockT, C> TraceIndexWorker<Block, C> {
+	pub fn new(
+		import_notifications: ImportNotifications<Block>,
+		timeout: Duration,
+		client: Arc<C>,
+		frontier_backend: Arc<fc_db::Backend<Block>>,
+		batch_size: u64,
+		sync_from: <Block::Header as HeaderT>::Number,
+	) -> Self {
+		Self {
+			import_notifications,
+			timeout,
+			inner_delay: None,
+
+			client,
+			frontier_backend,
+
+			have_next: true,
+			batch_size,
+			sync_from,
+		}
+	}
+}
+
+impl<Block: BlockT, C> Stream for TraceIndexWorker<Block, C>
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block> + Send + Sync,
+	C::Api: DebugRuntimeApi<Block>,
+{
+	type Item = ();
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<()>> {
+		let mut fire = false;
+
+		loop {
+			match Stream::poll_next(Pin::new(&mut self.import_notifications), cx) {
+				Poll::Pending => break,
+				Poll::Ready(Some(_)) => {
+					fire = true;
+				}
+				Poll::Ready(None) => return Poll::Ready(None),
+			}
+		}
+
+		let timeout = self.timeout;
+		let inner_delay = self.inner_delay.get_or_insert_with(|| Delay::new(timeout));
+
+		match Future::poll(Pin::new(inner_delay), cx) {
+			Poll::Pending => (),
+			Poll::Ready(()) => {
+				fire = true;
+			}
+		}
+
+		if self.have_next {
+			fire = true;
+		}
+
+		if fire {
+			self.inner_delay = None;
+
+			match index_blocks(
+				self.client.as_ref(),
+				self.frontier_backend.as_ref(),
+				self.batch_size,
+				self.sync_from,
+			) {
+				Ok(have_next) => {
+					self.have_next = have_next;
+					Poll::Ready(Some(()))
+				}
+				Err(e) => {
+					self.have_next = false;
+					debug!(target: "trace-index", "Indexing failed with error {:?}, retrying.", e);
+					Poll::Ready(Some(()))
+				}
+			}
+		} else {
+			Poll::Pending
+		}
+	}
+}
+
+/// Whether the canonical block at `number` is in the trace index.
+fn is_indexed<Block: BlockT, C>(
+	client: &C,
+	backend: &fc_db::Backend<Block>,
+	number: u64,
+) -> Result<bool, String>
+where
+	C: HeaderBackend<Block>,
+{
+	let canonical_hash = client
+		.hash(number.unique_saturated_into())
+		.map_err(|e| format!("{:?}", e))?;
+	Ok(backend
+		.trace_index()
+		.indexed_blocks(number, number)?
+		.into_iter()
+		.any(|(_, hash)| Some(hash) == canonical_hash))
+}
+
+/// Number of the first canonical block missing from the trace index, rewinding past the blocks of
+/// retracted forks.
+fn next_block<Block: BlockT, C>(
+	client: &C,
+	backend: &fc_db::Backend<Block>,
+	sync_from: u64,
+) -> Result<u64, String>
+where
+	C: HeaderBackend<Block>,
+{
+	let mut number = match backend.trace_index().tip()? {
+		Some((number, _)) if number >= sync_from => number,
+		_ => return Ok(sync_from),
+	};
+	while !is_indexed(client, backend, number)? {
+		if number == sync_from {
+			return Ok(sync_from);
+		}
+		number -= 1;
+	}
+	Ok(number + 1)
+}
+
+fn collect_addresses(frame: &CallFrame, from: &mut BTreeSet<H160>, to: &mut BTreeSet<H160>) {
+	from.insert(frame.from);
+	if let Some(address) = frame.to {
+		to.insert(address);
+	}
+	for call in &frame.calls {
+		collect_addresses(call, from, to);
+	}
+}
+
+fn index_block<Block: BlockT, C>(
+	client: &C,
+	backend: &fc_db::Backend<Block>,
+	number: u64,
+) -> Result<(), String>
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+	C::Api: DebugRuntimeApi<Block>,
+{
+	let id = BlockId::Number(number.unique_saturated_into());
+	let header = client
+		.header(id)
+		.map_err(|e| format!("{:?}", e))?
+		.ok_or_else(|| format!("Header not found for block {}", number))?;
+
+	let mut from = BTreeSet::new();
+	let mut to = BTreeSet::new();
+	// The genesis block has no transaction to replay.
+	if number > 0 {
+		let extrinsics = client
+			.block_body(header.hash())
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or_else(|| format!("Body not found for block {}", number))?;
+		let parent = BlockId::Hash(*header.parent_hash());
+		let api = client.runtime_api();
+		// Blocks of runtimes without tracing support, either without the debug API or built
+		// without tracing, are indexed without any address.
+		if api
+			.has_api::<dyn DebugRuntimeApi<Block>>(&parent)
+			.map_err(|e| format!("{:?}", e))?
+		{
+			api.initialize_block(&parent, &header)
+				.map_err(|e| format!("{:?}", e))?;
+			let traces = api
+				.trace_block(&parent, extrinsics, TracerKind::CallTracer)
+				.map_err(|e| format!("{:?}", e))?
+				.unwrap_or_default();
+			for (_, trace) in traces {
+				if let TransactionTrace::CallTracer(frame) = trace {
+					collect_addresses(&frame, &mut from, &mut to);
+				}
+			}
+		}
+	}
+
+	backend
+		.trace_index()
+		.write_block(fc_db::TraceIndexCommitment {
+			block_hash: header.hash(),
+			block_number: number,
+			from,
+			to,
+		})
+}
+
+/// Indexes up to `batch_size` canonical blocks, returning whether more blocks are waiting.
+fn index_blocks<Block: BlockT, C>(
+	client: &C,
+	backend: &fc_db::Backend<Block>,
+	batch_size: u64,
+	sync_from: <Block::Header as HeaderT>::Number,
+) -> Result<bool, String>
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+	C::Api: DebugRuntimeApi<Block>,
+{
+	let best: u64 = client.info().best_number.unique_saturated_into();
+	let next = next_block(client, backend, sync_from.unique_saturated_into())?;
+	if next > best {
+		return Ok(false);
+	}
+
+	let last = best.min(next.saturating_add(batch_size.max(1) - 1));
+	for number in next..=last {
+		index_block(client, backend, number)?;
+	}
+	Ok(last < best)
+}
@@ -25,11 +25,16 @@ use futures::{
 use futures_timer::Delay;
 use log::debug;
 // Substrate
-use sc_client_api::{BlockOf, ImportNotifications};
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockOf, ImportNotifications,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Header as HeaderT};
 // Frontier
+use fc_rpc::OverrideHandle;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -45,6 +50,7 @@ pub struct MappingSyncWorker<Block: BlockT, C, B> {
 
 	client: Arc<C>,
 	substrate_backend: Arc<B>,
+	overrides: Arc<OverrideHandle<Block>>,
 	frontier_backend: Arc<fc_db::Backend<Block>>,
 
 	have_next: bool,
@@ -61,6 +67,7 @@ impl<Block: BlockT, C, B> MappingSyncWorker<Block, C, B> {
 		timeout: Duration,
 		client: Arc<C>,
 		substrate_backend: Arc<B>,
+		overrides: Arc<OverrideHandle<Block>>,
 		frontier_backend: Arc<fc_db::Backend<Block>>,
 		retry_times: usize,
 		sync_from: <Block::Header as HeaderT>::Number,
@@ -73,6 +80,7 @@ impl<Block: BlockT, C, B> MappingSyncWorker<Block, C, B> {
 
 			client,
 			substrate_backend,
+			overrides,
 			frontier_backend,
 
 			have_next: true,
@@ -83,11 +91,14 @@ impl<Block: BlockT, C, B> MappingSyncWorker<Block, C, B> {
 	}
 }
 
-impl<Block: BlockT, C, B> Stream for MappingSyncWorker<Block, C, B>
+impl<Block, C, B> Stream for MappingSyncWorker<Block, C, B>
 where
+	Block: BlockT<Hash = H256> + Send + Sync + 'static,
 	C: ProvideRuntimeApi<Block> + Send + Sync + HeaderBackend<Block> + BlockOf,
+	C: StorageProvider<Block, B> + 'static,
 	C::Api: EthereumRuntimeRPCApi<Block>,
-	B: sc_client_api::Backend<Block>,
+	B: Backend<Block> + 'static,
+	B::State: StateBackend<BlakeTwo256>,
 {
 	type Item = ();
 
@@ -123,7 +134,8 @@ where
 
 			match crate::sync_blocks(
 				self.client.as_ref(),
-				self.substrate_backend.blockchain(),
+				self.substrate_backend.as_ref(),
+				self.overrides.as_ref(),
 				self.frontier_backend.as_ref(),
 				self.retry_times,
 				self.sync_from,
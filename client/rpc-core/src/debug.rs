@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2015-2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Debug rpc interface.
+
+use ethereum_types::H256;
+use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
+
+use crate::types::*;
+
+/// Debug rpc interface.
+#[rpc(server)]
+#[async_trait]
+pub trait DebugApi {
+	/// Replays the transaction with given hash and returns its trace.
+	#[method(name = "debug_traceTransaction")]
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> Result<TraceResult>;
+
+	/// Replays all the transactions of the block with given number and returns their traces.
+	#[method(name = "debug_traceBlockByNumber")]
+	async fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTraceResult>>;
+
+	/// Replays all the transactions of the block with given hash and returns their traces.
+	#[method(name = "debug_traceBlockByHash")]
+	async fn trace_block_by_hash(
+		&self,
+		hash: H256,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTraceResult>>;
+
+	/// Returns the EIP-2718 encoding of the transaction with given hash.
+	#[method(name = "debug_getRawTransaction")]
+	async fn get_raw_transaction(&self, transaction_hash: H256) -> Result<Option<Bytes>>;
+
+	/// Returns the RLP encoding of the header of the block with given number or hash.
+	#[method(name = "debug_getRawHeader")]
+	async fn get_raw_header(&self, number: BlockNumberOrHash) -> Result<Option<Bytes>>;
+
+	/// Returns the RLP encoding of the block with given number or hash.
+	#[method(name = "debug_getRawBlock")]
+	async fn get_raw_block(&self, number: BlockNumberOrHash) -> Result<Option<Bytes>>;
+
+	/// Returns the EIP-2718 encodings of the receipts of the block with given number or hash.
+	#[method(name = "debug_getRawReceipts")]
+	async fn get_raw_receipts(&self, number: BlockNumberOrHash) -> Result<Option<Vec<Bytes>>>;
+}
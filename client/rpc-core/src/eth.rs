@@ -125,6 +125,10 @@ pub trait EthApi {
 	#[method(name = "eth_getTransactionReceipt")]
 	async fn transaction_receipt(&self, hash: H256) -> Result<Option<Receipt>>;
 
+	/// Returns the receipts of all the transactions of a block.
+	#[method(name = "eth_getBlockReceipts")]
+	async fn block_receipts(&self, number: BlockNumberOrHash) -> Result<Option<Vec<Receipt>>>;
+
 	// ########################################################################
 	// State
 	// ########################################################################
@@ -145,18 +149,55 @@ pub trait EthApi {
 	#[method(name = "eth_getCode")]
 	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
 
+	/// Returns the account and storage values of given address at given time (block number),
+	/// along with the Substrate storage proofs of those values.
+	#[method(name = "eth_getProof")]
+	fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number: Option<BlockNumber>,
+	) -> Result<EthAccount>;
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
 
 	/// Call contract, returning the output data.
 	#[method(name = "eth_call")]
-	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+	fn call(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Bytes>;
 
 	/// Estimate gas needed for execution of given contract.
 	#[method(name = "eth_estimateGas")]
-	async fn estimate_gas(&self, request: CallRequest, number: Option<BlockNumber>)
-		-> Result<U256>;
+	async fn estimate_gas(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumberOrHash>,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<U256>;
+
+	/// Executes bundles of calls in order on top of the same block, each call seeing the state
+	/// changes of the previous ones, and returns the result of each call.
+	#[method(name = "eth_callMany")]
+	fn call_many(
+		&self,
+		bundles: Vec<CallBundle>,
+		context: CallManyContext,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Vec<Vec<CallManyResult>>>;
+
+	/// Generates an access list for a transaction, along with the gas it uses with that list.
+	#[method(name = "eth_createAccessList")]
+	fn create_access_list(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+	) -> Result<AccessListResult>;
 
 	// ########################################################################
 	// Fee
@@ -216,6 +257,36 @@ pub trait EthApi {
 	/// Sends signed transaction, returning its hash.
 	#[method(name = "eth_sendRawTransaction")]
 	async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256>;
+
+	/// Sends signed transaction and waits for its receipt, for at most `timeout` milliseconds.
+	#[method(name = "eth_sendRawTransactionSync")]
+	async fn send_raw_transaction_sync(
+		&self,
+		bytes: Bytes,
+		timeout: Option<u64>,
+	) -> Result<Receipt>;
+
+	// ########################################################################
+	// Sign
+	// ########################################################################
+
+	/// Signs data prefixed as an EIP-191 personal message with the given account.
+	#[method(name = "eth_sign")]
+	async fn sign(&self, address: H160, data: Bytes) -> Result<Bytes>;
+
+	/// Signs EIP-712 typed data with the given account.
+	#[method(name = "eth_signTypedData_v4")]
+	async fn sign_typed_data_v4(&self, address: H160, typed_data: TypedData) -> Result<Bytes>;
+
+	/// Signs data prefixed as an EIP-191 personal message with the given account. The password
+	/// is ignored: the accounts of the node signers are always unlocked.
+	#[method(name = "personal_sign")]
+	async fn personal_sign(
+		&self,
+		data: Bytes,
+		address: H160,
+		password: Option<String>,
+	) -> Result<Bytes>;
 }
 
 /// Eth filters rpc api (polling).
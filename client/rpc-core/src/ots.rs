@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Otterscan rpc interface.
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
+
+use crate::types::*;
+
+/// Otterscan rpc interface.
+#[rpc(server)]
+#[async_trait]
+pub trait OtsApi {
+	/// Returns the version of the Otterscan API implemented by the node.
+	#[method(name = "ots_getApiLevel")]
+	fn get_api_level(&self) -> Result<u64>;
+
+	/// Returns whether the account has code at given block.
+	#[method(name = "ots_hasCode")]
+	async fn has_code(&self, address: H160, number: BlockNumber) -> Result<bool>;
+
+	/// Returns the block with given number without its transactions, along with its issuance
+	/// and the fees paid by its transactions.
+	#[method(name = "ots_getBlockDetails")]
+	async fn get_block_details(&self, number: BlockNumber) -> Result<Option<BlockDetails>>;
+
+	/// Same as `ots_getBlockDetails`, for the block with given hash.
+	#[method(name = "ots_getBlockDetailsByHash")]
+	async fn get_block_details_by_hash(&self, hash: H256) -> Result<Option<BlockDetails>>;
+
+	/// Returns the block with given number and a page of its transactions with their receipts.
+	/// Page 0 holds the last `page_size` transactions of the block.
+	#[method(name = "ots_getBlockTransactions")]
+	async fn get_block_transactions(
+		&self,
+		number: BlockNumber,
+		page_number: usize,
+		page_size: usize,
+	) -> Result<Option<BlockTransactionsPage>>;
+
+	/// Returns the value transfers, creates and self-destructs made by the subcalls of the
+	/// transaction with given hash.
+	#[method(name = "ots_getInternalOperations")]
+	async fn get_internal_operations(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<InternalOperation>>>;
+
+	/// Returns the revert data of the transaction with given hash, empty if it succeeded.
+	#[method(name = "ots_getTransactionError")]
+	async fn get_transaction_error(&self, transaction_hash: H256) -> Result<Option<Bytes>>;
+
+	/// Returns the calls, creates and self-destructs of the transaction with given hash.
+	#[method(name = "ots_traceTransaction")]
+	async fn trace_transaction(&self, transaction_hash: H256) -> Result<Option<Vec<OtsTrace>>>;
+
+	/// Returns the transactions of an address in the blocks before the given one, 0 for the
+	/// latest block. The page holds at least `page_size` transactions, except the last one,
+	/// and whole blocks.
+	#[method(name = "ots_searchTransactionsBefore")]
+	async fn search_transactions_before(
+		&self,
+		address: H160,
+		block_number: u64,
+		page_size: usize,
+	) -> Result<TransactionsPage>;
+
+	/// Returns the transactions of an address in the blocks after the given one, 0 for the
+	/// genesis block. The page holds at least `page_size` transactions, except the first one,
+	/// and whole blocks.
+	#[method(name = "ots_searchTransactionsAfter")]
+	async fn search_transactions_after(
+		&self,
+		address: H160,
+		block_number: u64,
+		page_size: usize,
+	) -> Result<TransactionsPage>;
+
+	/// Returns the hash of the transaction sent by an address with given nonce.
+	#[method(name = "ots_getTransactionBySenderAndNonce")]
+	async fn get_transaction_by_sender_and_nonce(
+		&self,
+		address: H160,
+		nonce: U256,
+	) -> Result<Option<H256>>;
+
+	/// Returns the transaction that created the contract, and its sender.
+	#[method(name = "ots_getContractCreator")]
+	async fn get_contract_creator(&self, address: H160) -> Result<Option<ContractCreator>>;
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum::AccessListItem;
+use ethereum_types::U256;
+use serde::Serialize;
+
+/// `eth_createAccessList` response
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// Access list of the addresses and storage keys accessed by the transaction.
+	pub access_list: Vec<AccessListItem>,
+	/// Gas used by the transaction when sent with the access list.
+	pub gas_used: U256,
+	/// Reason of the failure of the transaction, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
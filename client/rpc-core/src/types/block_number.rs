@@ -197,6 +197,80 @@ impl<'a> Visitor<'a> for BlockNumberVisitor {
 	}
 }
 
+/// Block number param which also accepts a bare block hash, as geth's `BlockNumberOrHash`.
+///
+/// Only used by the methods that accept one, a 66 characters hex string being a block number
+/// everywhere else.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BlockNumberOrHash(pub BlockNumber);
+
+impl From<BlockNumberOrHash> for BlockNumber {
+	fn from(value: BlockNumberOrHash) -> Self {
+		value.0
+	}
+}
+
+impl<'a> Deserialize<'a> for BlockNumberOrHash {
+	fn deserialize<D>(deserializer: D) -> Result<BlockNumberOrHash, D::Error>
+	where
+		D: Deserializer<'a>,
+	{
+		deserializer.deserialize_any(BlockNumberOrHashVisitor)
+	}
+}
+
+struct BlockNumberOrHashVisitor;
+
+impl<'a> Visitor<'a> for BlockNumberOrHashVisitor {
+	type Value = BlockNumberOrHash;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			formatter,
+			"a block hash, a block number or 'latest', 'safe', 'finalized', 'earliest' or 'pending'"
+		)
+	}
+
+	fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+	where
+		V: MapAccess<'a>,
+	{
+		BlockNumberVisitor.visit_map(visitor).map(BlockNumberOrHash)
+	}
+
+	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		match value.strip_prefix("0x") {
+			Some(hash) if hash.len() == 64 => hash
+				.parse::<H256>()
+				.map(|hash| {
+					BlockNumberOrHash(BlockNumber::Hash {
+						hash,
+						require_canonical: false,
+					})
+				})
+				.map_err(|e| Error::custom(format!("Invalid block hash: {}", e))),
+			_ => BlockNumberVisitor.visit_str(value).map(BlockNumberOrHash),
+		}
+	}
+
+	fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		self.visit_str(value.as_ref())
+	}
+
+	fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		BlockNumberVisitor.visit_u64(value).map(BlockNumberOrHash)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -233,4 +307,29 @@ mod tests {
 		assert_eq!(match_block_number(bn_tag_finalized).unwrap(), 999);
 		assert_eq!(match_block_number(bn_tag_pending).unwrap(), 1001);
 	}
+
+	#[test]
+	fn block_hash_deserialize() {
+		let hash = H256::repeat_byte(0x12);
+		let json = format!(r#""{:?}""#, hash);
+
+		let bn_hash: BlockNumberOrHash = serde_json::from_str(&json).unwrap();
+		assert_eq!(
+			bn_hash.0,
+			BlockNumber::Hash {
+				hash,
+				require_canonical: false,
+			}
+		);
+		let bn_object: BlockNumberOrHash =
+			serde_json::from_str(&format!(r#"{{"blockHash":{}}}"#, json)).unwrap();
+		assert_eq!(bn_object, bn_hash);
+		let bn_hex: BlockNumberOrHash = serde_json::from_str(r#""0x45""#).unwrap();
+		assert_eq!(bn_hex.0, BlockNumber::Num(69));
+		let bn_tag: BlockNumberOrHash = serde_json::from_str(r#""latest""#).unwrap();
+		assert_eq!(bn_tag.0, BlockNumber::Latest);
+
+		// Everywhere else, a bare hash isn't a block number.
+		assert!(serde_json::from_str::<BlockNumber>(&json).is_err());
+	}
 }
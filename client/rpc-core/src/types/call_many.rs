@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BlockNumber, Bytes, CallRequest, Log};
+
+/// Block environment overrides of an `eth_callMany` bundle.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverride {
+	/// Fake block number.
+	pub block_number: Option<U256>,
+	/// Fake block timestamp, in seconds.
+	pub timestamp: Option<U256>,
+	/// Fake base fee per gas.
+	pub base_fee: Option<U256>,
+	/// Fake block author.
+	pub coinbase: Option<H160>,
+	/// Fake block gas limit, not supported.
+	pub gas_limit: Option<U256>,
+	/// Fake block difficulty, not supported.
+	pub difficulty: Option<U256>,
+}
+
+/// Calls executed in order by `eth_callMany`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundle {
+	pub transactions: Vec<CallRequest>,
+	/// Overrides applied before executing the bundle, they are kept by the next bundles.
+	pub block_override: Option<BlockOverride>,
+}
+
+/// Block on top of which `eth_callMany` executes its bundles.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyContext {
+	pub block_number: BlockNumber,
+	/// Only `-1`, executing on top of the whole block, is supported.
+	pub transaction_index: Option<i64>,
+}
+
+/// `eth_callMany` result of a call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyResult {
+	/// Returned data, or deployed code of a create.
+	pub value: Bytes,
+	/// Reason of the failure of the call, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	pub gas_used: U256,
+	/// Logs emitted by the call.
+	pub logs: Vec<Log>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn call_bundle_deserialization() {
+		let s = r#"[{
+			"transactions": [{ "to": "0x0000000000000000000000000000000000000001", "data": "0x01" }],
+			"blockOverride": { "blockNumber": "0x10", "baseFee": "0x0" }
+		}]"#;
+		let deserialized: Vec<CallBundle> = serde_json::from_str(s).unwrap();
+		assert_eq!(
+			deserialized,
+			vec![CallBundle {
+				transactions: vec![CallRequest {
+					to: Some(H160::from_low_u64_be(1)),
+					data: Some(Bytes(vec![1])),
+					..Default::default()
+				}],
+				block_override: Some(BlockOverride {
+					block_number: Some(U256::from(16)),
+					base_fee: Some(U256::zero()),
+					..Default::default()
+				}),
+			}]
+		);
+
+		let s = r#"{ "gasLimit": "0x1000", "difficulty": "0x0" }"#;
+		let deserialized: BlockOverride = serde_json::from_str(s).unwrap();
+		assert_eq!(
+			deserialized,
+			BlockOverride {
+				gas_limit: Some(U256::from(4096)),
+				difficulty: Some(U256::zero()),
+				..Default::default()
+			}
+		);
+
+		let s = r#"{ "blockNumber": "latest", "transactionIndex": -1 }"#;
+		let deserialized: CallManyContext = serde_json::from_str(s).unwrap();
+		assert_eq!(
+			deserialized,
+			CallManyContext {
+				block_number: BlockNumber::Latest,
+				transaction_index: Some(-1),
+			}
+		);
+	}
+}
@@ -16,9 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
+
 use crate::types::Bytes;
 use ethereum::AccessListItem;
-use ethereum_types::{H160, U256};
+use ethereum_types::{H160, H256, U256};
 use serde::Deserialize;
 
 /// Call request
@@ -50,3 +52,58 @@ pub struct CallRequest {
 	#[serde(rename = "type")]
 	pub transaction_type: Option<U256>,
 }
+
+/// State override of an account, for `eth_call` and `eth_estimateGas`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CallStateOverride {
+	/// Fake balance to set for the account before executing the call.
+	pub balance: Option<U256>,
+	/// Fake nonce to set for the account before executing the call.
+	pub nonce: Option<U256>,
+	/// Fake EVM bytecode to inject into the account before executing the call.
+	pub code: Option<Bytes>,
+	/// Fake key-value mapping to override all slots in the account storage before
+	/// executing the call.
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Fake key-value mapping to override individual slots in the account storage before
+	/// executing the call.
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// State overrides of `eth_call` and `eth_estimateGas`, by account.
+pub type CallStateOverrides = BTreeMap<H160, CallStateOverride>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn state_override_deserialization() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x1",
+				"code": "0x6000",
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+				}
+			}
+		}"#;
+		let deserialized: CallStateOverrides = serde_json::from_str(s).unwrap();
+
+		let mut state_diff = BTreeMap::new();
+		state_diff.insert(H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+		let mut expected = BTreeMap::new();
+		expected.insert(
+			H160::from_low_u64_be(1),
+			CallStateOverride {
+				balance: Some(U256::one()),
+				code: Some(Bytes(vec![0x60, 0x00])),
+				state_diff: Some(state_diff),
+				..Default::default()
+			},
+		);
+		assert_eq!(deserialized, expected);
+	}
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2015-2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Geth-compatible transaction tracing types.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::Bytes;
+
+/// Options of `debug_traceTransaction` and `debug_traceBlockBy*`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+	/// Name of the tracer to use. Only `callTracer` is supported, the struct logger is used
+	/// when no tracer is given.
+	pub tracer: Option<String>,
+	/// Struct logger: do not report storage.
+	#[serde(default)]
+	pub disable_storage: bool,
+	/// Struct logger: do not report the stack.
+	#[serde(default)]
+	pub disable_stack: bool,
+	/// Struct logger: report the memory.
+	#[serde(default)]
+	pub enable_memory: bool,
+}
+
+/// Output of a tracer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+	StructLogger(StructLoggerResult),
+	CallTracer(CallTrace),
+}
+
+/// Output of the struct logger.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerResult {
+	pub gas: u64,
+	pub failed: bool,
+	/// Hex encoded, without `0x` prefix.
+	pub return_value: String,
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// One executed opcode.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: String,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// Memory as 32 bytes words, hex encoded without `0x` prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Storage of the current contract, hex encoded without `0x` prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+/// A call frame of the `callTracer`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTrace {
+	#[serde(rename = "type")]
+	pub call_type: String,
+	pub from: H160,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to: Option<H160>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub value: Option<U256>,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub output: Option<Bytes>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallTrace>,
+}
+
+/// Trace of one transaction of a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTraceResult {
+	pub tx_hash: H256,
+	pub result: TraceResult,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn trace_params_deserialize() {
+		let params: TraceParams = serde_json::from_str(r#"{"tracer":"callTracer"}"#).unwrap();
+		assert_eq!(params.tracer, Some("callTracer".to_string()));
+		assert!(!params.disable_storage);
+
+		let params: TraceParams =
+			serde_json::from_str(r#"{"disableStorage":true,"enableMemory":true}"#).unwrap();
+		assert_eq!(
+			params,
+			TraceParams {
+				tracer: None,
+				disable_storage: true,
+				disable_stack: false,
+				enable_memory: true,
+			}
+		);
+	}
+
+	#[test]
+	fn call_trace_serialize() {
+		let trace = CallTrace {
+			call_type: "CALL".into(),
+			from: H160::repeat_byte(1),
+			to: Some(H160::repeat_byte(2)),
+			value: Some(U256::zero()),
+			gas: U256::from(21_000),
+			gas_used: U256::from(21_000),
+			input: Bytes(vec![]),
+			output: None,
+			error: None,
+			revert_reason: None,
+			calls: vec![],
+		};
+		let serialized = serde_json::to_value(&trace).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!({
+				"type": "CALL",
+				"from": "0x0101010101010101010101010101010101010101",
+				"to": "0x0202020202020202020202020202020202020202",
+				"value": "0x0",
+				"gas": "0x5208",
+				"gasUsed": "0x5208",
+				"input": "0x",
+			})
+		);
+	}
+}
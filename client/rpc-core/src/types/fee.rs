@@ -50,4 +50,33 @@ pub struct FeeHistoryCacheItem {
 	pub base_fee: u64,
 	pub gas_used_ratio: f64,
 	pub rewards: Vec<u64>,
+	/// Effective priority fees of the block, ascending, each value at most as many times as the
+	/// gas price oracle samples per block. The oracle skips those below its ignore price.
+	pub lowest_rewards: Vec<u64>,
+}
+
+/// Configuration of the `eth_gasPrice` oracle, which suggests the priority fee from the ones paid
+/// in recent blocks like Geth's `gasprice` package.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GasPriceOracleConfig {
+	/// Number of recent blocks sampled.
+	pub blocks: u64,
+	/// Percentile of the sampled priority fees suggested.
+	pub percentile: u64,
+	/// Maximum suggested priority fee.
+	pub max_price: U256,
+	/// Priority fees below this one are not sampled.
+	pub ignore_price: U256,
+}
+
+impl Default for GasPriceOracleConfig {
+	fn default() -> Self {
+		// https://github.com/ethereum/go-ethereum/blob/master/eth/ethconfig/config.go
+		Self {
+			blocks: 20,
+			percentile: 60,
+			max_price: U256::from(500_000_000_000u64),
+			ignore_price: U256::from(2),
+		}
+	}
 }
@@ -18,42 +18,65 @@
 
 //! RPC types
 
+mod access_list;
 mod account_info;
 mod block;
 mod block_number;
 mod bytes;
+mod call_many;
 mod call_request;
+mod debug;
 mod fee;
 mod filter;
 mod index;
 mod log;
+mod ots;
 mod receipt;
 mod sync;
+mod trace;
 mod transaction;
 mod transaction_request;
+mod txpool;
+mod typed_data;
 mod work;
 
 pub mod pubsub;
 
 pub use self::{
+	access_list::AccessListResult,
 	account_info::{AccountInfo, EthAccount, ExtAccountInfo, RecoveredAccount, StorageProof},
 	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader},
-	block_number::BlockNumber,
+	block_number::{BlockNumber, BlockNumberOrHash},
 	bytes::Bytes,
-	call_request::CallRequest,
-	fee::{FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit},
+	call_many::{BlockOverride, CallBundle, CallManyContext, CallManyResult},
+	call_request::{CallRequest, CallStateOverride, CallStateOverrides},
+	debug::{BlockTraceResult, CallTrace, StructLog, StructLoggerResult, TraceParams, TraceResult},
+	fee::{
+		FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit,
+		GasPriceOracleConfig,
+	},
 	filter::{
 		Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
 		FilteredParams, Topic, VariadicValue,
 	},
 	index::Index,
 	log::Log,
+	ots::{
+		BlockDetails, BlockTransactionsPage, ContractCreator, InternalOperation,
+		InternalOperationKind, Issuance, OtsBlock, OtsReceipt, OtsTrace, TransactionsPage,
+	},
 	receipt::Receipt,
 	sync::{
 		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 		Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
 	},
+	trace::{
+		ActionResult, CallAction, CallResult, CallType, CreateAction, CreateResult, LocalizedTrace,
+		SuicideAction, TraceAction, TraceFilter, TraceOutcome,
+	},
 	transaction::{LocalTransactionStatus, RichRawTransaction, Transaction},
 	transaction_request::{TransactionMessage, TransactionRequest},
+	txpool::{Summary, TransactionMap, TxPoolResult, TxPoolStatus},
+	typed_data::{TypedData, TypedDataField},
 	work::Work,
 };
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Otterscan `ots_` namespace types.
+
+use ethereum_types::{H160, H256, U256};
+use serde::{Serialize, Serializer};
+
+use crate::types::{Bytes, Receipt, RichBlock, Transaction};
+
+/// Transactions of an address returned by `ots_searchTransactionsBefore` and
+/// `ots_searchTransactionsAfter`, newest first.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsPage {
+	pub txs: Vec<Transaction>,
+	pub receipts: Vec<OtsReceipt>,
+	/// No newer transaction exists.
+	pub first_page: bool,
+	/// No older transaction exists.
+	pub last_page: bool,
+}
+
+/// Receipt with the timestamp of its block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsReceipt {
+	#[serde(flatten)]
+	pub receipt: Receipt,
+	pub timestamp: U256,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreator {
+	/// Hash of the creation transaction.
+	pub hash: H256,
+	pub creator: H160,
+}
+
+/// Block of `ots_getBlockDetails`, without its transactions.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlock {
+	#[serde(flatten)]
+	pub block: RichBlock,
+	pub transaction_count: usize,
+}
+
+/// Native currency minted by a block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issuance {
+	pub block_reward: U256,
+	pub uncle_reward: U256,
+	pub issuance: U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockDetails {
+	pub block: OtsBlock,
+	pub issuance: Issuance,
+	/// Sum of the fees paid by the transactions of the block.
+	pub total_fees: U256,
+}
+
+/// Page of the transactions of a block returned by `ots_getBlockTransactions`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionsPage {
+	/// Block with the transactions of the page, their input cropped to the function selector.
+	pub fullblock: OtsBlock,
+	/// Receipts of the transactions of the page, without their logs.
+	pub receipts: Vec<Receipt>,
+}
+
+/// Kind of an [`InternalOperation`], serialized as its number.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InternalOperationKind {
+	Transfer = 0,
+	SelfDestruct = 1,
+	Create = 2,
+	Create2 = 3,
+}
+
+impl Serialize for InternalOperationKind {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_u8(*self as u8)
+	}
+}
+
+/// A value transfer, create or self-destruct of `ots_getInternalOperations`, made by a subcall
+/// of the transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalOperation {
+	#[serde(rename = "type")]
+	pub kind: InternalOperationKind,
+	pub from: H160,
+	/// Receiver, created contract or beneficiary of the self-destruct.
+	pub to: H160,
+	pub value: U256,
+}
+
+/// A call, create or self-destruct of `ots_traceTransaction`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsTrace {
+	#[serde(rename = "type")]
+	pub call_type: String,
+	/// Depth of the call, 0 for the transaction itself.
+	pub depth: u32,
+	pub from: H160,
+	pub to: H160,
+	/// `null` for delegate and static calls.
+	pub value: Option<U256>,
+	pub input: Bytes,
+	pub output: Bytes,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn internal_operation_serialize() {
+		let operation = InternalOperation {
+			kind: InternalOperationKind::Create2,
+			from: H160::repeat_byte(0x11),
+			to: H160::repeat_byte(0x22),
+			value: U256::from(1),
+		};
+		assert_eq!(
+			serde_json::to_string(&operation).unwrap(),
+			r#"{"type":3,"from":"0x1111111111111111111111111111111111111111","to":"0x2222222222222222222222222222222222222222","value":"0x1"}"#
+		);
+	}
+}
@@ -18,7 +18,7 @@
 
 //! Pub-Sub types.
 
-use crate::types::{Filter, Log, RichHeader};
+use crate::types::{Filter, Log, RichHeader, Transaction};
 use ethereum_types::H256;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{from_value, Value};
@@ -32,6 +32,10 @@ pub enum Result {
 	Log(Box<Log>),
 	/// Transaction hash
 	TransactionHash(H256),
+	/// Full transaction
+	Transaction(Box<Transaction>),
+	/// Pending transaction status change
+	TransactionStatus(PendingTransactionStatus),
 	/// SyncStatus
 	SyncState(PubSubSyncStatus),
 }
@@ -53,6 +57,33 @@ pub struct SyncStatusMetadata {
 	pub highest_block: Option<u64>,
 }
 
+/// Status change of a pending transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct PendingTransactionStatus {
+	/// Hash of the transaction.
+	pub hash: H256,
+	#[serde(flatten)]
+	pub event: PendingTransactionEvent,
+}
+
+/// Pending transaction status change event.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum PendingTransactionEvent {
+	/// The transaction was included in a block.
+	Included {
+		#[serde(rename = "blockHash")]
+		block_hash: H256,
+	},
+	/// The transaction was replaced by another transaction with the same sender and nonce.
+	Replaced {
+		#[serde(rename = "replacedBy", skip_serializing_if = "Option::is_none")]
+		replaced_by: Option<H256>,
+	},
+	/// The transaction was dropped from the pool.
+	Dropped,
+}
+
 impl Serialize for Result {
 	fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
 	where
@@ -62,6 +93,8 @@ impl Serialize for Result {
 			Result::Header(ref header) => header.serialize(serializer),
 			Result::Log(ref log) => log.serialize(serializer),
 			Result::TransactionHash(ref hash) => hash.serialize(serializer),
+			Result::Transaction(ref transaction) => transaction.serialize(serializer),
+			Result::TransactionStatus(ref status) => status.serialize(serializer),
 			Result::SyncState(ref sync) => sync.serialize(serializer),
 		}
 	}
@@ -78,6 +111,8 @@ pub enum Kind {
 	Logs,
 	/// New Pending Transactions subscription.
 	NewPendingTransactions,
+	/// Pending transactions dropped, replaced or included subscription.
+	PendingTransactionStatus,
 	/// Node syncing status subscription.
 	Syncing,
 }
@@ -89,6 +124,8 @@ pub enum Params {
 	None,
 	/// Log parameters.
 	Logs(Filter),
+	/// New pending transactions parameter: whether to send full transactions.
+	Bool(bool),
 }
 
 impl Default for Params {
@@ -108,8 +145,59 @@ impl<'a> Deserialize<'a> for Params {
 			return Ok(Params::None);
 		}
 
+		if let Value::Bool(full) = v {
+			return Ok(Params::Bool(full));
+		}
+
 		from_value(v)
 			.map(Params::Logs)
 			.map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn params_deserialization() {
+		assert_eq!(
+			serde_json::from_str::<Params>("true").unwrap(),
+			Params::Bool(true)
+		);
+		assert_eq!(
+			serde_json::from_str::<Params>("null").unwrap(),
+			Params::None
+		);
+	}
+
+	#[test]
+	fn pending_transaction_status_serialization() {
+		let status = Result::TransactionStatus(PendingTransactionStatus {
+			hash: H256::repeat_byte(1),
+			event: PendingTransactionEvent::Replaced {
+				replaced_by: Some(H256::repeat_byte(2)),
+			},
+		});
+		assert_eq!(
+			serde_json::to_string(&status).unwrap(),
+			format!(
+				r#"{{"hash":"{:?}","status":"replaced","replacedBy":"{:?}"}}"#,
+				H256::repeat_byte(1),
+				H256::repeat_byte(2)
+			)
+		);
+
+		let dropped = Result::TransactionStatus(PendingTransactionStatus {
+			hash: H256::repeat_byte(1),
+			event: PendingTransactionEvent::Dropped,
+		});
+		assert_eq!(
+			serde_json::to_string(&dropped).unwrap(),
+			format!(
+				r#"{{"hash":"{:?}","status":"dropped"}}"#,
+				H256::repeat_byte(1)
+			)
+		);
+	}
+}
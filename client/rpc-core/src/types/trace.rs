@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! OpenEthereum-compatible flattened trace types.
+
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BlockNumber, Bytes};
+
+/// Options of `trace_filter`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// Defaults to the earliest block.
+	pub from_block: Option<BlockNumber>,
+	/// Defaults to the latest block.
+	pub to_block: Option<BlockNumber>,
+	/// Senders of the traces to return, any sender if empty.
+	pub from_address: Option<Vec<H160>>,
+	/// Receivers of the traces to return, any receiver if empty.
+	pub to_address: Option<Vec<H160>>,
+	/// Number of matching traces to skip.
+	pub after: Option<usize>,
+	/// Maximum number of traces to return.
+	pub count: Option<usize>,
+}
+
+/// Kind of a traced call.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub gas: U256,
+	pub input: Bytes,
+	pub value: U256,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+	pub from: H160,
+	pub gas: U256,
+	pub init: Bytes,
+	pub value: U256,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuicideAction {
+	pub address: H160,
+	pub refund_address: H160,
+	pub balance: U256,
+}
+
+/// Action of a trace, serialized as its `type` and `action` fields.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "type", content = "action")]
+pub enum TraceAction {
+	Call(CallAction),
+	Create(CreateAction),
+	Suicide(SuicideAction),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallResult {
+	pub gas_used: U256,
+	pub output: Bytes,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateResult {
+	pub address: H160,
+	pub code: Bytes,
+	pub gas_used: U256,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ActionResult {
+	Call(CallResult),
+	Create(CreateResult),
+}
+
+/// Outcome of a trace, serialized as either its `result` or its `error` field. Self-destructs
+/// have no result.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceOutcome {
+	Result(Option<ActionResult>),
+	Error(String),
+}
+
+/// A call, create or self-destruct of a transaction, located in its block and call tree.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+	#[serde(flatten)]
+	pub action: TraceAction,
+	#[serde(flatten)]
+	pub outcome: TraceOutcome,
+	/// Number of direct subtraces.
+	pub subtraces: usize,
+	/// Path from the root trace of the transaction, as indices of subtraces.
+	pub trace_address: Vec<usize>,
+	pub transaction_hash: H256,
+	pub transaction_position: usize,
+	pub block_hash: H256,
+	pub block_number: u64,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn trace_filter_deserialize() {
+		let filter: TraceFilter = serde_json::from_str(
+			r#"{"fromBlock":"0x1","toAddress":["0x0202020202020202020202020202020202020202"],"count":10}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			filter,
+			TraceFilter {
+				from_block: Some(BlockNumber::Num(1)),
+				to_address: Some(vec![H160::repeat_byte(2)]),
+				count: Some(10),
+				..Default::default()
+			}
+		);
+	}
+
+	#[test]
+	fn localized_trace_serialize() {
+		let mut trace = LocalizedTrace {
+			action: TraceAction::Call(CallAction {
+				call_type: CallType::DelegateCall,
+				from: H160::repeat_byte(1),
+				to: H160::repeat_byte(2),
+				gas: U256::from(21_000),
+				input: Bytes(vec![]),
+				value: U256::zero(),
+			}),
+			outcome: TraceOutcome::Result(Some(ActionResult::Call(CallResult {
+				gas_used: U256::from(21_000),
+				output: Bytes(vec![]),
+			}))),
+			subtraces: 0,
+			trace_address: vec![0],
+			transaction_hash: H256::repeat_byte(3),
+			transaction_position: 1,
+			block_hash: H256::repeat_byte(4),
+			block_number: 5,
+		};
+		assert_eq!(
+			serde_json::to_value(&trace).unwrap(),
+			serde_json::json!({
+				"type": "call",
+				"action": {
+					"callType": "delegatecall",
+					"from": "0x0101010101010101010101010101010101010101",
+					"to": "0x0202020202020202020202020202020202020202",
+					"gas": "0x5208",
+					"input": "0x",
+					"value": "0x0",
+				},
+				"result": {
+					"gasUsed": "0x5208",
+					"output": "0x",
+				},
+				"subtraces": 0,
+				"traceAddress": [0],
+				"transactionHash": "0x0303030303030303030303030303030303030303030303030303030303030303",
+				"transactionPosition": 1,
+				"blockHash": "0x0404040404040404040404040404040404040404040404040404040404040404",
+				"blockNumber": 5,
+			})
+		);
+
+		trace.outcome = TraceOutcome::Error("Reverted".into());
+		let serialized = serde_json::to_value(&trace).unwrap();
+		assert_eq!(serialized["error"], "Reverted");
+		assert!(serialized.get("result").is_none());
+	}
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2015-2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction pool types
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, U256, U64};
+use serde::{Serialize, Serializer};
+
+use crate::types::Transaction;
+
+/// Pool transactions grouped by sender, then by nonce.
+pub type TransactionMap<T> = BTreeMap<H160, BTreeMap<u64, T>>;
+
+/// Pending (ready) and queued (future) parts of the transaction pool.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TxPoolResult<T> {
+	pub pending: T,
+	pub queued: T,
+}
+
+/// Number of transactions in the pool.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TxPoolStatus {
+	pub pending: U64,
+	pub queued: U64,
+}
+
+/// Textual summary of a pool transaction, as returned by `txpool_inspect`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Summary {
+	pub to: Option<H160>,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_price: U256,
+}
+
+impl From<&Transaction> for Summary {
+	fn from(transaction: &Transaction) -> Self {
+		Summary {
+			to: transaction.to,
+			value: transaction.value,
+			gas: transaction.gas,
+			gas_price: transaction.gas_price.unwrap_or_default(),
+		}
+	}
+}
+
+impl Serialize for Summary {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let to = match self.to {
+			Some(to) => format!("0x{:x}", to),
+			None => "contract creation".to_string(),
+		};
+		serializer.serialize_str(&format!(
+			"{}: {} wei + {} gas × {} wei",
+			to, self.value, self.gas, self.gas_price
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn summary_serialize() {
+		let summary = Summary {
+			to: Some(H160::repeat_byte(0x11)),
+			value: U256::from(512),
+			gas: U256::from(21_000),
+			gas_price: U256::from(1_000_000_000),
+		};
+		assert_eq!(
+			serde_json::to_string(&summary).unwrap(),
+			r#""0x1111111111111111111111111111111111111111: 512 wei + 21000 gas × 1000000000 wei""#
+		);
+
+		let summary = Summary {
+			to: None,
+			..summary
+		};
+		assert_eq!(
+			serde_json::to_string(&summary).unwrap(),
+			r#""contract creation: 512 wei + 21000 gas × 1000000000 wei""#
+		);
+	}
+
+	#[test]
+	fn transaction_map_serialize() {
+		let mut map: TransactionMap<U64> = TransactionMap::new();
+		map.entry(H160::repeat_byte(0x11))
+			.or_default()
+			.insert(10, U64::from(1));
+		assert_eq!(
+			serde_json::to_value(&map).unwrap(),
+			serde_json::json!({
+				"0x1111111111111111111111111111111111111111": { "10": "0x1" },
+			})
+		);
+	}
+}
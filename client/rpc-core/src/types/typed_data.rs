@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Member of an EIP-712 struct type.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TypedDataField {
+	pub name: String,
+	#[serde(rename = "type")]
+	pub type_: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTypedData {
+	types: BTreeMap<String, Vec<TypedDataField>>,
+	primary_type: String,
+	domain: Map<String, Value>,
+	#[serde(default)]
+	message: Map<String, Value>,
+}
+
+/// EIP-712 typed data of `eth_signTypedData_v4`.
+///
+/// Wallets send it either as a JSON object or as a string containing the JSON object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "Value")]
+pub struct TypedData {
+	/// Struct types, including `EIP712Domain`.
+	pub types: BTreeMap<String, Vec<TypedDataField>>,
+	/// Type of `message`.
+	pub primary_type: String,
+	/// Values of the `EIP712Domain` struct.
+	pub domain: Map<String, Value>,
+	/// Values of the `primary_type` struct.
+	pub message: Map<String, Value>,
+}
+
+impl TryFrom<Value> for TypedData {
+	type Error = serde_json::Error;
+
+	fn try_from(value: Value) -> Result<Self, Self::Error> {
+		let raw: RawTypedData = match value {
+			Value::String(json) => serde_json::from_str(&json)?,
+			value => serde_json::from_value(value)?,
+		};
+		Ok(TypedData {
+			types: raw.types,
+			primary_type: raw.primary_type,
+			domain: raw.domain,
+			message: raw.message,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn typed_data_deserialization() {
+		let object = r#"{
+			"types": {
+				"EIP712Domain": [{ "name": "name", "type": "string" }],
+				"Mail": [{ "name": "contents", "type": "string" }]
+			},
+			"primaryType": "Mail",
+			"domain": { "name": "Ether Mail" },
+			"message": { "contents": "Hello, Bob!" }
+		}"#;
+		let deserialized: TypedData = serde_json::from_str(object).unwrap();
+		assert_eq!(deserialized.primary_type, "Mail");
+		assert_eq!(
+			deserialized.types["Mail"],
+			vec![TypedDataField {
+				name: "contents".into(),
+				type_: "string".into(),
+			}]
+		);
+
+		let string = serde_json::to_string(object).unwrap();
+		assert_eq!(
+			serde_json::from_str::<TypedData>(&string).unwrap(),
+			deserialized
+		);
+	}
+}
@@ -0,0 +1,555 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum::{BlockV2 as EthereumBlock, EnvelopedEncodable};
+use ethereum_types::{H256, U256};
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockBackend,
+};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT},
+};
+// Frontier
+use fc_rpc_core::{types::*, DebugApiServer};
+use fp_evm::{CallFrame, CallType, TracerKind, TransactionTrace};
+use fp_rpc::DebugRuntimeApi;
+
+use crate::{eth::EthBlockDataCacheTask, frontier_backend_client, internal_err, OverrideHandle};
+
+/// Debug API implementation.
+pub struct Debug<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<fc_db::Backend<B>>,
+	overrides: Arc<OverrideHandle<B>>,
+	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> Debug<B, C, BE> {
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<fc_db::Backend<B>>,
+		overrides: Arc<OverrideHandle<B>>,
+		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			overrides,
+			block_data_cache,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B: BlockT, C, BE> Clone for Debug<B, C, BE> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			overrides: self.overrides.clone(),
+			block_data_cache: self.block_data_cache.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B, C, BE> Debug<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	/// Header and extrinsics of the block to replay.
+	fn block(&self, id: BlockId<B>) -> Result<(B::Header, Vec<B::Extrinsic>)> {
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("header not found for block {:?}", id)))?;
+		let extrinsics = self
+			.client
+			.block_body(header.hash())
+			.map_err(|err| internal_err(format!("fetch block body failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("body not found for block {:?}", id)))?;
+		Ok((header, extrinsics))
+	}
+
+	fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> Result<TraceResult> {
+		let tracer = tracer_kind(params)?;
+		let (ethereum_block_hash, _) = frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			transaction_hash,
+			true,
+		)?
+		.ok_or_else(|| internal_err(format!("transaction {:?} not found", transaction_hash)))?;
+		let id = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			ethereum_block_hash,
+		)?
+		.ok_or_else(|| internal_err(format!("block {:?} not found", ethereum_block_hash)))?;
+
+		let (header, extrinsics) = self.block(id)?;
+		let trace = replay_transaction::<B, C>(
+			self.client.as_ref(),
+			&header,
+			extrinsics,
+			transaction_hash,
+			tracer,
+		)?
+		.ok_or_else(|| {
+			internal_err(format!(
+				"transaction {:?} not found in block",
+				transaction_hash
+			))
+		})?;
+		Ok(format_trace(trace))
+	}
+
+	fn trace_block(
+		&self,
+		id: BlockId<B>,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTraceResult>> {
+		let tracer = tracer_kind(params)?;
+		let (header, extrinsics) = self.block(id)?;
+		let parent = BlockId::Hash(*header.parent_hash());
+		let api = self.client.runtime_api();
+		ensure_debug_api::<B, C::Api>(&*api, &parent)?;
+		api.initialize_block(&parent, &header)
+			.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?;
+		let traces = api
+			.trace_block(&parent, extrinsics, tracer)
+			.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?
+			.map_err(|_| tracing_not_supported())?;
+		Ok(traces
+			.into_iter()
+			.map(|(tx_hash, trace)| BlockTraceResult {
+				tx_hash,
+				result: format_trace(trace),
+			})
+			.collect())
+	}
+
+	fn block_id(&self, number: BlockNumber) -> Result<Option<BlockId<B>>> {
+		frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)
+	}
+
+	async fn ethereum_block(&self, id: BlockId<B>) -> Result<Option<EthereumBlock>> {
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+		let schema =
+			frontier_backend_client::onchain_storage_schema::<B, C, BE>(self.client.as_ref(), id);
+		Ok(self
+			.block_data_cache
+			.current_block(schema, substrate_hash)
+			.await)
+	}
+
+	async fn raw_transaction(&self, transaction_hash: H256) -> Result<Option<Bytes>> {
+		let (ethereum_block_hash, ethereum_index) =
+			match frontier_backend_client::load_transactions::<B, C>(
+				self.client.as_ref(),
+				self.backend.as_ref(),
+				transaction_hash,
+				true,
+			)? {
+				Some(transaction) => transaction,
+				None => return Ok(None),
+			};
+		let id = match frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			ethereum_block_hash,
+		)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		Ok(self.ethereum_block(id).await?.and_then(|block| {
+			block
+				.transactions
+				.get(ethereum_index as usize)
+				.map(|transaction| Bytes(EnvelopedEncodable::encode(transaction).to_vec()))
+		}))
+	}
+
+	async fn raw_receipts(&self, number: BlockNumber) -> Result<Option<Vec<Bytes>>> {
+		let id = match self.block_id(number)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let schema =
+			frontier_backend_client::onchain_storage_schema::<B, C, BE>(self.client.as_ref(), id);
+		let handler = self
+			.overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&self.overrides.fallback);
+		Ok(handler.current_receipts(&id).map(|receipts| {
+			receipts
+				.iter()
+				.map(|receipt| Bytes(EnvelopedEncodable::encode(receipt).to_vec()))
+				.collect()
+		}))
+	}
+}
+
+#[async_trait]
+impl<B, C, BE> DebugApiServer for Debug<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> Result<TraceResult> {
+		let debug = self.clone();
+		spawn_replay(move || debug.trace_transaction(transaction_hash, params)).await
+	}
+
+	async fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTraceResult>> {
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)?
+		.ok_or_else(|| internal_err("pending block cannot be traced"))?;
+		let debug = self.clone();
+		spawn_replay(move || debug.trace_block(id, params)).await
+	}
+
+	async fn trace_block_by_hash(
+		&self,
+		hash: H256,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTraceResult>> {
+		let id = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+		)?
+		.ok_or_else(|| internal_err(format!("block {:?} not found", hash)))?;
+		let debug = self.clone();
+		spawn_replay(move || debug.trace_block(id, params)).await
+	}
+
+	async fn get_raw_transaction(&self, transaction_hash: H256) -> Result<Option<Bytes>> {
+		self.raw_transaction(transaction_hash).await
+	}
+
+	async fn get_raw_header(&self, number: BlockNumberOrHash) -> Result<Option<Bytes>> {
+		let id = match self.block_id(number.into())? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		Ok(self
+			.ethereum_block(id)
+			.await?
+			.map(|block| Bytes(rlp::encode(&block.header).to_vec())))
+	}
+
+	async fn get_raw_block(&self, number: BlockNumberOrHash) -> Result<Option<Bytes>> {
+		let id = match self.block_id(number.into())? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		Ok(self
+			.ethereum_block(id)
+			.await?
+			.map(|block| Bytes(rlp::encode(&block).to_vec())))
+	}
+
+	async fn get_raw_receipts(&self, number: BlockNumberOrHash) -> Result<Option<Vec<Bytes>>> {
+		self.raw_receipts(number.into()).await
+	}
+}
+
+pub(crate) fn ensure_debug_api<B, Api>(api: &Api, at: &BlockId<B>) -> Result<()>
+where
+	B: BlockT,
+	Api: ApiExt<B>,
+{
+	match api.has_api::<dyn DebugRuntimeApi<B>>(at) {
+		Ok(true) => Ok(()),
+		_ => Err(tracing_not_supported()),
+	}
+}
+
+/// Error of a runtime without the debug API, or built without tracing support.
+pub(crate) fn tracing_not_supported() -> jsonrpsee::core::Error {
+	internal_err("runtime does not support transaction tracing")
+}
+
+/// Runs `f`, which replays transactions, on a blocking thread rather than on the RPC server's.
+pub(crate) async fn spawn_replay<R, F>(f: F) -> Result<R>
+where
+	R: Send + 'static,
+	F: FnOnce() -> Result<R> + Send + 'static,
+{
+	tokio::task::spawn_blocking(f)
+		.await
+		.map_err(|e| internal_err(format!("replay task failed: {}", e)))?
+}
+
+/// Replays the extrinsics of the block of `header` on top of its parent, up to the Ethereum
+/// transaction `transaction_hash`, and returns the trace of this transaction.
+pub(crate) fn replay_transaction<B, C>(
+	client: &C,
+	header: &B::Header,
+	extrinsics: Vec<B::Extrinsic>,
+	transaction_hash: H256,
+	tracer: TracerKind,
+) -> Result<Option<TransactionTrace>>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: DebugRuntimeApi<B>,
+{
+	let parent = BlockId::Hash(*header.parent_hash());
+	let api = client.runtime_api();
+	ensure_debug_api::<B, C::Api>(&*api, &parent)?;
+	api.initialize_block(&parent, header)
+		.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?;
+	api.trace_transaction(&parent, extrinsics, transaction_hash, tracer)
+		.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?
+		.map_err(|_| tracing_not_supported())
+}
+
+fn tracer_kind(params: Option<TraceParams>) -> Result<TracerKind> {
+	let params = params.unwrap_or_default();
+	match params.tracer.as_deref() {
+		None => Ok(TracerKind::StructLogger {
+			disable_storage: params.disable_storage,
+			disable_stack: params.disable_stack,
+			enable_memory: params.enable_memory,
+		}),
+		Some("callTracer") => Ok(TracerKind::CallTracer),
+		Some(tracer) => Err(internal_err(format!("tracer {} is not supported", tracer))),
+	}
+}
+
+fn format_trace(trace: TransactionTrace) -> TraceResult {
+	match trace {
+		TransactionTrace::StructLogger(trace) => TraceResult::StructLogger(StructLoggerResult {
+			gas: trace.gas.low_u64(),
+			failed: trace.failed,
+			return_value: hex::encode(trace.return_value),
+			struct_logs: trace
+				.struct_logs
+				.into_iter()
+				.map(|log| StructLog {
+					pc: log.pc,
+					op: opcode_name(log.op),
+					gas: log.gas,
+					gas_cost: log.gas_cost,
+					depth: log.depth,
+					error: log.error.map(|e| String::from_utf8_lossy(&e).into_owned()),
+					stack: log.stack.map(|stack| {
+						stack
+							.into_iter()
+							.map(|word| U256::from_big_endian(word.as_bytes()))
+							.collect()
+					}),
+					memory: log
+						.memory
+						.map(|memory| memory.chunks(32).map(hex::encode).collect()),
+					storage: log.storage.map(|storage| {
+						storage
+							.into_iter()
+							.map(|(key, value)| (hex::encode(key), hex::encode(value)))
+							.collect()
+					}),
+				})
+				.collect(),
+		}),
+		TransactionTrace::CallTracer(frame) => TraceResult::CallTracer(format_call_frame(frame)),
+	}
+}
+
+/// Opcode name of a call type.
+pub(crate) fn call_type_name(call_type: CallType) -> &'static str {
+	match call_type {
+		CallType::Call => "CALL",
+		CallType::CallCode => "CALLCODE",
+		CallType::DelegateCall => "DELEGATECALL",
+		CallType::StaticCall => "STATICCALL",
+		CallType::Create => "CREATE",
+		CallType::Create2 => "CREATE2",
+		CallType::SelfDestruct => "SELFDESTRUCT",
+	}
+}
+
+fn format_call_frame(frame: CallFrame) -> CallTrace {
+	let call_type = call_type_name(frame.call_type);
+	let revert_reason = if frame.reverted {
+		revert_reason(&frame.output)
+	} else {
+		None
+	};
+	CallTrace {
+		call_type: call_type.to_string(),
+		from: frame.from,
+		to: frame.to,
+		value: frame.value,
+		gas: frame.gas,
+		gas_used: frame.gas_used,
+		input: Bytes(frame.input),
+		output: (!frame.output.is_empty()).then(|| Bytes(frame.output)),
+		error: frame
+			.error
+			.map(|e| String::from_utf8_lossy(&e).into_owned()),
+		revert_reason,
+		calls: frame.calls.into_iter().map(format_call_frame).collect(),
+	}
+}
+
+/// Decode the message of a solidity `Error(string)` revert.
+fn revert_reason(output: &[u8]) -> Option<String> {
+	const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+	if output.len() < 68 || output[..4] != ERROR_SELECTOR {
+		return None;
+	}
+	let len = U256::from_big_endian(&output[36..68]);
+	if len > U256::from(output.len() - 68) {
+		return None;
+	}
+	let message = &output[68..68 + len.as_usize()];
+	String::from_utf8(message.to_vec()).ok()
+}
+
+fn opcode_name(opcode: u8) -> String {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "KECCAK256",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x5c => "TLOAD",
+		0x5d => "TSTORE",
+		0x5e => "MCOPY",
+		0x5f => "PUSH0",
+		0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode 0x{:x} not defined", opcode),
+	};
+	name.to_string()
+}
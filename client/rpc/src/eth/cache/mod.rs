@@ -41,6 +41,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
 };
 // Frontier
+use fc_db::FeeHistoryEntry;
 use fc_rpc_core::types::*;
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 use fp_storage::EthereumStorageSchema;
@@ -51,6 +52,19 @@ use crate::{
 	overrides::{OverrideHandle, StorageOverride},
 };
 
+/// Fee history cache item of a persisted fee history entry.
+fn fee_history_cache_item<Hash>(entry: &FeeHistoryEntry<Hash>) -> FeeHistoryCacheItem {
+	FeeHistoryCacheItem {
+		base_fee: entry.base_fee,
+		gas_used_ratio: entry.gas_used as f64 / entry.gas_limit as f64,
+		rewards: entry.rewards.clone(),
+		lowest_rewards: entry.lowest_rewards.clone(),
+	}
+}
+
+/// Number of the lowest priority fees of each block sampled by the gas price oracle.
+pub(crate) const GAS_PRICE_ORACLE_SAMPLES: usize = 3;
+
 type WaitList<Hash, T> = HashMap<Hash, Vec<oneshot::Sender<Option<T>>>>;
 
 enum EthBlockDataCacheMessage<B: BlockT> {
@@ -306,133 +320,43 @@ where
 	pub async fn fee_history_task(
 		client: Arc<C>,
 		overrides: Arc<OverrideHandle<B>>,
+		backend: Arc<fc_db::Backend<B>>,
 		fee_history_cache: FeeHistoryCache,
 		block_limit: u64,
 	) {
-		struct TransactionHelper {
-			gas_used: u64,
-			effective_reward: u64,
-		}
-		// Calculates the cache for a single block
-		#[rustfmt::skip]
-			let fee_history_cache_item = |hash: H256| -> (
-			FeeHistoryCacheItem,
-			Option<u64>
-		) {
-			let id = BlockId::Hash(hash);
-			let schema =
-				frontier_backend_client::onchain_storage_schema::<B, C, BE>(client.as_ref(), id);
-			let handler = overrides
-				.schemas
-				.get(&schema)
-				.unwrap_or(&overrides.fallback);
-
-			// Evenly spaced percentile list from 0.0 to 100.0 with a 0.5 resolution.
-			// This means we cache 200 percentile points.
-			// Later in request handling we will approximate by rounding percentiles that
-			// fall in between with `(round(n*2)/2)`.
-			let reward_percentiles: Vec<f64> = {
-				let mut percentile: f64 = 0.0;
-				(0..201)
-					.into_iter()
-					.map(|_| {
-						let val = percentile;
-						percentile += 0.5;
-						val
-					})
-					.collect()
-			};
-
-			let block = handler.current_block(&id);
-			let mut block_number: Option<u64> = None;
-			let base_fee = client.runtime_api().gas_price(&id).unwrap_or_default();
-			let receipts = handler.current_receipts(&id);
-			let mut result = FeeHistoryCacheItem {
-				base_fee: base_fee.as_u64(),
-				gas_used_ratio: 0f64,
-				rewards: Vec::new(),
-			};
-			if let (Some(block), Some(receipts)) = (block, receipts) {
-				block_number = Some(block.header.number.as_u64());
-				let gas_used = block.header.gas_used.as_u64() as f64;
-				let gas_limit = block.header.gas_limit.as_u64() as f64;
-				result.gas_used_ratio = gas_used / gas_limit;
-
-				let mut previous_cumulative_gas = U256::zero();
-				let used_gas = |current: U256, previous: &mut U256| -> u64 {
-					let r = current.saturating_sub(*previous).as_u64();
-					*previous = current;
-					r
-				};
-				// Build a list of relevant transaction information.
-				let mut transactions: Vec<TransactionHelper> = receipts
-					.iter()
-					.enumerate()
-					.map(|(i, receipt)| TransactionHelper {
-						gas_used: match receipt {
-							ethereum::ReceiptV3::Legacy(d) | ethereum::ReceiptV3::EIP2930(d) | ethereum::ReceiptV3::EIP1559(d) => used_gas(d.used_gas, &mut previous_cumulative_gas),
-						},
-						effective_reward: match block.transactions.get(i) {
-							Some(&ethereum::TransactionV2::Legacy(ref t)) => {
-								t.gas_price.saturating_sub(base_fee).as_u64()
-							}
-							Some(&ethereum::TransactionV2::EIP2930(ref t)) => {
-								t.gas_price.saturating_sub(base_fee).as_u64()
-							}
-							Some(&ethereum::TransactionV2::EIP1559(ref t)) => t
-								.max_priority_fee_per_gas
-								.min(t.max_fee_per_gas.saturating_sub(base_fee))
-								.as_u64(),
-							None => 0,
-						},
-					})
-					.collect();
-				// Sort ASC by effective reward.
-				transactions.sort_by(|a, b| a.effective_reward.cmp(&b.effective_reward));
-
-				// Calculate percentile rewards.
-				result.rewards = reward_percentiles
-					.into_iter()
-					.filter_map(|p| {
-						let target_gas = (p * gas_used / 100f64) as u64;
-						let mut sum_gas = 0;
-						for tx in &transactions {
-							sum_gas += tx.gas_used;
-							if target_gas <= sum_gas {
-								return Some(tx.effective_reward);
-							}
-						}
-						None
-					})
-					.collect();
-			} else {
-				result.rewards = reward_percentiles.iter().map(|_| 0).collect();
-			}
-			(result, block_number)
-		};
-
-		// Commits the result to cache
-		let commit_if_any = |item: FeeHistoryCacheItem, key: Option<u64>| {
-			if let (Some(block_number), Ok(fee_history_cache)) =
-				(key, &mut fee_history_cache.lock())
-			{
-				fee_history_cache.insert(block_number, item);
-				// We want to remain within the configured cache bounds.
-				// The first key out of bounds.
-				let first_out = block_number.saturating_sub(block_limit);
-				// Out of bounds size.
-				let to_remove = (fee_history_cache.len() as u64).saturating_sub(block_limit);
-				// Remove all cache data before `block_limit`.
-				for i in 0..to_remove {
-					// Cannot overflow.
-					let key = first_out - i;
-					fee_history_cache.remove(&key);
-				}
+		let commit_if_any = |result: Option<(u64, FeeHistoryEntry<H256>)>| {
+			if let Some((block_number, entry)) = result {
+				Self::commit_fee_history_entry(
+					&backend,
+					&fee_history_cache,
+					block_limit,
+					block_number,
+					entry,
+				);
 			}
 		};
 
+		// Subscribe before backfilling, not to miss the blocks imported meanwhile.
 		let mut notification_st = client.import_notification_stream();
 
+		// Computing the missing entries replays up to `block_limit` blocks, on a blocking thread
+		// not to hold the imported blocks back.
+		{
+			let client = client.clone();
+			let overrides = overrides.clone();
+			let backend = backend.clone();
+			let fee_history_cache = fee_history_cache.clone();
+			tokio::task::spawn_blocking(move || {
+				Self::backfill_fee_history(
+					&client,
+					&overrides,
+					&backend,
+					&fee_history_cache,
+					block_limit,
+				)
+			});
+		}
+
 		while let Some(notification) = notification_st.next().await {
 			if notification.is_new_best {
 				// In case a re-org happened on import.
@@ -447,15 +371,214 @@ where
 						});
 						// Insert enacted.
 						let _ = tree_route.enacted().iter().map(|hash_and_number| {
-							let (result, block_number) =
-								fee_history_cache_item(hash_and_number.hash);
-							commit_if_any(result, block_number);
+							commit_if_any(Self::fee_history_entry(
+								&client,
+								&overrides,
+								hash_and_number.hash,
+							));
 						});
 					}
 				}
 				// Cache the imported block.
-				let (result, block_number) = fee_history_cache_item(notification.hash);
-				commit_if_any(result, block_number);
+				commit_if_any(Self::fee_history_entry(
+					&client,
+					&overrides,
+					notification.hash,
+				));
+			}
+		}
+	}
+
+	/// Fills the cache with the last `block_limit` canonical blocks: the persisted entries are
+	/// loaded and the missing ones are computed, so requests get the whole range.
+	fn backfill_fee_history(
+		client: &C,
+		overrides: &OverrideHandle<B>,
+		backend: &fc_db::Backend<B>,
+		fee_history_cache: &FeeHistoryCache,
+		block_limit: u64,
+	) {
+		let best_number =
+			UniqueSaturatedInto::<u64>::unique_saturated_into(client.info().best_number);
+		let mut backfilled = 0;
+		for block_number in (best_number + 1).saturating_sub(block_limit)..=best_number {
+			let hash = match client.hash(block_number.unique_saturated_into()) {
+				Ok(Some(hash)) => hash,
+				_ => continue,
+			};
+			match backend.fee_history().entry(block_number) {
+				Ok(Some(entry)) if entry.block_hash == hash => {
+					if let Ok(fee_history_cache) = &mut fee_history_cache.lock() {
+						fee_history_cache
+							.entry(block_number)
+							.or_insert_with(|| fee_history_cache_item(&entry));
+					}
+				}
+				_ => {
+					if let Some((block_number, entry)) =
+						Self::fee_history_entry(client, overrides, hash)
+					{
+						Self::commit_fee_history_entry(
+							backend,
+							fee_history_cache,
+							block_limit,
+							block_number,
+							entry,
+						);
+						backfilled += 1;
+					}
+				}
+			}
+		}
+		log::debug!(target: "fee-history", "Backfilled the fee history of {} blocks", backfilled);
+	}
+
+	/// Calculates the fee history of a single block.
+	fn fee_history_entry(
+		client: &C,
+		overrides: &OverrideHandle<B>,
+		hash: H256,
+	) -> Option<(u64, FeeHistoryEntry<H256>)> {
+		struct TransactionHelper {
+			gas_used: u64,
+			effective_reward: u64,
+		}
+
+		let id = BlockId::Hash(hash);
+		let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(client, id);
+		let handler = overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&overrides.fallback);
+
+		// Evenly spaced percentile list from 0.0 to 100.0 with a 0.5 resolution.
+		// This means we cache 200 percentile points.
+		// Later in request handling we will approximate by rounding percentiles that
+		// fall in between with `(round(n*2)/2)`.
+		let reward_percentiles: Vec<f64> = {
+			let mut percentile: f64 = 0.0;
+			(0..201)
+				.into_iter()
+				.map(|_| {
+					let val = percentile;
+					percentile += 0.5;
+					val
+				})
+				.collect()
+		};
+
+		let block = handler.current_block(&id);
+		let base_fee = client.runtime_api().gas_price(&id).unwrap_or_default();
+		let receipts = handler.current_receipts(&id);
+		if let (Some(block), Some(receipts)) = (block, receipts) {
+			let mut result = FeeHistoryEntry {
+				block_hash: hash,
+				base_fee: base_fee.as_u64(),
+				gas_used: block.header.gas_used.as_u64(),
+				gas_limit: block.header.gas_limit.as_u64(),
+				rewards: Vec::new(),
+				lowest_rewards: Vec::new(),
+			};
+			let gas_used = result.gas_used as f64;
+
+			let mut previous_cumulative_gas = U256::zero();
+			let used_gas = |current: U256, previous: &mut U256| -> u64 {
+				let r = current.saturating_sub(*previous).as_u64();
+				*previous = current;
+				r
+			};
+			// Build a list of relevant transaction information.
+			let mut transactions: Vec<TransactionHelper> = receipts
+				.iter()
+				.enumerate()
+				.map(|(i, receipt)| TransactionHelper {
+					gas_used: match receipt {
+						ethereum::ReceiptV3::Legacy(d)
+						| ethereum::ReceiptV3::EIP2930(d)
+						| ethereum::ReceiptV3::EIP1559(d) => used_gas(d.used_gas, &mut previous_cumulative_gas),
+					},
+					effective_reward: match block.transactions.get(i) {
+						Some(&ethereum::TransactionV2::Legacy(ref t)) => {
+							t.gas_price.saturating_sub(base_fee).as_u64()
+						}
+						Some(&ethereum::TransactionV2::EIP2930(ref t)) => {
+							t.gas_price.saturating_sub(base_fee).as_u64()
+						}
+						Some(&ethereum::TransactionV2::EIP1559(ref t)) => t
+							.max_priority_fee_per_gas
+							.min(t.max_fee_per_gas.saturating_sub(base_fee))
+							.as_u64(),
+						None => 0,
+					},
+				})
+				.collect();
+			// Sort ASC by effective reward.
+			transactions.sort_by(|a, b| a.effective_reward.cmp(&b.effective_reward));
+
+			// Rewards sampled by the gas price oracle. They are filtered by its ignore price when
+			// queried, and no value is needed more times than the samples taken per block.
+			for tx in &transactions {
+				let repeated = result
+					.lowest_rewards
+					.iter()
+					.rev()
+					.take_while(|reward| **reward == tx.effective_reward)
+					.count();
+				if repeated < GAS_PRICE_ORACLE_SAMPLES {
+					result.lowest_rewards.push(tx.effective_reward);
+				}
+			}
+
+			// Calculate percentile rewards.
+			result.rewards = reward_percentiles
+				.into_iter()
+				.filter_map(|p| {
+					let target_gas = (p * gas_used / 100f64) as u64;
+					let mut sum_gas = 0;
+					for tx in &transactions {
+						sum_gas += tx.gas_used;
+						if target_gas <= sum_gas {
+							return Some(tx.effective_reward);
+						}
+					}
+					None
+				})
+				.collect();
+			Some((block.header.number.as_u64(), result))
+		} else {
+			None
+		}
+	}
+
+	/// Commits the fee history of a block to the cache and persists it.
+	fn commit_fee_history_entry(
+		backend: &fc_db::Backend<B>,
+		fee_history_cache: &FeeHistoryCache,
+		block_limit: u64,
+		block_number: u64,
+		entry: FeeHistoryEntry<H256>,
+	) {
+		// The first key out of bounds.
+		let first_out = block_number.saturating_sub(block_limit);
+		let fee_history = backend.fee_history();
+		if let Err(e) = fee_history.write_entry(block_number, entry.clone(), first_out + 1) {
+			log::warn!(
+				target: "fee-history",
+				"Failed to persist the fee history of block {}: {}",
+				block_number,
+				e
+			);
+		}
+		if let Ok(fee_history_cache) = &mut fee_history_cache.lock() {
+			fee_history_cache.insert(block_number, fee_history_cache_item(&entry));
+			// We want to remain within the configured cache bounds. The backfill inserts older
+			// blocks concurrently, so the lowest ones are removed rather than those before
+			// `block_number`.
+			while fee_history_cache.len() as u64 > block_limit {
+				match fee_history_cache.keys().next().copied() {
+					Some(lowest) => fee_history_cache.remove(&lowest),
+					None => break,
+				};
 			}
 		}
 	}
@@ -18,8 +18,9 @@
 
 use std::sync::Arc;
 
-use ethereum_types::{H256, U256};
-use evm::{ExitError, ExitReason};
+use ethereum::AccessListItem;
+use ethereum_types::{H160, H256, U256};
+use evm::{ExitError, ExitReason, ExitRevert};
 use jsonrpsee::core::RpcResult as Result;
 // Substrate
 use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
@@ -35,6 +36,7 @@ use sp_runtime::{
 };
 // Frontier
 use fc_rpc_core::types::*;
+use fp_evm::{AccountOverride, CallInfo};
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
@@ -45,6 +47,9 @@ use crate::{
 /// Default JSONRPC error code return by geth
 pub const JSON_RPC_ERROR_DEFAULT: i32 = -32000;
 
+/// Maximum number of executions of `eth_createAccessList` waiting for the access list to settle.
+const MAX_ACCESS_LIST_ITERATIONS: usize = 16;
+
 /// Allow to adapt a request for `estimate_gas`.
 /// Can be used to estimate gas of some contracts using a different function
 /// in the case the normal gas estimation doesn't work.
@@ -74,7 +79,12 @@ where
 	A: ChainApi<Block = B> + 'static,
 	EGA: EstimateGasAdapter,
 {
-	pub fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+	pub fn call(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Bytes> {
 		let CallRequest {
 			from,
 			to,
@@ -122,6 +132,7 @@ where
 			} else {
 				return Err(internal_err("failed to retrieve Runtime Api version"));
 			};
+		let state_override = runtime_state_override(state_overrides, api_version)?;
 
 		let block = if api_version > 1 {
 			api.current_block(&id)
@@ -196,9 +207,36 @@ where
 
 					error_on_execution_failure(&info.exit_reason, &info.value)?;
 					Ok(Bytes(info.value))
-				} else if api_version == 4 {
+				} else if api_version >= 4 && api_version < 6 {
 					// Post-london + access list support
 					let access_list = access_list.unwrap_or_default();
+					#[allow(deprecated)]
+					let info = api.call_before_version_6(
+						&id,
+						from.unwrap_or_default(),
+						to,
+						data,
+						value.unwrap_or_default(),
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						Some(
+							access_list
+								.into_iter()
+								.map(|item| (item.address, item.storage_keys))
+								.collect(),
+						),
+					)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+					error_on_execution_failure(&info.exit_reason, &info.value)?;
+					Ok(Bytes(info.value))
+				} else if api_version >= 6 {
+					// Post-london + access list + state override support
+					let access_list = access_list.unwrap_or_default();
 					let info = api
 						.call(
 							&id,
@@ -217,6 +255,7 @@ where
 									.map(|item| (item.address, item.storage_keys))
 									.collect(),
 							),
+							state_override,
 						)
 						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
@@ -273,9 +312,39 @@ where
 						.account_code_at(&id, info.value)
 						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
 					Ok(Bytes(code))
-				} else if api_version == 4 {
+				} else if api_version >= 4 && api_version < 6 {
 					// Post-london + access list support
 					let access_list = access_list.unwrap_or_default();
+					#[allow(deprecated)]
+					let info = api.create_before_version_6(
+						&id,
+						from.unwrap_or_default(),
+						data,
+						value.unwrap_or_default(),
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						Some(
+							access_list
+								.into_iter()
+								.map(|item| (item.address, item.storage_keys))
+								.collect(),
+						),
+					)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+					error_on_execution_failure(&info.exit_reason, &[])?;
+
+					let code = api
+						.account_code_at(&id, info.value)
+						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+					Ok(Bytes(code))
+				} else if api_version >= 6 {
+					// Post-london + access list + state override support
+					let access_list = access_list.unwrap_or_default();
 					let info = api
 						.create(
 							&id,
@@ -293,6 +362,7 @@ where
 									.map(|item| (item.address, item.storage_keys))
 									.collect(),
 							),
+							state_override,
 						)
 						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
@@ -310,15 +380,425 @@ where
 		}
 	}
 
-	pub async fn estimate_gas(&self, request: CallRequest, _: Option<BlockNumber>) -> Result<U256> {
+	pub fn call_many(
+		&self,
+		bundles: Vec<CallBundle>,
+		context: CallManyContext,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Vec<Vec<CallManyResult>>> {
+		if !matches!(context.transaction_index, None | Some(-1)) {
+			return Err(internal_err(
+				"eth_callMany only executes on top of the whole block, transactionIndex must be -1",
+			));
+		}
+
+		let (id, api) = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(context.block_number),
+		)? {
+			Some(id) => (id, self.client.runtime_api()),
+			None => {
+				// Not mapped in the db, assume pending.
+				let id = BlockId::Hash(self.client.info().best_hash);
+				let api = pending_runtime_api(self.client.as_ref(), self.graph.as_ref())?;
+				(id, api)
+			}
+		};
+
+		if let Ok(BlockStatus::Unknown) = self.client.status(id) {
+			return Err(crate::err(JSON_RPC_ERROR_DEFAULT, "header not found", None));
+		}
+
+		let api_version =
+			if let Ok(Some(api_version)) = api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&id) {
+				api_version
+			} else {
+				return Err(internal_err("failed to retrieve Runtime Api version"));
+			};
+		if api_version < 6 {
+			return Err(internal_err("eth_callMany is not supported by the runtime"));
+		}
+		let mut state_override = runtime_state_override(state_overrides, api_version)?;
+
+		let block_gas_limit = api
+			.current_block(&id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+			.header
+			.gas_limit;
+		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+
+		// All the calls are made with the same ApiRef instance, so that the overlayed state of
+		// an execution is seen by the next ones.
+		// The block overrides of a bundle are kept by the next bundles, they are passed to each
+		// execution as the runtime only applies them for the duration of a call.
+		let mut log_index = 0usize;
+		let mut block_override: Option<fp_evm::BlockOverride> = None;
+		let mut results = Vec::with_capacity(bundles.len());
+		for bundle in bundles {
+			if let Some(bundle_override) = bundle.block_override {
+				if api_version < 8 {
+					return Err(internal_err(
+						"block overrides are not supported by the runtime",
+					));
+				}
+				let bundle_override = runtime_block_override(bundle_override)?;
+				let previous = block_override.take().unwrap_or_default();
+				block_override = Some(fp_evm::BlockOverride {
+					number: bundle_override.number.or(previous.number),
+					timestamp: bundle_override.timestamp.or(previous.timestamp),
+					base_fee: bundle_override.base_fee.or(previous.base_fee),
+					coinbase: bundle_override.coinbase.or(previous.coinbase),
+				});
+			}
+
+			let mut bundle_results = Vec::with_capacity(bundle.transactions.len());
+			for request in bundle.transactions {
+				let CallRequest {
+					from,
+					to,
+					gas_price,
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					gas,
+					value,
+					data,
+					nonce,
+					access_list,
+					..
+				} = request;
+
+				let (max_fee_per_gas, max_priority_fee_per_gas) = {
+					let details =
+						fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+					(details.max_fee_per_gas, details.max_priority_fee_per_gas)
+				};
+				let gas_limit = match gas {
+					Some(amount) if amount > max_gas_limit => {
+						return Err(internal_err(format!(
+							"provided gas limit is too high (can be up to {}x the block gas limit)",
+							self.execute_gas_limit_multiplier
+						)));
+					}
+					Some(amount) => amount,
+					None => max_gas_limit,
+				};
+				let from = from.unwrap_or_default();
+				let data = data.map(|d| d.0).unwrap_or_default();
+				let value = value.unwrap_or_default();
+				let access_list = Some(
+					access_list
+						.unwrap_or_default()
+						.into_iter()
+						.map(|item| (item.address, item.storage_keys))
+						.collect(),
+				);
+				// The state overrides are applied once, before the first call.
+				let state_override = state_override.take();
+
+				let info = match to {
+					Some(to) => match block_override.clone() {
+						None => api.call(
+							&id,
+							from,
+							to,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							false,
+							access_list,
+							state_override,
+						),
+						Some(block_override) => api.call_with_block_override(
+							&id,
+							from,
+							to,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							access_list,
+							state_override,
+							block_override,
+						),
+					}
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?,
+					None => match block_override.clone() {
+						None => api.create(
+							&id,
+							from,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							false,
+							access_list,
+							state_override,
+						),
+						Some(block_override) => api.create_with_block_override(
+							&id,
+							from,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							access_list,
+							state_override,
+							block_override,
+						),
+					}
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map(|info| {
+						let code = if info.exit_reason.is_succeed() {
+							api.account_code_at(&id, info.value).unwrap_or_default()
+						} else {
+							Vec::new()
+						};
+						CallInfo {
+							exit_reason: info.exit_reason,
+							value: code,
+							used_gas: info.used_gas,
+							logs: info.logs,
+						}
+					}),
+				};
+
+				let result = match info {
+					Ok(info) => {
+						let error = match info.exit_reason {
+							ExitReason::Succeed(_) => None,
+							ExitReason::Revert(_) => Some("execution reverted".to_string()),
+							ExitReason::Error(e) => Some(format!("evm error: {:?}", e)),
+							ExitReason::Fatal(e) => Some(format!("evm fatal: {:?}", e)),
+						};
+						let logs = info
+							.logs
+							.into_iter()
+							.enumerate()
+							.map(|(transaction_log_index, log)| {
+								log_index += 1;
+								Log {
+									address: log.address,
+									topics: log.topics,
+									data: Bytes(log.data),
+									block_hash: None,
+									block_number: None,
+									transaction_hash: None,
+									transaction_index: None,
+									log_index: Some(U256::from(log_index - 1)),
+									transaction_log_index: Some(U256::from(transaction_log_index)),
+									removed: false,
+								}
+							})
+							.collect();
+						CallManyResult {
+							value: Bytes(info.value),
+							error,
+							gas_used: info.used_gas,
+							logs,
+						}
+					}
+					Err(err) => CallManyResult {
+						value: Bytes(Vec::new()),
+						error: Some(format!("execution fatal: {:?}", err)),
+						gas_used: U256::zero(),
+						logs: Vec::new(),
+					},
+				};
+				bundle_results.push(result);
+			}
+			results.push(bundle_results);
+		}
+
+		Ok(results)
+	}
+
+	pub fn create_access_list(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+	) -> Result<AccessListResult> {
+		let CallRequest {
+			from,
+			to,
+			gas_price,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			gas,
+			value,
+			data,
+			nonce,
+			access_list,
+			..
+		} = request;
+
+		let (max_fee_per_gas, max_priority_fee_per_gas) = {
+			let details = fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+			(details.max_fee_per_gas, details.max_priority_fee_per_gas)
+		};
+
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number,
+		)?;
+		// Not mapped in the db, assume pending.
+		let is_pending = id.is_none();
+		let id = id.unwrap_or_else(|| BlockId::Hash(self.client.info().best_hash));
+
+		if let Ok(BlockStatus::Unknown) = self.client.status(id) {
+			return Err(crate::err(JSON_RPC_ERROR_DEFAULT, "header not found", None));
+		}
+
+		// A new ApiRef instance is used per execution, so that the overlayed state of an
+		// execution does not affect the next one.
+		let runtime_api = || {
+			if is_pending {
+				pending_runtime_api(self.client.as_ref(), self.graph.as_ref())
+			} else {
+				Ok(self.client.runtime_api())
+			}
+		};
+
+		let api = runtime_api()?;
+		let api_version =
+			if let Ok(Some(api_version)) = api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&id) {
+				api_version
+			} else {
+				return Err(internal_err("failed to retrieve Runtime Api version"));
+			};
+		if api_version < 7 {
+			return Err(internal_err(
+				"eth_createAccessList is not supported by the runtime",
+			));
+		}
+
+		let block_gas_limit = api
+			.current_block(&id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+			.header
+			.gas_limit;
+		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+
+		// use given gas limit or query current block's limit
+		let gas_limit = match gas {
+			Some(amount) => {
+				if amount > max_gas_limit {
+					return Err(internal_err(format!(
+						"provided gas limit is too high (can be up to {}x the block gas limit)",
+						self.execute_gas_limit_multiplier
+					)));
+				}
+				amount
+			}
+			None => max_gas_limit,
+		};
+
+		let from = from.unwrap_or_default();
+		let data = data.map(|d| d.0).unwrap_or_default();
+		let value = value.unwrap_or_default();
+
+		// Warming up addresses and storage keys may change the execution path, so execute
+		// again with the collected access list until it does not change anymore. An access list
+		// seen before would repeat the same cycle of executions.
+		let mut access_list: Vec<(H160, Vec<H256>)> = access_list
+			.unwrap_or_default()
+			.into_iter()
+			.map(|item| (item.address, item.storage_keys))
+			.collect();
+		let mut seen = Vec::new();
+		for _ in 0..MAX_ACCESS_LIST_ITERATIONS {
+			let (info, accessed) = runtime_api()?
+				.create_access_list(
+					&id,
+					from,
+					to,
+					data.clone(),
+					value,
+					gas_limit,
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					Some(access_list.clone()),
+				)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+				.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+			if accessed == access_list {
+				let error = match info.exit_reason {
+					ExitReason::Succeed(_) => None,
+					ExitReason::Revert(_) => Some("execution reverted".to_string()),
+					ExitReason::Error(e) => Some(format!("evm error: {:?}", e)),
+					ExitReason::Fatal(e) => Some(format!("evm fatal: {:?}", e)),
+				};
+				return Ok(AccessListResult {
+					access_list: accessed
+						.into_iter()
+						.map(|(address, storage_keys)| AccessListItem {
+							address,
+							storage_keys,
+						})
+						.collect(),
+					gas_used: info.used_gas,
+					error,
+				});
+			}
+			if seen.contains(&accessed) {
+				return Err(internal_err("access list does not converge"));
+			}
+			seen.push(std::mem::replace(&mut access_list, accessed));
+		}
+		Err(internal_err(format!(
+			"access list did not converge after {} executions",
+			MAX_ACCESS_LIST_ITERATIONS
+		)))
+	}
+
+	pub async fn estimate_gas(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<U256> {
 		let client = Arc::clone(&self.client);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
 
 		// Define the lower bound of estimate
 		const MIN_GAS_PER_TX: U256 = U256([21_000, 0, 0, 0]);
 
-		// Get best hash (TODO missing support for estimating gas historically)
-		let best_hash = client.info().best_hash;
+		let (substrate_hash, is_pending) = match frontier_backend_client::native_block_id::<B, C>(
+			client.as_ref(),
+			self.backend.as_ref(),
+			number,
+		)? {
+			Some(id) => match client.block_hash_from_id(&id) {
+				Ok(Some(hash)) => (hash, false),
+				_ => return Err(crate::err(JSON_RPC_ERROR_DEFAULT, "header not found", None)),
+			},
+			// Not mapped in the db, assume pending.
+			None => (client.info().best_hash, true),
+		};
+
+		// A new ApiRef instance is used per execution, so that the overlayed state of an
+		// execution does not affect the next one.
+		let runtime_api = || {
+			if is_pending {
+				pending_runtime_api(client.as_ref(), self.graph.as_ref())
+			} else {
+				Ok(client.runtime_api())
+			}
+		};
 
 		// Adapt request for gas estimation.
 		let request = EGA::adapt_request(request);
@@ -330,10 +810,16 @@ where
 		};
 		if is_simple_transfer {
 			if let Some(to) = request.to {
-				let to_code = client
-					.runtime_api()
-					.account_code_at(&BlockId::Hash(best_hash), to)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+				let overridden_code = state_overrides
+					.as_ref()
+					.and_then(|state_overrides| state_overrides.get(&to))
+					.and_then(|state_override| state_override.code.as_ref());
+				let to_code = match overridden_code {
+					Some(code) => code.0.clone(),
+					None => runtime_api()?
+						.account_code_at(&BlockId::Hash(substrate_hash), to)
+						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?,
+				};
 				if to_code.is_empty() {
 					return Ok(MIN_GAS_PER_TX);
 				}
@@ -354,7 +840,6 @@ where
 		};
 
 		let block_gas_limit = {
-			let substrate_hash = client.info().best_hash;
 			let id = BlockId::Hash(substrate_hash);
 			let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(&client, id);
 			let block = block_data_cache.current_block(schema, substrate_hash).await;
@@ -368,7 +853,7 @@ where
 		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
 
 		// Determine the highest possible gas limits
-		let mut highest = match request.gas {
+		let (mut highest, mut cap_bound) = match request.gas {
 			Some(amount) => {
 				if amount > max_gas_limit {
 					return Err(internal_err(format!(
@@ -376,21 +861,29 @@ where
 						self.execute_gas_limit_multiplier
 					)));
 				}
-				amount
+				(amount, GasCapBound::Request)
 			}
-			None => max_gas_limit,
+			None => (max_gas_limit, GasCapBound::Block),
 		};
 
-		let api = client.runtime_api();
+		let api = runtime_api()?;
 
 		// Recap the highest gas allowance with account's balance.
 		if let Some(from) = request.from {
 			let gas_price = gas_price.unwrap_or_default();
 			if gas_price > U256::zero() {
-				let balance = api
-					.account_basic(&BlockId::Hash(best_hash), from)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-					.balance;
+				let overridden_balance = state_overrides
+					.as_ref()
+					.and_then(|state_overrides| state_overrides.get(&from))
+					.and_then(|state_override| state_override.balance);
+				let balance = match overridden_balance {
+					Some(balance) => balance,
+					None => {
+						api.account_basic(&BlockId::Hash(substrate_hash), from)
+							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+							.balance
+					}
+				};
 				let mut available = balance;
 				if let Some(value) = request.value {
 					if value > available {
@@ -409,6 +902,7 @@ where
 							allowance
 						);
 					highest = allowance;
+					cap_bound = GasCapBound::Balance;
 				}
 			}
 		}
@@ -424,15 +918,14 @@ where
 		// A new ApiRef instance needs to be used per execution to avoid the overlayed state to affect
 		// the estimation result of subsequent calls.
 		//
-		// Note that this would have a performance penalty if we introduce gas estimation for past
-		// blocks - and thus, past runtime versions. Substrate has a default `runtime_cache_size` of
-		// 2 slots LRU-style, meaning if users were to access multiple runtime versions in a short period
-		// of time, the RPC response time would degrade a lot, as the VersionedRuntime needs to be compiled.
-		//
-		// To solve that, and if we introduce historical gas estimation, we'd need to increase that default.
+		// Note that estimating against past blocks - and thus, past runtime versions - has a performance
+		// penalty. Substrate has a default `runtime_cache_size` of 2 slots LRU-style, meaning if users
+		// were to access multiple runtime versions in a short period of time, the RPC response time
+		// would degrade a lot, as the VersionedRuntime needs to be compiled.
 		#[rustfmt::skip]
 			let executable = move |
-				request, gas_limit, api_version, api: sp_api::ApiRef<'_, C::Api>, estimate_mode
+				request, gas_limit, api_version, api: sp_api::ApiRef<'_, C::Api>, estimate_mode,
+				state_override
 			| -> Result<ExecutableResult> {
 				let CallRequest {
 					from,
@@ -456,7 +949,7 @@ where
 							// Legacy pre-london
 							#[allow(deprecated)]
 							api.call_before_version_2(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								to,
 								data,
@@ -472,7 +965,7 @@ where
 							// Post-london
 							#[allow(deprecated)]
 							api.call_before_version_4(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								to,
 								data,
@@ -485,11 +978,35 @@ where
 							)
 							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
-						} else {
+						} else if api_version < 6 {
 							// Post-london + access list support
 							let access_list = access_list.unwrap_or_default();
+							#[allow(deprecated)]
+							api.call_before_version_6(
+								&BlockId::Hash(substrate_hash),
+								from.unwrap_or_default(),
+								to,
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								nonce,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
+						} else {
+							// Post-london + access list + state override support
+							let access_list = access_list.unwrap_or_default();
 							api.call(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								to,
 								data,
@@ -505,6 +1022,7 @@ where
 										.map(|item| (item.address, item.storage_keys))
 										.collect(),
 								),
+								state_override,
 							)
 							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
@@ -517,7 +1035,7 @@ where
 							// Legacy pre-london
 							#[allow(deprecated)]
 							api.create_before_version_2(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								data,
 								value.unwrap_or_default(),
@@ -532,7 +1050,7 @@ where
 							// Post-london
 							#[allow(deprecated)]
 							api.create_before_version_4(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								data,
 								value.unwrap_or_default(),
@@ -544,11 +1062,34 @@ where
 							)
 							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
-						} else {
+						} else if api_version < 6 {
 							// Post-london + access list support
 							let access_list = access_list.unwrap_or_default();
+							#[allow(deprecated)]
+							api.create_before_version_6(
+								&BlockId::Hash(substrate_hash),
+								from.unwrap_or_default(),
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								nonce,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
+						} else {
+							// Post-london + access list + state override support
+							let access_list = access_list.unwrap_or_default();
 							api.create(
-								&BlockId::Hash(best_hash),
+								&BlockId::Hash(substrate_hash),
 								from.unwrap_or_default(),
 								data,
 								value.unwrap_or_default(),
@@ -563,6 +1104,7 @@ where
 										.map(|item| (item.address, item.storage_keys))
 										.collect(),
 								),
+								state_override,
 							)
 							.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
 							.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
@@ -578,14 +1120,13 @@ where
 				})
 			};
 		let api_version = if let Ok(Some(api_version)) =
-			client
-				.runtime_api()
-				.api_version::<dyn EthereumRuntimeRPCApi<B>>(&BlockId::Hash(best_hash))
+			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&BlockId::Hash(substrate_hash))
 		{
 			api_version
 		} else {
 			return Err(internal_err("failed to retrieve Runtime Api version"));
 		};
+		let state_override = runtime_state_override(state_overrides, api_version)?;
 
 		// Verify that the transaction succeed with highest capacity
 		let cap = highest;
@@ -598,49 +1139,39 @@ where
 			request.clone(),
 			highest,
 			api_version,
-			client.runtime_api(),
+			runtime_api()?,
 			estimate_mode,
+			state_override.clone(),
 		)?;
 		match exit_reason {
 			ExitReason::Succeed(_) => (),
-			ExitReason::Error(ExitError::OutOfGas) => {
-				return Err(internal_err(format!(
-					"gas required exceeds allowance {}",
-					cap
-				)))
-			}
+			ExitReason::Error(ExitError::OutOfGas) => return Err(cap_bound.out_of_gas(cap)),
 			// If the transaction reverts, there are two possible cases,
 			// it can revert because the called contract feels that it does not have enough
 			// gas left to continue, or it can revert for another reason unrelated to gas.
-			ExitReason::Revert(revert) => {
-				if request.gas.is_some() || request.gas_price.is_some() {
-					// If the user has provided a gas limit or a gas price, then we have executed
-					// with less block gas limit, so we must reexecute with block gas limit to
-					// know if the revert is due to a lack of gas or not.
-					let ExecutableResult {
-						data,
-						exit_reason,
-						used_gas: _,
-					} = executable(
-						request.clone(),
-						max_gas_limit,
-						api_version,
-						client.runtime_api(),
-						estimate_mode,
-					)?;
-					match exit_reason {
-						ExitReason::Succeed(_) => {
-							return Err(internal_err(format!(
-								"gas required exceeds allowance {}",
-								cap
-							)))
-						}
-						// The execution has been done with block gas limit, so it is not a lack of gas from the user.
-						other => error_on_execution_failure(&other, &data)?,
-					}
-				} else {
-					// The execution has already been done with block gas limit, so it is not a lack of gas from the user.
-					error_on_execution_failure(&ExitReason::Revert(revert), &data)?
+			ExitReason::Revert(_) => {
+				if let Some(err) = cap_bound.reverted(&data) {
+					return Err(err);
+				}
+				// If the gas limit or the balance of the user capped the gas, then we have
+				// executed with less block gas limit, so we must reexecute with block gas
+				// limit to know if the revert is due to a lack of gas or not.
+				let ExecutableResult {
+					data,
+					exit_reason,
+					used_gas: _,
+				} = executable(
+					request.clone(),
+					max_gas_limit,
+					api_version,
+					runtime_api()?,
+					estimate_mode,
+					state_override.clone(),
+				)?;
+				match exit_reason {
+					ExitReason::Succeed(_) => return Err(cap_bound.out_of_gas(cap)),
+					// The execution has been done with block gas limit, so it is not a lack of gas from the user.
+					other => error_on_execution_failure(&other, &data)?,
 				}
 			}
 			other => error_on_execution_failure(&other, &data)?,
@@ -663,6 +1194,13 @@ where
 			// Execute the binary search and hone in on an executable gas limit.
 			let mut previous_highest = highest;
 			while (highest - lowest) > U256::one() {
+				// Stop once the estimate is within the accepted error ratio.
+				if (highest - lowest).saturating_mul(U256::from(1_000_000))
+					< highest.saturating_mul(U256::from(self.estimate_gas_error_ratio_ppm))
+				{
+					break;
+				}
+
 				let ExecutableResult {
 					data,
 					exit_reason,
@@ -671,8 +1209,9 @@ where
 					request.clone(),
 					mid,
 					api_version,
-					client.runtime_api(),
+					runtime_api()?,
 					estimate_mode,
+					state_override.clone(),
 				)?;
 				match exit_reason {
 					ExitReason::Succeed(_) => {
@@ -699,6 +1238,44 @@ where
 	}
 }
 
+/// What limits the gas limits tried by eth_estimateGas, and so how a failure at that limit is
+/// reported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum GasCapBound {
+	/// The block gas limit, times `execute_gas_limit_multiplier`.
+	Block,
+	/// The gas limit of the request.
+	Request,
+	/// The balance of the sender, at the gas price of the request.
+	Balance,
+}
+
+impl GasCapBound {
+	/// Error of a transaction which does not succeed with `cap` gas.
+	fn out_of_gas(self, cap: U256) -> jsonrpsee::core::Error {
+		match self {
+			GasCapBound::Balance => internal_err(format!(
+				"insufficient funds for gas * price + value: gas required exceeds allowance {}",
+				cap
+			)),
+			GasCapBound::Block | GasCapBound::Request => {
+				internal_err(format!("gas required exceeds allowance {}", cap))
+			}
+		}
+	}
+
+	/// Error of a transaction which reverts with `data` at the cap, or `None` if the revert may
+	/// be due to a lack of gas and the transaction must be executed again at the block gas limit.
+	/// A revert with data is a failure of its own, whatever the cap.
+	fn reverted(self, data: &[u8]) -> Option<jsonrpsee::core::Error> {
+		if self == GasCapBound::Block || !data.is_empty() {
+			error_on_execution_failure(&ExitReason::Revert(ExitRevert::Reverted), data).err()
+		} else {
+			None
+		}
+	}
+}
+
 pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> Result<()> {
 	match reason {
 		ExitReason::Succeed(_) => Ok(()),
@@ -740,6 +1317,77 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> Result<()
 	}
 }
 
+/// Convert the `stateOverride` parameter of `eth_call` and `eth_estimateGas` to the runtime
+/// representation, checking it can be applied by a runtime of version `api_version`.
+fn runtime_state_override(
+	state_overrides: Option<CallStateOverrides>,
+	api_version: u32,
+) -> Result<Option<Vec<(H160, AccountOverride)>>> {
+	let state_overrides = match state_overrides {
+		Some(state_overrides) => state_overrides,
+		None => return Ok(None),
+	};
+	if api_version < 6 {
+		return Err(internal_err(
+			"state overrides are not supported by the runtime",
+		));
+	}
+
+	state_overrides
+		.into_iter()
+		.map(|(address, state_override)| {
+			if state_override.state.is_some() && state_override.state_diff.is_some() {
+				return Err(internal_err(format!(
+					"account {:?} has both 'state' and 'stateDiff'",
+					address
+				)));
+			}
+			Ok((
+				address,
+				AccountOverride {
+					balance: state_override.balance,
+					nonce: state_override.nonce,
+					code: state_override.code.map(|code| code.into_vec()),
+					state: state_override
+						.state
+						.map(|state| state.into_iter().collect()),
+					state_diff: state_override
+						.state_diff
+						.map(|state_diff| state_diff.into_iter().collect()),
+				},
+			))
+		})
+		.collect::<Result<Vec<_>>>()
+		.map(Some)
+}
+
+/// Convert the `blockOverride` of an `eth_callMany` bundle to the runtime representation.
+fn runtime_block_override(block_override: BlockOverride) -> Result<fp_evm::BlockOverride> {
+	if block_override.gas_limit.is_some() {
+		return Err(internal_err("gasLimit block override is not supported"));
+	}
+	if block_override.difficulty.is_some() {
+		return Err(internal_err("difficulty block override is not supported"));
+	}
+	let to_u64 = |field: &str, value: Option<U256>| -> Result<Option<u64>> {
+		value
+			.map(|value| {
+				if value > U256::from(u64::MAX) {
+					Err(internal_err(format!("{} override is too high", field)))
+				} else {
+					Ok(value.as_u64())
+				}
+			})
+			.transpose()
+	};
+	Ok(fp_evm::BlockOverride {
+		number: to_u64("block number", block_override.block_number)?,
+		timestamp: to_u64("timestamp", block_override.timestamp)?,
+		base_fee: block_override.base_fee,
+		coinbase: block_override.coinbase,
+	})
+}
+
 struct FeeDetails {
 	gas_price: Option<U256>,
 	max_fee_per_gas: Option<U256>,
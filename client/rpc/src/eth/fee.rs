@@ -32,7 +32,10 @@ use sp_runtime::{
 use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{eth::Eth, frontier_backend_client, internal_err};
+use crate::{
+	eth::{cache::GAS_PRICE_ORACLE_SAMPLES, Eth},
+	frontier_backend_client, internal_err,
+};
 
 impl<B, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A>
 where
@@ -46,10 +49,47 @@ where
 	pub fn gas_price(&self) -> Result<U256> {
 		let block = BlockId::Hash(self.client.info().best_hash);
 
-		self.client
+		let base_fee = self
+			.client
 			.runtime_api()
 			.gas_price(&block)
-			.map_err(|err| internal_err(format!("fetch runtime chain id failed: {:?}", err)))
+			.map_err(|err| internal_err(format!("fetch runtime chain id failed: {:?}", err)))?;
+		Ok(base_fee.saturating_add(self.suggested_priority_fee()?))
+	}
+
+	/// Priority fee at the configured percentile of the lowest ones paid in recent blocks.
+	// https://github.com/ethereum/go-ethereum/blob/master/eth/gasprice/gasprice.go
+	fn suggested_priority_fee(&self) -> Result<U256> {
+		let config = &self.gas_price_oracle;
+		let highest =
+			UniqueSaturatedInto::<u64>::unique_saturated_into(self.client.info().best_number);
+		let lowest = highest.saturating_sub(config.blocks.saturating_sub(1));
+
+		let mut rewards = Vec::new();
+		if let Ok(fee_history_cache) = &self.fee_history_cache.lock() {
+			for n in lowest..highest + 1 {
+				if let Some(block) = fee_history_cache.get(&n) {
+					rewards.extend(
+						block
+							.lowest_rewards
+							.iter()
+							.map(|r| U256::from(*r))
+							.filter(|r| *r >= config.ignore_price)
+							.take(GAS_PRICE_ORACLE_SAMPLES),
+					);
+				}
+			}
+		} else {
+			return Err(internal_err("Failed to read fee oracle cache."));
+		}
+		rewards.sort();
+
+		let index = rewards.len().saturating_sub(1) * config.percentile.min(100) as usize / 100;
+		Ok(rewards
+			.get(index)
+			.copied()
+			.unwrap_or_default()
+			.min(config.max_price))
 	}
 
 	pub fn fee_history(
@@ -16,7 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{marker::PhantomData, sync::Arc, time};
+use std::{
+	collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+	marker::PhantomData,
+	sync::Arc,
+	time,
+};
 
 use ethereum::BlockV2 as EthereumBlock;
 use ethereum_types::{H256, U256};
@@ -31,6 +36,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, NumberFor, One, Saturating, UniqueSaturatedInto},
 };
 // Frontier
+use fc_db::IndexedLog;
 use fc_rpc_core::{types::*, EthFilterApiServer};
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
@@ -234,6 +240,7 @@ where
 		};
 
 		let client = Arc::clone(&self.client);
+		let backend = Arc::clone(&self.backend);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
 		let max_past_logs = self.max_past_logs;
 
@@ -267,6 +274,7 @@ where
 				let mut ret: Vec<Log> = Vec::new();
 				let _ = filter_range_logs(
 					client.as_ref(),
+					backend.as_ref(),
 					&block_data_cache,
 					&mut ret,
 					max_past_logs,
@@ -306,6 +314,7 @@ where
 		})();
 
 		let client = Arc::clone(&self.client);
+		let backend = Arc::clone(&self.backend);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
 		let max_past_logs = self.max_past_logs;
 
@@ -331,6 +340,7 @@ where
 		let mut ret: Vec<Log> = Vec::new();
 		let _ = filter_range_logs(
 			client.as_ref(),
+			backend.as_ref(),
 			&block_data_cache,
 			&mut ret,
 			max_past_logs,
@@ -410,6 +420,7 @@ where
 
 			let _ = filter_range_logs(
 				client.as_ref(),
+				backend.as_ref(),
 				&block_data_cache,
 				&mut ret,
 				max_past_logs,
@@ -425,6 +436,7 @@ where
 
 async fn filter_range_logs<B: BlockT, C, BE>(
 	client: &C,
+	backend: &fc_db::Backend<B>,
 	block_data_cache: &EthBlockDataCacheTask<B>,
 	ret: &mut Vec<Log>,
 	max_past_logs: u32,
@@ -440,6 +452,39 @@ where
 	BE: Backend<B> + 'static,
 	BE::State: StateBackend<BlakeTwo256>,
 {
+	let from_number = UniqueSaturatedInto::<u64>::unique_saturated_into(from);
+	let to_number = UniqueSaturatedInto::<u64>::unique_saturated_into(to);
+	if let Some(blocks) = indexed_blocks(client, backend, filter, from_number, to_number)? {
+		for (number, substrate_hash) in blocks {
+			// The index holds the logs of every fork, skip the non canonical blocks.
+			let canonical_hash = client
+				.hash(number.unique_saturated_into())
+				.map_err(|err| internal_err(format!("{:?}", err)))?;
+			if canonical_hash != Some(substrate_hash) {
+				continue;
+			}
+
+			let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(
+				client,
+				BlockId::Hash(substrate_hash),
+			);
+			let block = block_data_cache.current_block(schema, substrate_hash).await;
+			let statuses = block_data_cache
+				.current_transaction_statuses(schema, substrate_hash)
+				.await;
+			if let (Some(block), Some(statuses)) = (block, statuses) {
+				filter_block_logs(ret, filter, block, statuses);
+			}
+			if ret.len() as u32 > max_past_logs {
+				return Err(internal_err(format!(
+					"query returned more than {} results",
+					max_past_logs
+				)));
+			}
+		}
+		return Ok(());
+	}
+
 	// Max request duration of 10 seconds.
 	let max_duration = time::Duration::from_secs(10);
 	let begin_request = time::Instant::now();
@@ -500,6 +545,108 @@ where
 	Ok(())
 }
 
+/// Blocks of `from..=to` that may hold logs matching `filter`, according to the log index.
+///
+/// Returns `None` when the index cannot answer the query: the filter has neither an address
+/// nor a topic, or some canonical blocks of the range have not been indexed.
+fn indexed_blocks<B: BlockT, C>(
+	client: &C,
+	backend: &fc_db::Backend<B>,
+	filter: &Filter,
+	from: u64,
+	to: u64,
+) -> Result<Option<BTreeSet<(u64, B::Hash)>>>
+where
+	C: HeaderBackend<B>,
+{
+	let log_index = backend.log_index();
+
+	let addresses = match &filter.address {
+		Some(VariadicValue::Single(address)) => vec![*address],
+		Some(VariadicValue::Multiple(addresses)) => addresses.clone(),
+		_ => Vec::new(),
+	};
+	// Positional topic patterns, a log matches the filter if it matches any of them.
+	let patterns: Vec<Vec<(u8, H256)>> = FilteredParams::new(Some(filter.clone()))
+		.flat_topics
+		.into_iter()
+		.map(|topic| match topic {
+			VariadicValue::Single(Some(topic)) => vec![(0, topic)],
+			VariadicValue::Multiple(topics) => topics
+				.into_iter()
+				.enumerate()
+				.filter_map(|(position, topic)| topic.map(|topic| (position as u8, topic)))
+				.collect(),
+			_ => Vec::new(),
+		})
+		.collect();
+	let has_topics = !patterns.is_empty() && patterns.iter().all(|pattern| !pattern.is_empty());
+
+	if addresses.is_empty() && !has_topics {
+		return Ok(None);
+	}
+	let canonical_hash = |number: u64| {
+		client
+			.hash(number.unique_saturated_into())
+			.map_err(|err| format!("{:?}", err))
+	};
+	if !log_index
+		.is_indexed(from, to, canonical_hash)
+		.map_err(internal_err)?
+	{
+		return Ok(None);
+	}
+
+	let mut candidates: Option<HashSet<IndexedLog<B::Hash>>> = None;
+	if !addresses.is_empty() {
+		let mut logs = HashSet::new();
+		for address in addresses.iter() {
+			logs.extend(
+				log_index
+					.address_logs(address, from, to)
+					.map_err(internal_err)?,
+			);
+		}
+		candidates = Some(logs);
+	}
+	if has_topics {
+		let mut topic_logs = HashMap::new();
+		let mut logs = HashSet::new();
+		for pattern in patterns {
+			let mut matching: Option<HashSet<IndexedLog<B::Hash>>> = None;
+			for (position, topic) in pattern {
+				let with_topic = match topic_logs.entry((position, topic)) {
+					Entry::Occupied(entry) => entry.into_mut(),
+					Entry::Vacant(entry) => entry.insert(
+						log_index
+							.topic_logs(position, &topic, from, to)
+							.map_err(internal_err)?
+							.into_iter()
+							.collect::<HashSet<_>>(),
+					),
+				};
+				matching = Some(match matching {
+					Some(matching) => matching.intersection(with_topic).copied().collect(),
+					None => with_topic.clone(),
+				});
+			}
+			logs.extend(matching.unwrap_or_default());
+		}
+		candidates = Some(match candidates {
+			Some(candidates) => candidates.intersection(&logs).copied().collect(),
+			None => logs,
+		});
+	}
+
+	Ok(Some(
+		candidates
+			.unwrap_or_default()
+			.into_iter()
+			.map(|log| (log.block_number, log.block_hash))
+			.collect(),
+	))
+}
+
 fn filter_block_logs<'a>(
 	ret: &'a mut Vec<Log>,
 	filter: &'a Filter,
@@ -24,17 +24,21 @@ mod fee;
 mod filter;
 pub mod format;
 mod mining;
+mod sign;
 mod state;
 mod submit;
 mod transaction;
 
-use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc, time::Duration};
 
 use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
 use ethereum_types::{H160, H256, H512, H64, U256, U64};
 use jsonrpsee::core::{async_trait, RpcResult as Result};
 // Substrate
-use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	ProofProvider,
+};
 use sc_network::NetworkService;
 use sc_network_common::ExHashT;
 use sc_transaction_pool::{ChainApi, Pool};
@@ -53,6 +57,7 @@ use fp_rpc::{ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi, TransactionSta
 
 use crate::{internal_err, overrides::OverrideHandle, public_key, signer::EthSigner};
 
+pub(crate) use self::transaction::receipts_build;
 pub use self::{
 	cache::{EthBlockDataCacheTask, EthTask},
 	execute::EstimateGasAdapter,
@@ -67,7 +72,7 @@ pub struct Eth<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi, EGA = ()> {
 	convert_transaction: Option<CT>,
 	network: Arc<NetworkService<B, H>>,
 	is_authority: bool,
-	signers: Vec<Box<dyn EthSigner>>,
+	signers: Arc<Vec<Box<dyn EthSigner>>>,
 	overrides: Arc<OverrideHandle<B>>,
 	backend: Arc<fc_db::Backend<B>>,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
@@ -76,6 +81,14 @@ pub struct Eth<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi, EGA = ()> {
 	/// When using eth_call/eth_estimateGas, the maximum allowed gas limit will be
 	/// block.gas_limit * execute_gas_limit_multiplier
 	execute_gas_limit_multiplier: u64,
+	/// With the `rpc_binary_search_estimate` feature, eth_estimateGas also stops its binary search
+	/// once `(highest - lowest) / highest` is below this ratio, in parts per million, 0 disables
+	/// it.
+	#[cfg_attr(not(feature = "rpc_binary_search_estimate"), allow(dead_code))]
+	estimate_gas_error_ratio_ppm: u64,
+	gas_price_oracle: GasPriceOracleConfig,
+	/// Longest wait of eth_sendRawTransactionSync for a receipt.
+	send_raw_transaction_sync_timeout: Duration,
 	_marker: PhantomData<(B, BE, EGA)>,
 }
 
@@ -94,6 +107,9 @@ impl<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A
 		fee_history_cache: FeeHistoryCache,
 		fee_history_cache_limit: FeeHistoryCacheLimit,
 		execute_gas_limit_multiplier: u64,
+		estimate_gas_error_ratio: f64,
+		gas_price_oracle: GasPriceOracleConfig,
+		send_raw_transaction_sync_timeout: Duration,
 	) -> Self {
 		Self {
 			client,
@@ -102,13 +118,17 @@ impl<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A
 			convert_transaction,
 			network,
 			is_authority,
-			signers,
+			signers: Arc::new(signers),
 			overrides,
 			backend,
 			block_data_cache,
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			estimate_gas_error_ratio_ppm: (estimate_gas_error_ratio.clamp(0.0, 1.0) * 1_000_000.0)
+				as u64,
+			gas_price_oracle,
+			send_raw_transaction_sync_timeout,
 			_marker: PhantomData,
 		}
 	}
@@ -118,7 +138,7 @@ impl<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A
 impl<B, C, P, CT, BE, H: ExHashT, A> EthApiServer for Eth<B, C, P, CT, BE, H, A>
 where
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
-	C: ProvideRuntimeApi<B> + StorageProvider<B, BE>,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + ProofProvider<B>,
 	C: HeaderBackend<B> + Send + Sync + 'static,
 	C::Api: BlockBuilderApi<B> + ConvertTransactionRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
 	P: TransactionPool<Block = B> + Send + Sync + 'static,
@@ -224,6 +244,10 @@ where
 		self.transaction_receipt(hash).await
 	}
 
+	async fn block_receipts(&self, number: BlockNumberOrHash) -> Result<Option<Vec<Receipt>>> {
+		self.block_receipts(number.into()).await
+	}
+
 	// ########################################################################
 	// State
 	// ########################################################################
@@ -244,20 +268,53 @@ where
 		self.code_at(address, number)
 	}
 
+	fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number: Option<BlockNumber>,
+	) -> Result<EthAccount> {
+		self.proof(address, storage_keys, number)
+	}
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
 
-	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
-		self.call(request, number)
+	fn call(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Bytes> {
+		self.call(request, number, state_overrides)
 	}
 
 	async fn estimate_gas(
 		&self,
 		request: CallRequest,
-		number: Option<BlockNumber>,
+		number: Option<BlockNumberOrHash>,
+		state_overrides: Option<CallStateOverrides>,
 	) -> Result<U256> {
-		self.estimate_gas(request, number).await
+		self.estimate_gas(request, number.map(Into::into), state_overrides)
+			.await
+	}
+
+	fn call_many(
+		&self,
+		bundles: Vec<CallBundle>,
+		context: CallManyContext,
+		state_overrides: Option<CallStateOverrides>,
+	) -> Result<Vec<Vec<CallManyResult>>> {
+		self.call_many(bundles, context, state_overrides)
+	}
+
+	fn create_access_list(
+		&self,
+		request: CallRequest,
+		number: Option<BlockNumber>,
+	) -> Result<AccessListResult> {
+		self.create_access_list(request, number)
 	}
 
 	// ########################################################################
@@ -316,9 +373,38 @@ where
 	async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256> {
 		self.send_raw_transaction(bytes).await
 	}
+
+	async fn send_raw_transaction_sync(
+		&self,
+		bytes: Bytes,
+		timeout: Option<u64>,
+	) -> Result<Receipt> {
+		self.send_raw_transaction_sync(bytes, timeout).await
+	}
+
+	// ########################################################################
+	// Sign
+	// ########################################################################
+
+	async fn sign(&self, address: H160, data: Bytes) -> Result<Bytes> {
+		self.sign(address, data).await
+	}
+
+	async fn sign_typed_data_v4(&self, address: H160, typed_data: TypedData) -> Result<Bytes> {
+		self.sign_typed_data_v4(address, typed_data).await
+	}
+
+	async fn personal_sign(
+		&self,
+		data: Bytes,
+		address: H160,
+		_password: Option<String>,
+	) -> Result<Bytes> {
+		self.sign(address, data).await
+	}
 }
 
-fn rich_block_build(
+pub(crate) fn rich_block_build(
 	block: EthereumBlock,
 	statuses: Vec<Option<TransactionStatus>>,
 	hash: Option<H256>,
@@ -384,7 +470,7 @@ fn rich_block_build(
 	}
 }
 
-fn transaction_build(
+pub(crate) fn transaction_build(
 	ethereum_transaction: EthereumTransaction,
 	block: Option<EthereumBlock>,
 	status: Option<TransactionStatus>,
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::H160;
+use jsonrpsee::core::RpcResult as Result;
+// Substrate
+use sc_network_common::ExHashT;
+use sc_transaction_pool::ChainApi;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_rpc_core::types::*;
+
+use crate::{eth::Eth, internal_err, signer::EthSigner};
+
+impl<B: BlockT, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A> {
+	/// Run `f` with the signer of `address` on a blocking thread, as external signers wait for
+	/// the user to approve each request.
+	pub(crate) async fn with_signer_of<R, F>(&self, address: H160, f: F) -> Result<R>
+	where
+		R: Send + 'static,
+		F: FnOnce(&dyn EthSigner) -> Result<R> + Send + 'static,
+	{
+		let signers = self.signers.clone();
+		tokio::task::spawn_blocking(move || {
+			let signer = signers
+				.iter()
+				.find(|signer| signer.accounts().contains(&address))
+				.ok_or_else(|| internal_err("no signer available"))?;
+			f(signer.as_ref())
+		})
+		.await
+		.map_err(|e| internal_err(format!("signer task failed: {}", e)))?
+	}
+
+	pub async fn sign(&self, address: H160, data: Bytes) -> Result<Bytes> {
+		let signature = self
+			.with_signer_of(address, move |signer| {
+				signer.sign_message(&data.into_vec(), &address)
+			})
+			.await?;
+		Ok(Bytes(signature.to_vec()))
+	}
+
+	pub async fn sign_typed_data_v4(&self, address: H160, typed_data: TypedData) -> Result<Bytes> {
+		let signature = self
+			.with_signer_of(address, move |signer| {
+				signer.sign_typed_data(&typed_data, &address)
+			})
+			.await?;
+		Ok(Bytes(signature.to_vec()))
+	}
+}
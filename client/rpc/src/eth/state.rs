@@ -20,16 +20,20 @@ use codec::Encode;
 use ethereum_types::{H160, H256, U256};
 use jsonrpsee::core::RpcResult as Result;
 // Substrate
-use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	ProofProvider,
+};
 use sc_network_common::ExHashT;
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::hashing::keccak_256;
 use sp_runtime::{
 	generic::BlockId,
-	traits::{BlakeTwo256, Block as BlockT},
+	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT},
 };
 // Frontier
 use fc_rpc_core::types::*;
@@ -43,7 +47,7 @@ use crate::{
 impl<B, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A>
 where
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
-	C: ProvideRuntimeApi<B> + StorageProvider<B, BE>,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + ProofProvider<B>,
 	C: HeaderBackend<B> + Send + Sync + 'static,
 	C::Api: BlockBuilderApi<B> + EthereumRuntimeRPCApi<B>,
 	BE: Backend<B> + 'static,
@@ -182,4 +186,89 @@ where
 			Ok(Bytes(vec![]))
 		}
 	}
+
+	pub fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		number: Option<BlockNumber>,
+	) -> Result<EthAccount> {
+		if number == Some(BlockNumber::Pending) {
+			return Err(internal_err(
+				"proofs of the pending state are not supported",
+			));
+		}
+
+		let id = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number,
+		)? {
+			Some(id) => id,
+			None => return Err(internal_err("block not found")),
+		};
+		let header = match self.client.header(id) {
+			Ok(Some(header)) => header,
+			_ => return Err(internal_err("block header not found")),
+		};
+		let hash = header.hash();
+
+		let api = self.client.runtime_api();
+		let api_version =
+			if let Ok(Some(api_version)) = api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&id) {
+				api_version
+			} else {
+				return Err(internal_err("failed to retrieve Runtime Api version"));
+			};
+		if api_version < 5 {
+			return Err(internal_err("eth_getProof is not supported by the runtime"));
+		}
+
+		let account = api.account_basic(&id, address).map_err(|err| {
+			internal_err(format!("fetch runtime account basic failed: {:?}", err))
+		})?;
+		let code = api
+			.account_code_at(&id, address)
+			.map_err(|err| internal_err(format!("fetch runtime account code failed: {:?}", err)))?;
+		let account_info_key = api
+			.account_info_key(&id, address)
+			.map_err(|err| internal_err(format!("fetch runtime account key failed: {:?}", err)))?;
+
+		let read_proof = |keys: &[&[u8]]| -> Result<Vec<Bytes>> {
+			self.client
+				.read_proof(&hash, &mut keys.iter().copied())
+				.map(|proof| proof.into_iter_nodes().map(Bytes).collect())
+				.map_err(|err| internal_err(format!("failed to generate read proof: {:?}", err)))
+		};
+
+		let code_key = fp_rpc::account_code_key(address);
+		let account_proof = read_proof(&[&account_info_key[..], &code_key[..]])?;
+
+		let storage_proof = storage_keys
+			.into_iter()
+			.map(|index| {
+				let value = api.storage_at(&id, address, index).map_err(|err| {
+					internal_err(format!("fetch runtime storage failed: {:?}", err))
+				})?;
+				let storage_key = fp_rpc::account_storage_key(address, index);
+				Ok(StorageProof {
+					key: index,
+					value: U256::from_big_endian(value.as_bytes()),
+					proof: read_proof(&[&storage_key[..]])?,
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(EthAccount {
+			address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: H256::from(keccak_256(&code)),
+			// There is no per-account storage trie: every proof is checked against the
+			// state root of the Substrate block.
+			storage_hash: *header.state_root(),
+			account_proof,
+			storage_proof,
+		})
+	}
 }
@@ -16,14 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::time::Duration;
+
 use ethereum_types::H256;
-use futures::future::TryFutureExt;
+use futures::{future::TryFutureExt, StreamExt};
 use jsonrpsee::core::RpcResult as Result;
 // Substrate
 use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
 use sc_network_common::ExHashT;
 use sc_transaction_pool::ChainApi;
-use sc_transaction_pool_api::TransactionPool;
+use sc_transaction_pool_api::{TransactionPool, TransactionStatus};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
@@ -37,10 +39,17 @@ use fc_rpc_core::types::*;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
 
 use crate::{
+	err,
 	eth::{format, Eth},
 	internal_err,
 };
 
+/// Error code of an `eth_sendRawTransactionSync` call timing out, its data is the transaction hash.
+const TRANSACTION_RECEIPT_TIMEOUT_CODE: i32 = 4;
+
+/// Interval between two reads of the receipt of an included transaction.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 impl<B, C, P, CT, BE, H: ExHashT, A: ChainApi> Eth<B, C, P, CT, BE, H, A>
 where
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
@@ -133,22 +142,9 @@ where
 			_ => return Err(internal_err("invalid transaction parameters")),
 		};
 
-		let mut transaction = None;
-
-		for signer in &self.signers {
-			if signer.accounts().contains(&from) {
-				match signer.sign(message, &from) {
-					Ok(t) => transaction = Some(t),
-					Err(e) => return Err(e),
-				}
-				break;
-			}
-		}
-
-		let transaction = match transaction {
-			Some(transaction) => transaction,
-			None => return Err(internal_err("no signer available")),
-		};
+		let transaction = self
+			.with_signer_of(from, move |signer| signer.sign(message, &from))
+			.await?;
 		let transaction_hash = transaction.hash();
 
 		let block_hash = BlockId::hash(self.client.info().best_hash);
@@ -209,7 +205,9 @@ where
 			.await
 	}
 
-	pub async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256> {
+	/// Hash of the raw transaction, along with the best block and the extrinsic to submit to the
+	/// pool.
+	fn raw_transaction_extrinsic(&self, bytes: Bytes) -> Result<(H256, BlockId<B>, B::Extrinsic)> {
 		let slice = &bytes.0[..];
 		if slice.is_empty() {
 			return Err(internal_err("transaction data is empty"));
@@ -275,10 +273,71 @@ where
 			}
 		};
 
+		Ok((transaction_hash, block_hash, extrinsic))
+	}
+
+	pub async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256> {
+		let (transaction_hash, block_hash, extrinsic) = self.raw_transaction_extrinsic(bytes)?;
+
 		self.pool
 			.submit_one(&block_hash, TransactionSource::Local, extrinsic)
 			.map_ok(move |_| transaction_hash)
 			.map_err(|err| internal_err(format::Geth::pool_error(err)))
 			.await
 	}
+
+	/// Submits the raw transaction and waits for its receipt, for at most `timeout` milliseconds
+	/// and never longer than the configured timeout.
+	pub async fn send_raw_transaction_sync(
+		&self,
+		bytes: Bytes,
+		timeout: Option<u64>,
+	) -> Result<Receipt> {
+		let timeout = timeout.map_or(self.send_raw_transaction_sync_timeout, |timeout| {
+			Duration::from_millis(timeout).min(self.send_raw_transaction_sync_timeout)
+		});
+		let (transaction_hash, block_hash, extrinsic) = self.raw_transaction_extrinsic(bytes)?;
+
+		let mut statuses = self
+			.pool
+			.submit_and_watch(&block_hash, TransactionSource::Local, extrinsic)
+			.map_err(|err| internal_err(format::Geth::pool_error(err)))
+			.await?;
+		let receipt = async {
+			while let Some(status) = statuses.next().await {
+				match status {
+					TransactionStatus::InBlock(_) | TransactionStatus::Finalized(_) => {
+						// The receipt is readable once the mapping sync worker indexed the block.
+						loop {
+							match self.transaction_receipt(transaction_hash).await {
+								Ok(Some(receipt)) => return Ok(receipt),
+								Ok(None) => tokio::time::sleep(RECEIPT_POLL_INTERVAL).await,
+								Err(e) => return Err(e),
+							}
+						}
+					}
+					TransactionStatus::Usurped(_)
+					| TransactionStatus::Dropped
+					| TransactionStatus::Invalid => {
+						return Err(internal_err("transaction was dropped from the pool"))
+					}
+					_ => (),
+				}
+			}
+			Err(internal_err("transaction status stream ended"))
+		};
+
+		match tokio::time::timeout(timeout, receipt).await {
+			Ok(receipt) => receipt,
+			Err(_) => Err(err(
+				TRANSACTION_RECEIPT_TIMEOUT_CODE,
+				format!(
+					"transaction {:?} was not included within {}ms",
+					transaction_hash,
+					timeout.as_millis()
+				),
+				Some(transaction_hash.as_bytes()),
+			)),
+		}
+	}
 }
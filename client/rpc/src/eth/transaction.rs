@@ -18,7 +18,7 @@
 
 use std::sync::Arc;
 
-use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
 use ethereum_types::{H256, U256, U64};
 use jsonrpsee::core::RpcResult as Result;
 // Substrate
@@ -35,7 +35,7 @@ use sp_runtime::{
 };
 // Frontier
 use fc_rpc_core::types::*;
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
 use crate::{
 	eth::{transaction_build, Eth},
@@ -313,140 +313,151 @@ where
 		let is_eip1559 = handler.is_eip1559(&id);
 
 		match (block, statuses, receipts) {
-			(Some(block), Some(statuses), Some(receipts)) => {
-				let block_hash = H256::from(keccak_256(&rlp::encode(&block.header)));
-				let receipt = receipts[index].clone();
+			(Some(block), Some(mut statuses), Some(mut receipts)) => {
+				if index >= receipts.len() || index >= statuses.len() {
+					return Err(internal_err(format!("{:?} is out of bounds", index)));
+				}
+				// The receipts before the transaction give its cumulative gas and log index.
+				statuses.truncate(index + 1);
+				receipts.truncate(index + 1);
+				let base_fee = client.runtime_api().gas_price(&id).unwrap_or_default();
 
-				let (logs, logs_bloom, status_code, cumulative_gas_used, gas_used) = if !is_eip1559
-				{
-					// Pre-london frontier update stored receipts require cumulative gas calculation.
-					match receipt {
-						ethereum::ReceiptV3::Legacy(ref d) => {
-							let index = core::cmp::min(receipts.len(), index + 1);
-							let cumulative_gas: u32 = receipts[..index]
-								.iter()
-								.map(|r| match r {
-									ethereum::ReceiptV3::Legacy(d) => Ok(d.used_gas.as_u32()),
-									_ => Err(internal_err(format!(
-										"Unknown receipt for request {}",
-										hash
-									))),
-								})
-								.sum::<Result<u32>>()?;
-							(
-								d.logs.clone(),
-								d.logs_bloom,
-								d.status_code,
-								U256::from(cumulative_gas),
-								d.used_gas,
-							)
-						}
-						_ => {
-							return Err(internal_err(format!(
-								"Unknown receipt for request {}",
-								hash
-							)))
-						}
-					}
-				} else {
-					match receipt {
-						ethereum::ReceiptV3::Legacy(ref d)
-						| ethereum::ReceiptV3::EIP2930(ref d)
-						| ethereum::ReceiptV3::EIP1559(ref d) => {
-							let cumulative_gas = d.used_gas;
-							let gas_used = if index > 0 {
-								let previous_receipt = receipts[index - 1].clone();
-								let previous_gas_used = match previous_receipt {
-									ethereum::ReceiptV3::Legacy(d)
-									| ethereum::ReceiptV3::EIP2930(d)
-									| ethereum::ReceiptV3::EIP1559(d) => d.used_gas,
-								};
-								cumulative_gas.saturating_sub(previous_gas_used)
-							} else {
-								cumulative_gas
-							};
-							(
-								d.logs.clone(),
-								d.logs_bloom,
-								d.status_code,
-								cumulative_gas,
-								gas_used,
-							)
-						}
-					}
-				};
+				Ok(receipts_build(&block, statuses, receipts, is_eip1559, base_fee)?.pop())
+			}
+			_ => Ok(None),
+		}
+	}
 
-				let status = statuses[index].clone();
-				let mut cumulative_receipts = receipts;
-				cumulative_receipts.truncate((status.transaction_index + 1) as usize);
-				let transaction = block.transactions[index].clone();
-				let effective_gas_price = match transaction {
-					EthereumTransaction::Legacy(t) => t.gas_price,
-					EthereumTransaction::EIP2930(t) => t.gas_price,
-					EthereumTransaction::EIP1559(t) => client
-						.runtime_api()
-						.gas_price(&id)
-						.unwrap_or_default()
-						.checked_add(t.max_priority_fee_per_gas)
-						.unwrap_or_else(U256::max_value)
-						.min(t.max_fee_per_gas),
-				};
+	pub async fn block_receipts(&self, number: BlockNumber) -> Result<Option<Vec<Receipt>>> {
+		let client = Arc::clone(&self.client);
+		let overrides = Arc::clone(&self.overrides);
+		let block_data_cache = Arc::clone(&self.block_data_cache);
+		let backend = Arc::clone(&self.backend);
 
-				return Ok(Some(Receipt {
-					transaction_hash: Some(status.transaction_hash),
-					transaction_index: Some(status.transaction_index.into()),
+		let id = match frontier_backend_client::native_block_id::<B, C>(
+			client.as_ref(),
+			backend.as_ref(),
+			Some(number),
+		)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let substrate_hash = client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+
+		let schema =
+			frontier_backend_client::onchain_storage_schema::<B, C, BE>(client.as_ref(), id);
+		let handler = overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&overrides.fallback);
+
+		let block = block_data_cache.current_block(schema, substrate_hash).await;
+		let statuses = block_data_cache
+			.current_transaction_statuses(schema, substrate_hash)
+			.await;
+		let receipts = handler.current_receipts(&id);
+		let is_eip1559 = handler.is_eip1559(&id);
+
+		let (block, statuses, receipts) = match (block, statuses, receipts) {
+			(Some(block), Some(statuses), Some(receipts)) => (block, statuses, receipts),
+			_ => return Ok(None),
+		};
+		let base_fee = client.runtime_api().gas_price(&id).unwrap_or_default();
+
+		receipts_build(&block, statuses, receipts, is_eip1559, base_fee).map(Some)
+	}
+}
+
+/// Receipts of all the transactions of `block`.
+pub(crate) fn receipts_build(
+	block: &EthereumBlock,
+	statuses: Vec<TransactionStatus>,
+	receipts: Vec<ethereum::ReceiptV3>,
+	is_eip1559: bool,
+	base_fee: U256,
+) -> Result<Vec<Receipt>> {
+	let block_hash = H256::from(keccak_256(&rlp::encode(&block.header)));
+
+	let mut block_receipts = Vec::with_capacity(receipts.len());
+	let mut cumulative_gas_used = U256::zero();
+	let mut log_index = 0u32;
+	for ((receipt, status), transaction) in receipts
+		.into_iter()
+		.zip(statuses.into_iter())
+		.zip(block.transactions.iter())
+	{
+		let (d, transaction_type) = match receipt {
+			ethereum::ReceiptV3::Legacy(d) => (d, U256::from(0)),
+			ethereum::ReceiptV3::EIP2930(d) => (d, U256::from(1)),
+			ethereum::ReceiptV3::EIP1559(d) => (d, U256::from(2)),
+		};
+		let gas_used = if !is_eip1559 {
+			// Pre-london frontier update stored receipts require cumulative gas calculation.
+			if transaction_type != U256::zero() {
+				return Err(internal_err(format!(
+					"Unknown receipt for block {}",
+					block_hash
+				)));
+			}
+			cumulative_gas_used = cumulative_gas_used.saturating_add(d.used_gas);
+			d.used_gas
+		} else {
+			let gas_used = d.used_gas.saturating_sub(cumulative_gas_used);
+			cumulative_gas_used = d.used_gas;
+			gas_used
+		};
+
+		let effective_gas_price = match transaction {
+			EthereumTransaction::Legacy(t) => t.gas_price,
+			EthereumTransaction::EIP2930(t) => t.gas_price,
+			EthereumTransaction::EIP1559(t) => base_fee
+				.checked_add(t.max_priority_fee_per_gas)
+				.unwrap_or_else(U256::max_value)
+				.min(t.max_fee_per_gas),
+		};
+
+		let logs = d
+			.logs
+			.into_iter()
+			.enumerate()
+			.map(|(i, log)| {
+				let log = Log {
+					address: log.address,
+					topics: log.topics,
+					data: Bytes(log.data),
 					block_hash: Some(block_hash),
-					from: Some(status.from),
-					to: status.to,
 					block_number: Some(block.header.number),
-					cumulative_gas_used,
-					gas_used: Some(gas_used),
-					contract_address: status.contract_address,
-					logs: {
-						let mut pre_receipts_log_index = None;
-						if cumulative_receipts.len() > 0 {
-							cumulative_receipts.truncate(cumulative_receipts.len() - 1);
-							pre_receipts_log_index = Some(
-								cumulative_receipts
-									.iter()
-									.map(|r| match r {
-										ethereum::ReceiptV3::Legacy(d)
-										| ethereum::ReceiptV3::EIP2930(d)
-										| ethereum::ReceiptV3::EIP1559(d) => d.logs.len() as u32,
-									})
-									.sum::<u32>(),
-							);
-						}
-						logs.iter()
-							.enumerate()
-							.map(|(i, log)| Log {
-								address: log.address,
-								topics: log.topics.clone(),
-								data: Bytes(log.data.clone()),
-								block_hash: Some(block_hash),
-								block_number: Some(block.header.number),
-								transaction_hash: Some(status.transaction_hash),
-								transaction_index: Some(status.transaction_index.into()),
-								log_index: Some(U256::from(
-									(pre_receipts_log_index.unwrap_or(0)) + i as u32,
-								)),
-								transaction_log_index: Some(U256::from(i)),
-								removed: false,
-							})
-							.collect()
-					},
-					status_code: Some(U64::from(status_code)),
-					logs_bloom,
-					state_root: None,
-					effective_gas_price,
-					transaction_type: match receipt {
-						ethereum::ReceiptV3::Legacy(_) => U256::from(0),
-						ethereum::ReceiptV3::EIP2930(_) => U256::from(1),
-						ethereum::ReceiptV3::EIP1559(_) => U256::from(2),
-					},
-				}));
-			}
-			_ => Ok(None),
-		}
+					transaction_hash: Some(status.transaction_hash),
+					transaction_index: Some(status.transaction_index.into()),
+					log_index: Some(U256::from(log_index)),
+					transaction_log_index: Some(U256::from(i)),
+					removed: false,
+				};
+				log_index += 1;
+				log
+			})
+			.collect();
+
+		block_receipts.push(Receipt {
+			transaction_hash: Some(status.transaction_hash),
+			transaction_index: Some(status.transaction_index.into()),
+			block_hash: Some(block_hash),
+			from: Some(status.from),
+			to: status.to,
+			block_number: Some(block.header.number),
+			cumulative_gas_used,
+			gas_used: Some(gas_used),
+			contract_address: status.contract_address,
+			logs,
+			status_code: Some(U64::from(d.status_code)),
+			logs_bloom: d.logs_bloom,
+			state_root: None,
+			effective_gas_price,
+			transaction_type,
+		});
 	}
+
+	Ok(block_receipts)
 }
@@ -30,7 +30,8 @@ use sc_client_api::{
 use sc_network::{NetworkService, NetworkStatusProvider};
 use sc_network_common::ExHashT;
 use sc_rpc::SubscriptionTaskExecutor;
-use sc_transaction_pool_api::TransactionPool;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TransactionStatus};
 use sp_api::{ApiExt, BlockId, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
@@ -39,14 +40,17 @@ use sp_runtime::traits::{BlakeTwo256, Block as BlockT, UniqueSaturatedInto};
 // Frontier
 use fc_rpc_core::{
 	types::{
-		pubsub::{Kind, Params, PubSubSyncStatus, Result as PubSubResult, SyncStatusMetadata},
+		pubsub::{
+			Kind, Params, PendingTransactionEvent, PendingTransactionStatus, PubSubSyncStatus,
+			Result as PubSubResult, SyncStatusMetadata,
+		},
 		Bytes, FilteredParams, Header, Log, Rich,
 	},
 	EthPubSubApiServer,
 };
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{frontier_backend_client, overrides::OverrideHandle};
+use crate::{eth::transaction_build, frontier_backend_client, overrides::OverrideHandle};
 
 #[derive(Debug)]
 pub struct EthereumSubIdProvider;
@@ -58,8 +62,9 @@ impl jsonrpsee::core::traits::IdProvider for EthereumSubIdProvider {
 }
 
 /// Eth pub-sub API implementation.
-pub struct EthPubSub<B: BlockT, P, C, BE, H: ExHashT> {
+pub struct EthPubSub<B: BlockT, P, C, BE, H: ExHashT, A: ChainApi> {
 	pool: Arc<P>,
+	graph: Arc<Pool<A>>,
 	client: Arc<C>,
 	network: Arc<NetworkService<B, H>>,
 	subscriptions: SubscriptionTaskExecutor,
@@ -68,12 +73,13 @@ pub struct EthPubSub<B: BlockT, P, C, BE, H: ExHashT> {
 	_marker: PhantomData<BE>,
 }
 
-impl<B: BlockT, P, C, BE, H: ExHashT> EthPubSub<B, P, C, BE, H>
+impl<B: BlockT, P, C, BE, H: ExHashT, A: ChainApi> EthPubSub<B, P, C, BE, H, A>
 where
 	C: HeaderBackend<B> + Send + Sync + 'static,
 {
 	pub fn new(
 		pool: Arc<P>,
+		graph: Arc<Pool<A>>,
 		client: Arc<C>,
 		network: Arc<NetworkService<B, H>>,
 		subscriptions: SubscriptionTaskExecutor,
@@ -84,6 +90,7 @@ where
 			UniqueSaturatedInto::<u64>::unique_saturated_into(client.info().best_number);
 		Self {
 			pool,
+			graph,
 			client,
 			network,
 			subscriptions,
@@ -194,7 +201,57 @@ impl EthSubscriptionResult {
 	}
 }
 
-impl<B: BlockT, P, C, BE, H: ExHashT> EthPubSubApiServer for EthPubSub<B, P, C, BE, H>
+/// Ethereum transaction of a pool extrinsic, `None` if it is not an Ethereum transaction.
+fn ethereum_transaction<B, C>(client: &C, xt: B::Extrinsic) -> Option<EthereumTransaction>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+{
+	let best_block: BlockId<B> = BlockId::Hash(client.info().best_hash);
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn EthereumRuntimeRPCApi<B>>(&best_block)
+		.ok()??;
+
+	let xts = vec![xt];
+	let mut txs: Vec<EthereumTransaction> = if api_version > 1 {
+		api.extrinsic_filter(&best_block, xts).ok()?
+	} else {
+		#[allow(deprecated)]
+		let legacy = api
+			.extrinsic_filter_before_version_2(&best_block, xts)
+			.ok()?;
+		legacy.into_iter().map(|tx| tx.into()).collect()
+	};
+
+	if txs.len() == 1 {
+		txs.pop()
+	} else {
+		None
+	}
+}
+
+/// Event of a pool status change of a transaction, `None` for the statuses not reported.
+fn pending_transaction_event<Hash, BlockHash>(
+	status: TransactionStatus<Hash, BlockHash>,
+	ethereum_block_hash: impl Fn(BlockHash) -> Option<H256>,
+	ethereum_transaction_hash: impl Fn(&Hash) -> Option<H256>,
+) -> Option<PendingTransactionEvent> {
+	match status {
+		TransactionStatus::InBlock(block_hash) => ethereum_block_hash(block_hash)
+			.map(|block_hash| PendingTransactionEvent::Included { block_hash }),
+		TransactionStatus::Usurped(by) => Some(PendingTransactionEvent::Replaced {
+			replaced_by: ethereum_transaction_hash(&by),
+		}),
+		TransactionStatus::Dropped | TransactionStatus::Invalid => {
+			Some(PendingTransactionEvent::Dropped)
+		}
+		_ => None,
+	}
+}
+
+impl<B: BlockT, P, C, BE, H: ExHashT, A> EthPubSubApiServer for EthPubSub<B, P, C, BE, H, A>
 where
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
 	P: TransactionPool<Block = B> + Send + Sync + 'static,
@@ -203,6 +260,7 @@ where
 	C::Api: EthereumRuntimeRPCApi<B>,
 	BE: Backend<B> + 'static,
 	BE::State: StateBackend<BlakeTwo256>,
+	A: ChainApi<Block = B> + 'static,
 {
 	fn subscribe(
 		&self,
@@ -212,6 +270,7 @@ where
 	) -> SubscriptionResult {
 		sink.accept()?;
 
+		let full_transactions = matches!(params, Some(Params::Bool(true)));
 		let filtered_params = match params {
 			Some(Params::Logs(filter)) => FilteredParams::new(Some(filter)),
 			_ => FilteredParams::default(),
@@ -219,6 +278,7 @@ where
 
 		let client = self.client.clone();
 		let pool = self.pool.clone();
+		let graph = self.graph.clone();
 		let network = self.network.clone();
 		let overrides = self.overrides.clone();
 		let starting_block = self.starting_block;
@@ -291,55 +351,88 @@ where
 					sink.pipe_from_stream(stream).await;
 				}
 				Kind::NewPendingTransactions => {
-					use sc_transaction_pool_api::InPoolTransaction;
-
 					let stream = pool
 						.import_notification_stream()
 						.filter_map(move |txhash| {
-							if let Some(xt) = pool.ready_transaction(&txhash) {
-								let best_block: BlockId<B> = BlockId::Hash(client.info().best_hash);
-
-								let api = client.runtime_api();
-
-								let api_version = if let Ok(Some(api_version)) =
-									api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&best_block)
-								{
-									api_version
-								} else {
-									return futures::future::ready(None);
-								};
-
-								let xts = vec![xt.data().clone()];
-
-								let txs: Option<Vec<EthereumTransaction>> = if api_version > 1 {
-									api.extrinsic_filter(&best_block, xts).ok()
-								} else {
-									#[allow(deprecated)]
-									if let Ok(legacy) =
-										api.extrinsic_filter_before_version_2(&best_block, xts)
-									{
-										Some(legacy.into_iter().map(|tx| tx.into()).collect())
-									} else {
-										None
-									}
-								};
-
-								let res = match txs {
-									Some(txs) => {
-										if txs.len() == 1 {
-											Some(txs[0].clone())
-										} else {
-											None
-										}
-									}
-									_ => None,
-								};
-								futures::future::ready(res)
+							let transaction = pool.ready_transaction(&txhash).and_then(|xt| {
+								ethereum_transaction::<B, C>(client.as_ref(), xt.data().clone())
+							});
+							futures::future::ready(transaction)
+						})
+						.map(move |transaction| {
+							if full_transactions {
+								PubSubResult::Transaction(Box::new(transaction_build(
+									transaction,
+									None,
+									None,
+									None,
+								)))
 							} else {
-								futures::future::ready(None)
+								PubSubResult::TransactionHash(transaction.hash())
 							}
+						});
+					sink.pipe_from_stream(stream).await;
+				}
+				Kind::PendingTransactionStatus => {
+					let ethereum_block_hash = {
+						let client = client.clone();
+						move |hash: B::Hash| {
+							let id = BlockId::Hash(hash);
+							let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(
+								client.as_ref(),
+								id,
+							);
+							let handler = overrides
+								.schemas
+								.get(&schema)
+								.unwrap_or(&overrides.fallback);
+							handler.current_block(&id).map(|block| block.header.hash())
+						}
+					};
+					let ethereum_transaction_hash = {
+						let client = client.clone();
+						let graph = graph.clone();
+						move |hash: &B::Hash| -> Option<H256> {
+							let xt = graph.validated_pool().ready_by_hash(hash)?;
+							ethereum_transaction::<B, C>(client.as_ref(), xt.data().clone())
+								.map(|transaction| transaction.hash())
+						}
+					};
+
+					// Watch each Ethereum transaction entering the pool until it leaves it.
+					let imports = graph.validated_pool().import_notification_stream();
+					let watch = {
+						let ethereum_transaction_hash = ethereum_transaction_hash.clone();
+						move |(xt_hash, hash): (B::Hash, H256)| {
+							let ethereum_block_hash = ethereum_block_hash.clone();
+							let ethereum_transaction_hash = ethereum_transaction_hash.clone();
+							let watcher = graph.validated_pool().create_watcher(xt_hash);
+							watcher
+								.into_stream()
+								.filter_map(move |status| {
+									let event = pending_transaction_event(
+										status,
+										&ethereum_block_hash,
+										&ethereum_transaction_hash,
+									)
+									.map(|event| {
+										PubSubResult::TransactionStatus(PendingTransactionStatus {
+											hash,
+											event,
+										})
+									});
+									futures::future::ready(event)
+								})
+								.boxed()
+						}
+					};
+
+					let stream = imports
+						.filter_map(move |xt_hash| {
+							let hash = ethereum_transaction_hash(&xt_hash);
+							futures::future::ready(hash.map(|hash| (xt_hash, hash)))
 						})
-						.map(|transaction| PubSubResult::TransactionHash(transaction.hash()));
+						.flat_map_unordered(None, watch);
 					sink.pipe_from_stream(stream).await;
 				}
 				Kind::Syncing => {
@@ -25,27 +25,36 @@
 	clippy::new_without_default
 )]
 
+mod debug;
 mod eth;
 mod eth_pubsub;
 mod net;
+mod ots;
 mod overrides;
 mod signer;
+mod trace;
+mod txpool;
 mod web3;
 
 pub use self::{
+	debug::Debug,
 	eth::{format, EstimateGasAdapter, Eth, EthBlockDataCacheTask, EthFilter, EthTask},
 	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
 	net::Net,
+	ots::Ots,
 	overrides::{
 		OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override, SchemaV2Override,
 		SchemaV3Override, StorageOverride,
 	},
-	signer::{EthDevSigner, EthSigner},
+	signer::{EthClefSigner, EthDevSigner, EthKeystoreSigner, EthSigner},
+	trace::Trace,
+	txpool::TxPool,
 	web3::Web3,
 };
 pub use ethereum::TransactionV2 as EthereumTransaction;
 pub use fc_rpc_core::{
-	EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer, Web3ApiServer,
+	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
+	OtsApiServer, TraceApiServer, TxPoolApiServer, Web3ApiServer,
 };
 
 pub mod frontier_backend_client {
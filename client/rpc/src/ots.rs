@@ -0,0 +1,699 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockBackend,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, UniqueSaturatedInto},
+};
+// Frontier
+use fc_db::{AddressTransaction, ADDRESS_INDEX_BUCKET_SIZE};
+use fc_rpc_core::{types::*, OtsApiServer};
+use fp_evm::{CallFrame, CallType, TracerKind, TransactionTrace};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
+
+use crate::{
+	debug::{call_type_name, replay_transaction, spawn_replay},
+	eth::{receipts_build, rich_block_build, transaction_build},
+	frontier_backend_client, internal_err, OverrideHandle, StorageOverride,
+};
+
+/// Version of the Otterscan API implemented by [`Ots`].
+const API_LEVEL: u64 = 8;
+
+/// Otterscan API implementation.
+pub struct Ots<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<fc_db::Backend<B>>,
+	overrides: Arc<OverrideHandle<B>>,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> Ots<B, C, BE> {
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<fc_db::Backend<B>>,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			overrides,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B: BlockT, C, BE> Clone for Ots<B, C, BE> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			overrides: self.overrides.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+/// Ethereum block with its formatted transactions and receipts.
+struct BlockData {
+	block: EthereumBlock,
+	transactions: Vec<Transaction>,
+	receipts: Vec<Receipt>,
+}
+
+impl<B, C, BE> Ots<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B> + DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	fn handler(&self, id: BlockId<B>) -> &(dyn StorageOverride<B> + Send + Sync) {
+		let schema =
+			frontier_backend_client::onchain_storage_schema::<B, C, BE>(self.client.as_ref(), id);
+		self.overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&self.overrides.fallback)
+			.as_ref()
+	}
+
+	fn block_data(&self, id: BlockId<B>) -> Result<Option<BlockData>> {
+		let handler = self.handler(id);
+		let (block, statuses, receipts) = match (
+			handler.current_block(&id),
+			handler.current_transaction_statuses(&id),
+			handler.current_receipts(&id),
+		) {
+			(Some(block), Some(statuses), Some(receipts)) => (block, statuses, receipts),
+			_ => return Ok(None),
+		};
+		let base_fee = self.client.runtime_api().gas_price(&id).unwrap_or_default();
+
+		let receipts = receipts_build(
+			&block,
+			statuses.clone(),
+			receipts,
+			handler.is_eip1559(&id),
+			base_fee,
+		)?;
+		let transactions = block
+			.transactions
+			.iter()
+			.zip(statuses)
+			.map(|(transaction, status)| {
+				transaction_build(
+					transaction.clone(),
+					Some(block.clone()),
+					Some(status),
+					Some(base_fee),
+				)
+			})
+			.collect();
+		Ok(Some(BlockData {
+			block,
+			transactions,
+			receipts,
+		}))
+	}
+
+	/// Whether `hash` is the hash of the canonical block at height `number`.
+	fn is_canonical(&self, number: u64, hash: H256) -> Result<bool> {
+		Ok(self
+			.client
+			.hash(number.unique_saturated_into())
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			== Some(hash))
+	}
+
+	/// Canonical transactions of `address` in the blocks of `bucket`, ascending.
+	fn address_transactions(
+		&self,
+		address: &H160,
+		bucket: u64,
+	) -> Result<Vec<AddressTransaction<H256>>> {
+		let mut transactions = Vec::new();
+		for transaction in self
+			.backend
+			.address_index()
+			.transactions(address, bucket)
+			.map_err(|err| internal_err(format!("fetch address index failed: {:?}", err)))?
+		{
+			if self.is_canonical(transaction.block_number, transaction.block_hash)? {
+				transactions.push(transaction);
+			}
+		}
+		transactions
+			.sort_by_key(|transaction| (transaction.block_number, transaction.ethereum_index));
+		Ok(transactions)
+	}
+
+	fn address_buckets(&self, address: &H160) -> Result<Vec<u64>> {
+		self.backend
+			.address_index()
+			.buckets(address)
+			.map_err(|err| internal_err(format!("fetch address index failed: {:?}", err)))
+	}
+
+	/// Transactions of `address` in the blocks before `block_number`, or after it, as a page of
+	/// whole blocks holding at least `page_size` transactions.
+	fn search_transactions(
+		&self,
+		address: H160,
+		block_number: u64,
+		page_size: usize,
+		before: bool,
+	) -> Result<TransactionsPage> {
+		// 0 stands for the latest block when searching backward.
+		let end = if before && block_number == 0 {
+			u64::MAX
+		} else {
+			block_number
+		};
+		let in_range = |number: u64| {
+			if before {
+				number < end
+			} else {
+				number > end
+			}
+		};
+
+		let mut buckets: Vec<u64> = self
+			.address_buckets(&address)?
+			.into_iter()
+			.filter(|bucket| {
+				let first = bucket * ADDRESS_INDEX_BUCKET_SIZE;
+				in_range(first) || in_range(first.saturating_add(ADDRESS_INDEX_BUCKET_SIZE - 1))
+			})
+			.collect();
+		if before {
+			buckets.reverse();
+		}
+
+		let mut found = Vec::new();
+		let mut has_more = false;
+		for bucket in buckets {
+			let mut transactions: Vec<_> = self
+				.address_transactions(&address, bucket)?
+				.into_iter()
+				.filter(|transaction| in_range(transaction.block_number))
+				.collect();
+			if before {
+				transactions.reverse();
+			}
+			found.extend(transactions);
+
+			// Cut the page at the first block boundary past the page size.
+			if let Some(page_end) = (page_size.max(1)..found.len())
+				.find(|&i| found[i].block_number != found[i - 1].block_number)
+			{
+				found.truncate(page_end);
+				has_more = true;
+				break;
+			}
+		}
+		// Pages are sorted newest first.
+		if !before {
+			found.reverse();
+		}
+
+		let mut txs = Vec::with_capacity(found.len());
+		let mut receipts = Vec::with_capacity(found.len());
+		let mut current: Option<(H256, BlockData)> = None;
+		for transaction in found {
+			if current.as_ref().map(|(hash, _)| *hash) != Some(transaction.block_hash) {
+				let data = self
+					.block_data(BlockId::Hash(transaction.block_hash))?
+					.ok_or_else(|| {
+						internal_err(format!("block {:?} not found", transaction.block_hash))
+					})?;
+				current = Some((transaction.block_hash, data));
+			}
+			let (_, data) = current
+				.as_ref()
+				.expect("the block of the transaction is loaded above; qed");
+			let index = transaction.ethereum_index as usize;
+			match (data.transactions.get(index), data.receipts.get(index)) {
+				(Some(tx), Some(receipt)) => {
+					txs.push(tx.clone());
+					receipts.push(OtsReceipt {
+						receipt: receipt.clone(),
+						timestamp: U256::from(data.block.header.timestamp / 1000),
+					});
+				}
+				_ => {
+					return Err(internal_err(format!(
+						"transaction {:?} not found in block",
+						transaction.ethereum_transaction_hash
+					)))
+				}
+			}
+		}
+
+		let (first_page, last_page) = if before {
+			(block_number == 0, !has_more)
+		} else {
+			(!has_more, block_number == 0)
+		};
+		Ok(TransactionsPage {
+			txs,
+			receipts,
+			first_page,
+			last_page,
+		})
+	}
+
+	fn nonce_after(&self, address: H160, block_hash: H256) -> Result<U256> {
+		Ok(self
+			.client
+			.runtime_api()
+			.account_basic(&BlockId::Hash(block_hash), address)
+			.map_err(|err| internal_err(format!("fetch runtime account basic failed: {:?}", err)))?
+			.nonce)
+	}
+
+	fn transaction_by_sender_and_nonce(&self, address: H160, nonce: U256) -> Result<Option<H256>> {
+		// The transaction is in the first bucket after whose last canonical block the nonce of the
+		// sender is greater, so that a single bucket is read.
+		let buckets = self.address_buckets(&address)?;
+		let best: u64 = self.client.info().best_number.unique_saturated_into();
+		let (mut low, mut high) = (0, buckets.len());
+		while low < high {
+			let middle = (low + high) / 2;
+			let last = best.min(
+				buckets[middle]
+					.saturating_mul(ADDRESS_INDEX_BUCKET_SIZE)
+					.saturating_add(ADDRESS_INDEX_BUCKET_SIZE - 1),
+			);
+			let last_hash = self
+				.client
+				.hash(last.unique_saturated_into())
+				.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("header not found for block {}", last)))?;
+			if self.nonce_after(address, last_hash)? > nonce {
+				high = middle;
+			} else {
+				low = middle + 1;
+			}
+		}
+		let bucket = match buckets.get(low) {
+			Some(bucket) => *bucket,
+			None => return Ok(None),
+		};
+
+		let mut blocks: Vec<(u64, H256)> = Vec::new();
+		for transaction in self.address_transactions(&address, bucket)? {
+			let block = (transaction.block_number, transaction.block_hash);
+			if blocks.last() != Some(&block) {
+				blocks.push(block);
+			}
+		}
+
+		// The transaction is in the first block after which the nonce of the sender is greater.
+		let (mut low, mut high) = (0, blocks.len());
+		while low < high {
+			let middle = (low + high) / 2;
+			if self.nonce_after(address, blocks[middle].1)? > nonce {
+				high = middle;
+			} else {
+				low = middle + 1;
+			}
+		}
+		let (_, block_hash) = match blocks.get(low) {
+			Some(block) => *block,
+			None => return Ok(None),
+		};
+
+		let id = BlockId::Hash(block_hash);
+		let handler = self.handler(id);
+		let (block, statuses) = match (
+			handler.current_block(&id),
+			handler.current_transaction_statuses(&id),
+		) {
+			(Some(block), Some(statuses)) => (block, statuses),
+			_ => return Ok(None),
+		};
+		Ok(block
+			.transactions
+			.iter()
+			.zip(statuses)
+			.find(|(transaction, status)| {
+				let transaction_nonce = match transaction {
+					EthereumTransaction::Legacy(t) => t.nonce,
+					EthereumTransaction::EIP2930(t) => t.nonce,
+					EthereumTransaction::EIP1559(t) => t.nonce,
+				};
+				status.from == address && transaction_nonce == nonce
+			})
+			.map(|(_, status)| status.transaction_hash))
+	}
+
+	fn contract_creator(&self, address: H160) -> Result<Option<ContractCreator>> {
+		for creation in self
+			.backend
+			.address_index()
+			.contract_creations(&address)
+			.map_err(|err| internal_err(format!("fetch address index failed: {:?}", err)))?
+		{
+			let transaction = creation.transaction;
+			if self.is_canonical(transaction.block_number, transaction.block_hash)? {
+				return Ok(Some(ContractCreator {
+					hash: transaction.ethereum_transaction_hash,
+					creator: creation.creator,
+				}));
+			}
+		}
+		Ok(None)
+	}
+
+	fn block_id(&self, number: BlockNumber) -> Result<Option<BlockId<B>>> {
+		frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)
+	}
+
+	fn block_details(&self, id: Option<BlockId<B>>) -> Result<Option<BlockDetails>> {
+		let id = match id {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let data = match self.block_data(id)? {
+			Some(data) => data,
+			None => return Ok(None),
+		};
+		let base_fee = self.client.runtime_api().gas_price(&id).unwrap_or_default();
+
+		let total_fees = data
+			.receipts
+			.iter()
+			.map(|receipt| {
+				receipt
+					.gas_used
+					.unwrap_or_default()
+					.saturating_mul(receipt.effective_gas_price)
+			})
+			.fold(U256::zero(), |total, fee| total.saturating_add(fee));
+		let transaction_count = data.transactions.len();
+		// Without full transactions, the statuses are not read.
+		let mut block = rich_block_build(data.block, Vec::new(), None, false, Some(base_fee));
+		block.inner.transactions = BlockTransactions::Hashes(Vec::new());
+
+		Ok(Some(BlockDetails {
+			block: OtsBlock {
+				block,
+				transaction_count,
+			},
+			// Blocks do not mint the native currency.
+			issuance: Issuance::default(),
+			total_fees,
+		}))
+	}
+
+	fn block_transactions(
+		&self,
+		number: BlockNumber,
+		page_number: usize,
+		page_size: usize,
+	) -> Result<Option<BlockTransactionsPage>> {
+		let id = match self.block_id(number)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let data = match self.block_data(id)? {
+			Some(data) => data,
+			None => return Ok(None),
+		};
+		let base_fee = self.client.runtime_api().gas_price(&id).unwrap_or_default();
+
+		// Pages are counted from the end of the block.
+		let transaction_count = data.transactions.len();
+		let end = transaction_count.saturating_sub(page_number.saturating_mul(page_size));
+		let start = end.saturating_sub(page_size);
+		let transactions = data.transactions[start..end]
+			.iter()
+			.cloned()
+			.map(|mut transaction| {
+				transaction.input.0.truncate(4);
+				transaction
+			})
+			.collect();
+		let receipts = data.receipts[start..end]
+			.iter()
+			.cloned()
+			.map(|mut receipt| {
+				receipt.logs = Vec::new();
+				receipt
+			})
+			.collect();
+
+		let mut block = rich_block_build(data.block, Vec::new(), None, false, Some(base_fee));
+		block.inner.transactions = BlockTransactions::Full(transactions);
+		Ok(Some(BlockTransactionsPage {
+			fullblock: OtsBlock {
+				block,
+				transaction_count,
+			},
+			receipts,
+		}))
+	}
+
+	/// Call frame of the transaction with given hash, replayed with a call tracer.
+	fn call_frame(&self, transaction_hash: H256) -> Result<Option<CallFrame>> {
+		let (ethereum_block_hash, _) = match frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			transaction_hash,
+			true,
+		)? {
+			Some(transaction) => transaction,
+			None => return Ok(None),
+		};
+		let id = match frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			ethereum_block_hash,
+		)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("header not found for block {:?}", id)))?;
+		let extrinsics = self
+			.client
+			.block_body(header.hash())
+			.map_err(|err| internal_err(format!("fetch block body failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("body not found for block {:?}", id)))?;
+
+		match replay_transaction::<B, C>(
+			self.client.as_ref(),
+			&header,
+			extrinsics,
+			transaction_hash,
+			TracerKind::CallTracer,
+		)? {
+			Some(TransactionTrace::CallTracer(frame)) => Ok(Some(frame)),
+			_ => Ok(None),
+		}
+	}
+
+	fn trace_transaction(&self, transaction_hash: H256) -> Result<Option<Vec<OtsTrace>>> {
+		Ok(self.call_frame(transaction_hash)?.map(|frame| {
+			let mut traces = Vec::new();
+			flatten_call_frame(frame, 0, &mut traces);
+			traces
+		}))
+	}
+
+	fn internal_operations(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<InternalOperation>>> {
+		Ok(self.call_frame(transaction_hash)?.map(|frame| {
+			let mut operations = Vec::new();
+			for call in &frame.calls {
+				collect_internal_operations(call, &mut operations);
+			}
+			operations
+		}))
+	}
+
+	fn transaction_error(&self, transaction_hash: H256) -> Result<Option<Bytes>> {
+		Ok(self.call_frame(transaction_hash)?.map(|frame| {
+			if frame.reverted || frame.error.is_some() {
+				Bytes(frame.output)
+			} else {
+				Bytes(Vec::new())
+			}
+		}))
+	}
+}
+
+#[async_trait]
+impl<B, C, BE> OtsApiServer for Ots<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B> + DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	fn get_api_level(&self) -> Result<u64> {
+		Ok(API_LEVEL)
+	}
+
+	async fn has_code(&self, address: H160, number: BlockNumber) -> Result<bool> {
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)?
+		.ok_or_else(|| internal_err("header not found"))?;
+		Ok(self
+			.handler(id)
+			.account_code_at(&id, address)
+			.map_or(false, |code| !code.is_empty()))
+	}
+
+	async fn get_block_details(&self, number: BlockNumber) -> Result<Option<BlockDetails>> {
+		self.block_details(self.block_id(number)?)
+	}
+
+	async fn get_block_details_by_hash(&self, hash: H256) -> Result<Option<BlockDetails>> {
+		self.block_details(frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+		)?)
+	}
+
+	async fn get_block_transactions(
+		&self,
+		number: BlockNumber,
+		page_number: usize,
+		page_size: usize,
+	) -> Result<Option<BlockTransactionsPage>> {
+		self.block_transactions(number, page_number, page_size)
+	}
+
+	async fn get_internal_operations(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<InternalOperation>>> {
+		let ots = self.clone();
+		spawn_replay(move || ots.internal_operations(transaction_hash)).await
+	}
+
+	async fn get_transaction_error(&self, transaction_hash: H256) -> Result<Option<Bytes>> {
+		let ots = self.clone();
+		spawn_replay(move || ots.transaction_error(transaction_hash)).await
+	}
+
+	async fn trace_transaction(&self, transaction_hash: H256) -> Result<Option<Vec<OtsTrace>>> {
+		let ots = self.clone();
+		spawn_replay(move || ots.trace_transaction(transaction_hash)).await
+	}
+
+	async fn search_transactions_before(
+		&self,
+		address: H160,
+		block_number: u64,
+		page_size: usize,
+	) -> Result<TransactionsPage> {
+		self.search_transactions(address, block_number, page_size, true)
+	}
+
+	async fn search_transactions_after(
+		&self,
+		address: H160,
+		block_number: u64,
+		page_size: usize,
+	) -> Result<TransactionsPage> {
+		self.search_transactions(address, block_number, page_size, false)
+	}
+
+	async fn get_transaction_by_sender_and_nonce(
+		&self,
+		address: H160,
+		nonce: U256,
+	) -> Result<Option<H256>> {
+		self.transaction_by_sender_and_nonce(address, nonce)
+	}
+
+	async fn get_contract_creator(&self, address: H160) -> Result<Option<ContractCreator>> {
+		self.contract_creator(address)
+	}
+}
+
+/// Append the traces of `frame` and of its subcalls, depth first.
+fn flatten_call_frame(frame: CallFrame, depth: u32, traces: &mut Vec<OtsTrace>) {
+	traces.push(OtsTrace {
+		call_type: call_type_name(frame.call_type).to_string(),
+		depth,
+		from: frame.from,
+		to: frame.to.unwrap_or_default(),
+		value: frame.value,
+		input: Bytes(frame.input),
+		output: Bytes(frame.output),
+	});
+	for call in frame.calls {
+		flatten_call_frame(call, depth + 1, traces);
+	}
+}
+
+/// Append the value transfers, creates and self-destructs of `frame` and of its subcalls.
+fn collect_internal_operations(frame: &CallFrame, operations: &mut Vec<InternalOperation>) {
+	let value = frame.value.unwrap_or_default();
+	let kind = match frame.call_type {
+		CallType::Call if !value.is_zero() => Some(InternalOperationKind::Transfer),
+		CallType::Create => Some(InternalOperationKind::Create),
+		CallType::Create2 => Some(InternalOperationKind::Create2),
+		CallType::SelfDestruct => Some(InternalOperationKind::SelfDestruct),
+		_ => None,
+	};
+	if let Some(kind) = kind {
+		operations.push(InternalOperation {
+			kind,
+			from: frame.from,
+			to: frame.to.unwrap_or_default(),
+			value,
+		});
+	}
+	for call in &frame.calls {
+		collect_internal_operations(call, operations);
+	}
+}
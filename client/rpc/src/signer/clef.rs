@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::{
+	io::{Read, Write},
+	net::TcpStream,
+	path::PathBuf,
+	str::FromStr,
+	time::Duration,
+};
+
+use ethereum::{AccessListItem, TransactionAction, TransactionV2 as EthereumTransaction};
+use ethereum_types::{H160, U256};
+use jsonrpsee::core::Error;
+use serde_json::{json, Value};
+// Frontier
+use fc_rpc_core::types::{TransactionMessage, TypedData};
+
+use super::EthSigner;
+use crate::internal_err;
+
+/// Clef asks the user to approve each request, give them time to answer. Requests are made from
+/// blocking threads, see `Eth::with_signer_of`.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Endpoint of an external signer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum ClefEndpoint {
+	/// Path of a unix socket.
+	Ipc(PathBuf),
+	/// `host:port` and path of an `http://` URL.
+	Http { host: String, path: String },
+}
+
+impl FromStr for ClefEndpoint {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(url) = s.strip_prefix("http://") {
+			let (host, path) = match url.find('/') {
+				Some(index) => (&url[..index], &url[index..]),
+				None => (url, "/"),
+			};
+			if host.is_empty() {
+				return Err(format!("invalid external signer url {}", s));
+			}
+			Ok(ClefEndpoint::Http {
+				host: host.to_string(),
+				path: path.to_string(),
+			})
+		} else if s.contains("://") {
+			Err(format!("unsupported external signer url {}", s))
+		} else {
+			Ok(ClefEndpoint::Ipc(PathBuf::from(s)))
+		}
+	}
+}
+
+impl ClefEndpoint {
+	fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+		let body = json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		})
+		.to_string();
+
+		let response: Value = match self {
+			#[cfg(unix)]
+			ClefEndpoint::Ipc(path) => {
+				let mut stream = UnixStream::connect(path).map_err(|e| format!("{}", e))?;
+				stream
+					.set_read_timeout(Some(REQUEST_TIMEOUT))
+					.map_err(|e| format!("{}", e))?;
+				stream
+					.write_all(body.as_bytes())
+					.map_err(|e| format!("{}", e))?;
+				serde_json::Deserializer::from_reader(stream)
+					.into_iter()
+					.next()
+					.ok_or_else(|| "connection closed".to_string())?
+					.map_err(|e| format!("invalid response: {}", e))?
+			}
+			#[cfg(not(unix))]
+			ClefEndpoint::Ipc(_) => {
+				return Err("ipc external signers are only supported on unix".to_string())
+			}
+			ClefEndpoint::Http { host, path } => {
+				let mut stream = TcpStream::connect(host).map_err(|e| format!("{}", e))?;
+				stream
+					.set_read_timeout(Some(REQUEST_TIMEOUT))
+					.map_err(|e| format!("{}", e))?;
+				let request = format!(
+					"POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+					path,
+					host,
+					body.len(),
+					body
+				);
+				stream
+					.write_all(request.as_bytes())
+					.map_err(|e| format!("{}", e))?;
+				let mut response = Vec::new();
+				stream
+					.read_to_end(&mut response)
+					.map_err(|e| format!("{}", e))?;
+				let response = String::from_utf8_lossy(&response);
+				let (head, body) = response
+					.split_once("\r\n\r\n")
+					.ok_or_else(|| "invalid http response".to_string())?;
+				let status = head.lines().next().unwrap_or_default();
+				if status.split_whitespace().nth(1) != Some("200") {
+					return Err(format!("http error: {}", status));
+				}
+				serde_json::from_str(body).map_err(|e| format!("invalid response: {}", e))?
+			}
+		};
+
+		if let Some(error) = response.get("error") {
+			let message = error
+				.get("message")
+				.and_then(Value::as_str)
+				.unwrap_or("unknown error");
+			return Err(message.to_string());
+		}
+		response
+			.get("result")
+			.cloned()
+			.ok_or_else(|| "missing result".to_string())
+	}
+}
+
+/// Signer forwarding transactions to a Clef compatible external signer.
+///
+/// The accounts are listed once, when connecting, as Clef asks the user to approve the listing.
+#[derive(Clone)]
+pub struct EthClefSigner {
+	endpoint: ClefEndpoint,
+	accounts: Vec<H160>,
+}
+
+impl EthClefSigner {
+	/// Connect to the external signer at `endpoint`, an ipc path or an `http://` url.
+	pub fn connect(endpoint: &str) -> Result<Self, String> {
+		let endpoint = endpoint.parse::<ClefEndpoint>()?;
+		let accounts = endpoint
+			.request("account_list", json!([]))
+			.and_then(|result| {
+				serde_json::from_value(result).map_err(|e| format!("invalid accounts: {}", e))
+			})
+			.map_err(|e| format!("cannot list the external signer accounts: {}", e))?;
+
+		Ok(Self { endpoint, accounts })
+	}
+
+	/// Sign data with `method`, which returns the signature as `r || s || v`.
+	fn sign_data(&self, method: &str, params: Value, address: &H160) -> Result<[u8; 65], Error> {
+		if !self.accounts.contains(address) {
+			return Err(internal_err("signer not available"));
+		}
+
+		let result = self
+			.endpoint
+			.request(method, params)
+			.map_err(|e| internal_err(format!("external signer error: {}", e)))?;
+		let signature = result
+			.as_str()
+			.and_then(|signature| hex::decode(signature.trim_start_matches("0x")).ok())
+			.filter(|signature| signature.len() == 65)
+			.ok_or_else(|| internal_err("external signer returned an invalid signature"))?;
+
+		let mut rsv = [0u8; 65];
+		rsv.copy_from_slice(&signature);
+		Ok(rsv)
+	}
+}
+
+fn quantity(value: U256) -> String {
+	format!("0x{:x}", value)
+}
+
+fn access_list(items: &[AccessListItem]) -> Value {
+	items
+		.iter()
+		.map(|item| {
+			json!({
+				"address": format!("{:?}", item.address),
+				"storageKeys": item
+					.storage_keys
+					.iter()
+					.map(|key| format!("{:?}", key))
+					.collect::<Vec<_>>(),
+			})
+		})
+		.collect()
+}
+
+/// Arguments of `account_signTransaction` for `message`.
+fn transaction_args(message: &TransactionMessage, from: &H160) -> Value {
+	let mut args = json!({ "from": format!("{:?}", from) });
+	let fields = args.as_object_mut().expect("args is an object; qed");
+	let (nonce, gas_limit, action, value, input) = match message {
+		TransactionMessage::Legacy(m) => {
+			fields.insert("gasPrice".into(), quantity(m.gas_price).into());
+			if let Some(chain_id) = m.chain_id {
+				fields.insert("chainId".into(), quantity(chain_id.into()).into());
+			}
+			(m.nonce, m.gas_limit, &m.action, m.value, &m.input)
+		}
+		TransactionMessage::EIP2930(m) => {
+			fields.insert("gasPrice".into(), quantity(m.gas_price).into());
+			fields.insert("chainId".into(), quantity(m.chain_id.into()).into());
+			fields.insert("accessList".into(), access_list(&m.access_list));
+			(m.nonce, m.gas_limit, &m.action, m.value, &m.input)
+		}
+		TransactionMessage::EIP1559(m) => {
+			fields.insert("maxFeePerGas".into(), quantity(m.max_fee_per_gas).into());
+			fields.insert(
+				"maxPriorityFeePerGas".into(),
+				quantity(m.max_priority_fee_per_gas).into(),
+			);
+			fields.insert("chainId".into(), quantity(m.chain_id.into()).into());
+			fields.insert("accessList".into(), access_list(&m.access_list));
+			(m.nonce, m.gas_limit, &m.action, m.value, &m.input)
+		}
+	};
+	if let TransactionAction::Call(to) = action {
+		fields.insert("to".into(), format!("{:?}", to).into());
+	}
+	fields.insert("nonce".into(), quantity(nonce).into());
+	fields.insert("gas".into(), quantity(gas_limit).into());
+	fields.insert("value".into(), quantity(value).into());
+	fields.insert("data".into(), format!("0x{}", hex::encode(input)).into());
+	args
+}
+
+impl EthSigner for EthClefSigner {
+	fn accounts(&self) -> Vec<H160> {
+		self.accounts.clone()
+	}
+
+	fn sign(
+		&self,
+		message: TransactionMessage,
+		address: &H160,
+	) -> Result<EthereumTransaction, Error> {
+		if !self.accounts.contains(address) {
+			return Err(internal_err("signer not available"));
+		}
+
+		let result = self
+			.endpoint
+			.request(
+				"account_signTransaction",
+				json!([transaction_args(&message, address)]),
+			)
+			.map_err(|e| internal_err(format!("external signer error: {}", e)))?;
+		let raw = result
+			.get("raw")
+			.and_then(Value::as_str)
+			.and_then(|raw| hex::decode(raw.trim_start_matches("0x")).ok())
+			.ok_or_else(|| internal_err("external signer returned no transaction"))?;
+
+		ethereum::EnvelopedDecodable::decode(&raw)
+			.map_err(|_| internal_err("external signer returned an invalid transaction"))
+	}
+
+	fn sign_message(&self, message: &[u8], address: &H160) -> Result<[u8; 65], Error> {
+		self.sign_data(
+			"account_signData",
+			json!([
+				"text/plain",
+				format!("{:?}", address),
+				format!("0x{}", hex::encode(message)),
+			]),
+			address,
+		)
+	}
+
+	fn sign_typed_data(&self, typed_data: &TypedData, address: &H160) -> Result<[u8; 65], Error> {
+		self.sign_data(
+			"account_signTypedData",
+			json!([format!("{:?}", address), typed_data]),
+			address,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_endpoints() {
+		assert_eq!(
+			"/home/user/.clef/clef.ipc".parse(),
+			Ok(ClefEndpoint::Ipc(PathBuf::from(
+				"/home/user/.clef/clef.ipc"
+			)))
+		);
+		assert_eq!(
+			"http://localhost:8550".parse(),
+			Ok(ClefEndpoint::Http {
+				host: "localhost:8550".into(),
+				path: "/".into(),
+			})
+		);
+		assert_eq!(
+			"http://127.0.0.1:8550/rpc".parse(),
+			Ok(ClefEndpoint::Http {
+				host: "127.0.0.1:8550".into(),
+				path: "/rpc".into(),
+			})
+		);
+		assert!("https://localhost:8550".parse::<ClefEndpoint>().is_err());
+	}
+}
@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::path::Path;
+
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::H160;
+use hmac::Hmac;
+use jsonrpsee::core::Error;
+use serde::Deserialize;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+// Substrate
+use sp_core::hashing::keccak_256;
+// Frontier
+use fc_rpc_core::types::{TransactionMessage, TypedData};
+
+use super::{
+	find_key, personal_message_hash, secret_key_address, sign_hash, sign_transaction,
+	typed_data::typed_data_hash, EthSigner,
+};
+use crate::internal_err;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Signer backed by Ethereum JSON v3 keystore files, decrypted once at startup.
+#[derive(Clone)]
+pub struct EthKeystoreSigner {
+	keys: Vec<libsecp256k1::SecretKey>,
+}
+
+impl EthKeystoreSigner {
+	/// Decrypt every keystore file of `dir` with `password`.
+	pub fn from_dir(dir: &Path, password: &str) -> Result<Self, String> {
+		let entries = std::fs::read_dir(dir)
+			.map_err(|e| format!("cannot read keystore directory {}: {}", dir.display(), e))?;
+
+		let mut keys = Vec::new();
+		for entry in entries {
+			let path = entry.map_err(|e| format!("{}", e))?.path();
+			if !path.is_file() {
+				continue;
+			}
+			let json = std::fs::read(&path)
+				.map_err(|e| format!("cannot read keystore {}: {}", path.display(), e))?;
+			let key = decrypt_keystore(&json, password.as_bytes())
+				.map_err(|e| format!("invalid keystore {}: {}", path.display(), e))?;
+			keys.push(key);
+		}
+
+		Ok(Self { keys })
+	}
+}
+
+impl EthSigner for EthKeystoreSigner {
+	fn accounts(&self) -> Vec<H160> {
+		self.keys.iter().map(secret_key_address).collect()
+	}
+
+	fn sign(
+		&self,
+		message: TransactionMessage,
+		address: &H160,
+	) -> Result<EthereumTransaction, Error> {
+		sign_transaction(message, find_key(&self.keys, address)?)
+	}
+
+	fn sign_message(&self, message: &[u8], address: &H160) -> Result<[u8; 65], Error> {
+		sign_hash(
+			personal_message_hash(message),
+			find_key(&self.keys, address)?,
+		)
+	}
+
+	fn sign_typed_data(&self, typed_data: &TypedData, address: &H160) -> Result<[u8; 65], Error> {
+		let hash = typed_data_hash(typed_data).map_err(internal_err)?;
+		sign_hash(hash, find_key(&self.keys, address)?)
+	}
+}
+
+#[derive(Deserialize)]
+struct Keystore {
+	address: Option<String>,
+	#[serde(alias = "Crypto")]
+	crypto: KeystoreCrypto,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCrypto {
+	cipher: String,
+	cipherparams: CipherParams,
+	ciphertext: String,
+	kdf: String,
+	kdfparams: serde_json::Value,
+	mac: String,
+}
+
+#[derive(Deserialize)]
+struct CipherParams {
+	iv: String,
+}
+
+#[derive(Deserialize)]
+struct ScryptParams {
+	dklen: usize,
+	n: u64,
+	r: u32,
+	p: u32,
+	salt: String,
+}
+
+#[derive(Deserialize)]
+struct Pbkdf2Params {
+	dklen: usize,
+	c: u32,
+	prf: String,
+	salt: String,
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, String> {
+	hex::decode(value.trim_start_matches("0x")).map_err(|_| format!("invalid {}", field))
+}
+
+/// Derive the key encryption key of a keystore from its password.
+fn derive_key(kdf: &str, params: serde_json::Value, password: &[u8]) -> Result<Vec<u8>, String> {
+	match kdf {
+		"scrypt" => {
+			let params: ScryptParams = serde_json::from_value(params)
+				.map_err(|e| format!("invalid scrypt parameters: {}", e))?;
+			if !params.n.is_power_of_two() {
+				return Err("invalid scrypt parameters: n is not a power of two".into());
+			}
+			let salt = decode_hex("salt", &params.salt)?;
+			let scrypt_params =
+				scrypt::Params::new(params.n.trailing_zeros() as u8, params.r, params.p)
+					.map_err(|e| format!("invalid scrypt parameters: {}", e))?;
+			let mut key = vec![0u8; params.dklen];
+			scrypt::scrypt(password, &salt, &scrypt_params, &mut key)
+				.map_err(|e| format!("invalid scrypt parameters: {}", e))?;
+			Ok(key)
+		}
+		"pbkdf2" => {
+			let params: Pbkdf2Params = serde_json::from_value(params)
+				.map_err(|e| format!("invalid pbkdf2 parameters: {}", e))?;
+			if params.prf != "hmac-sha256" {
+				return Err(format!("unsupported pbkdf2 prf {}", params.prf));
+			}
+			let salt = decode_hex("salt", &params.salt)?;
+			let mut key = vec![0u8; params.dklen];
+			pbkdf2::pbkdf2::<Hmac<Sha256>>(password, &salt, params.c, &mut key);
+			Ok(key)
+		}
+		kdf => Err(format!("unsupported kdf {}", kdf)),
+	}
+}
+
+/// Decrypt the secret key of an Ethereum JSON v3 keystore.
+fn decrypt_keystore(json: &[u8], password: &[u8]) -> Result<libsecp256k1::SecretKey, String> {
+	let keystore: Keystore =
+		serde_json::from_slice(json).map_err(|e| format!("invalid json: {}", e))?;
+	let crypto = keystore.crypto;
+	if crypto.cipher != "aes-128-ctr" {
+		return Err(format!("unsupported cipher {}", crypto.cipher));
+	}
+
+	let derived_key = derive_key(&crypto.kdf, crypto.kdfparams, password)?;
+	if derived_key.len() < 32 {
+		return Err("derived key is too short".into());
+	}
+
+	let mut ciphertext = decode_hex("ciphertext", &crypto.ciphertext)?;
+	let mac = decode_hex("mac", &crypto.mac)?;
+	let expected_mac = keccak_256(&[&derived_key[16..32], &ciphertext[..]].concat());
+	// Compared in constant time, not to leak how much of the mac a password matches.
+	if !bool::from(mac.ct_eq(&expected_mac[..])) {
+		return Err("wrong password".into());
+	}
+
+	let iv = decode_hex("iv", &crypto.cipherparams.iv)?;
+	let mut cipher = Aes128Ctr::new_from_slices(&derived_key[..16], &iv)
+		.map_err(|_| "invalid iv".to_string())?;
+	cipher.apply_keystream(&mut ciphertext);
+
+	let secret = libsecp256k1::SecretKey::parse_slice(&ciphertext)
+		.map_err(|_| "invalid secret key".to_string())?;
+	if let Some(address) = keystore.address {
+		let address = decode_hex("address", &address)?;
+		if address != secret_key_address(&secret).as_bytes() {
+			return Err("address does not match the secret key".into());
+		}
+	}
+
+	Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Test vector of the Web3 Secret Storage Definition.
+	const PBKDF2_KEYSTORE: &str = r#"{
+		"crypto": {
+			"cipher": "aes-128-ctr",
+			"cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+			"ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+			"kdf": "pbkdf2",
+			"kdfparams": {
+				"c": 262144,
+				"dklen": 32,
+				"prf": "hmac-sha256",
+				"salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+			},
+			"mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+		},
+		"id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+		"version": 3
+	}"#;
+
+	#[test]
+	fn decrypts_pbkdf2_keystore() {
+		let secret = decrypt_keystore(PBKDF2_KEYSTORE.as_bytes(), b"testpassword").unwrap();
+		assert_eq!(
+			hex::encode(secret.serialize()),
+			"7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+		);
+	}
+
+	#[test]
+	fn rejects_wrong_password() {
+		assert_eq!(
+			decrypt_keystore(PBKDF2_KEYSTORE.as_bytes(), b"wrongpassword").err(),
+			Some("wrong password".to_string())
+		);
+	}
+}
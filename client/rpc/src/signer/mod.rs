@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::{H160, H256};
+use jsonrpsee::core::Error;
+// Substrate
+use sp_core::hashing::keccak_256;
+// Frontier
+use fc_rpc_core::types::{TransactionMessage, TypedData};
+
+use crate::internal_err;
+
+mod clef;
+mod keystore;
+mod typed_data;
+
+pub use self::{clef::EthClefSigner, keystore::EthKeystoreSigner};
+
+/// A generic Ethereum signer.
+pub trait EthSigner: Send + Sync {
+	/// Available accounts from this signer.
+	fn accounts(&self) -> Vec<H160>;
+	/// Sign a transaction message using the given account in message.
+	fn sign(
+		&self,
+		message: TransactionMessage,
+		address: &H160,
+	) -> Result<EthereumTransaction, Error>;
+	/// Sign a message prefixed as an EIP-191 personal message using the given account.
+	fn sign_message(&self, message: &[u8], address: &H160) -> Result<[u8; 65], Error>;
+	/// Sign EIP-712 typed data using the given account.
+	fn sign_typed_data(&self, typed_data: &TypedData, address: &H160) -> Result<[u8; 65], Error>;
+}
+
+pub struct EthDevSigner {
+	keys: Vec<libsecp256k1::SecretKey>,
+}
+
+impl EthDevSigner {
+	pub fn new() -> Self {
+		Self {
+			keys: vec![libsecp256k1::SecretKey::parse(&[
+				0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+				0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+				0x11, 0x11, 0x11, 0x11,
+			])
+			.expect("Test key is valid; qed")],
+		}
+	}
+}
+
+fn secret_key_address(secret: &libsecp256k1::SecretKey) -> H160 {
+	let public = libsecp256k1::PublicKey::from_secret_key(secret);
+	public_key_address(&public)
+}
+
+fn public_key_address(public: &libsecp256k1::PublicKey) -> H160 {
+	let mut res = [0u8; 64];
+	res.copy_from_slice(&public.serialize()[1..65]);
+	H160::from(H256::from(keccak_256(&res)))
+}
+
+impl EthSigner for EthDevSigner {
+	fn accounts(&self) -> Vec<H160> {
+		self.keys.iter().map(secret_key_address).collect()
+	}
+
+	fn sign(
+		&self,
+		message: TransactionMessage,
+		address: &H160,
+	) -> Result<EthereumTransaction, Error> {
+		sign_transaction(message, find_key(&self.keys, address)?)
+	}
+
+	fn sign_message(&self, message: &[u8], address: &H160) -> Result<[u8; 65], Error> {
+		sign_hash(
+			personal_message_hash(message),
+			find_key(&self.keys, address)?,
+		)
+	}
+
+	fn sign_typed_data(&self, typed_data: &TypedData, address: &H160) -> Result<[u8; 65], Error> {
+		let hash = typed_data::typed_data_hash(typed_data).map_err(internal_err)?;
+		sign_hash(hash, find_key(&self.keys, address)?)
+	}
+}
+
+fn find_key<'a>(
+	keys: &'a [libsecp256k1::SecretKey],
+	address: &H160,
+) -> Result<&'a libsecp256k1::SecretKey, Error> {
+	keys.iter()
+		.find(|secret| &secret_key_address(secret) == address)
+		.ok_or_else(|| internal_err("signer not available"))
+}
+
+/// Hash of `message` prefixed as an EIP-191 personal message.
+fn personal_message_hash(message: &[u8]) -> H256 {
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(message);
+	H256::from(keccak_256(&prefixed))
+}
+
+/// Sign `hash` with a secp256k1 secret key, returning the signature as `r || s || v`.
+fn sign_hash(hash: H256, secret: &libsecp256k1::SecretKey) -> Result<[u8; 65], Error> {
+	let signing_message = libsecp256k1::Message::parse_slice(&hash[..])
+		.map_err(|_| internal_err("invalid signing message"))?;
+	let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+	let mut rsv = [0u8; 65];
+	rsv[..64].copy_from_slice(&signature.serialize());
+	rsv[64] = 27 + recid.serialize();
+	Ok(rsv)
+}
+
+/// Sign a transaction message with a secp256k1 secret key.
+fn sign_transaction(
+	message: TransactionMessage,
+	secret: &libsecp256k1::SecretKey,
+) -> Result<EthereumTransaction, Error> {
+	let transaction = match message {
+		TransactionMessage::Legacy(m) => {
+			let signing_message = libsecp256k1::Message::parse_slice(&m.hash()[..])
+				.map_err(|_| internal_err("invalid signing message"))?;
+			let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+			let v = match m.chain_id {
+				None => 27 + recid.serialize() as u64,
+				Some(chain_id) => 2 * chain_id + 35 + recid.serialize() as u64,
+			};
+			let rs = signature.serialize();
+			let r = H256::from_slice(&rs[0..32]);
+			let s = H256::from_slice(&rs[32..64]);
+			EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+				nonce: m.nonce,
+				gas_price: m.gas_price,
+				gas_limit: m.gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input,
+				signature: ethereum::TransactionSignature::new(v, r, s)
+					.ok_or_else(|| internal_err("signer generated invalid signature"))?,
+			})
+		}
+		TransactionMessage::EIP2930(m) => {
+			let signing_message = libsecp256k1::Message::parse_slice(&m.hash()[..])
+				.map_err(|_| internal_err("invalid signing message"))?;
+			let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+			let rs = signature.serialize();
+			let r = H256::from_slice(&rs[0..32]);
+			let s = H256::from_slice(&rs[32..64]);
+			EthereumTransaction::EIP2930(ethereum::EIP2930Transaction {
+				chain_id: m.chain_id,
+				nonce: m.nonce,
+				gas_price: m.gas_price,
+				gas_limit: m.gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input.clone(),
+				access_list: m.access_list,
+				odd_y_parity: recid.serialize() != 0,
+				r,
+				s,
+			})
+		}
+		TransactionMessage::EIP1559(m) => {
+			let signing_message = libsecp256k1::Message::parse_slice(&m.hash()[..])
+				.map_err(|_| internal_err("invalid signing message"))?;
+			let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+			let rs = signature.serialize();
+			let r = H256::from_slice(&rs[0..32]);
+			let s = H256::from_slice(&rs[32..64]);
+			EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+				chain_id: m.chain_id,
+				nonce: m.nonce,
+				max_priority_fee_per_gas: m.max_priority_fee_per_gas,
+				max_fee_per_gas: m.max_fee_per_gas,
+				gas_limit: m.gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input.clone(),
+				access_list: m.access_list,
+				odd_y_parity: recid.serialize() != 0,
+				r,
+				s,
+			})
+		}
+	};
+
+	Ok(transaction)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signs_personal_message() {
+		let signer = EthDevSigner::new();
+		let address = signer.accounts()[0];
+		let signature = signer.sign_message(b"hello", &address).unwrap();
+
+		let message = libsecp256k1::Message::parse(&personal_message_hash(b"hello").0);
+		let recovered = libsecp256k1::recover(
+			&message,
+			&libsecp256k1::Signature::parse_standard_slice(&signature[..64]).unwrap(),
+			&libsecp256k1::RecoveryId::parse_rpc(signature[64]).unwrap(),
+		)
+		.unwrap();
+		assert_eq!(public_key_address(&recovered), address);
+	}
+}
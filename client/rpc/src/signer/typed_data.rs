@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EIP-712 hashing of typed data, following the `eth_signTypedData_v4` rules.
+
+use std::collections::BTreeSet;
+
+use ethereum_types::{H256, U256};
+use serde_json::{Map, Value};
+// Substrate
+use sp_core::hashing::keccak_256;
+// Frontier
+use fc_rpc_core::types::{TypedData, TypedDataField};
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Hash of `typed_data` to sign.
+pub fn typed_data_hash(typed_data: &TypedData) -> Result<H256, String> {
+	let mut encoded = vec![0x19, 0x01];
+	encoded.extend_from_slice(&hash_struct(typed_data, DOMAIN_TYPE, &typed_data.domain)?);
+	if typed_data.primary_type != DOMAIN_TYPE {
+		encoded.extend_from_slice(&hash_struct(
+			typed_data,
+			&typed_data.primary_type,
+			&typed_data.message,
+		)?);
+	}
+	Ok(H256::from(keccak_256(&encoded)))
+}
+
+fn struct_fields<'a>(
+	typed_data: &'a TypedData,
+	name: &str,
+) -> Result<&'a [TypedDataField], String> {
+	typed_data
+		.types
+		.get(name)
+		.map(Vec::as_slice)
+		.ok_or_else(|| format!("unknown type {}", name))
+}
+
+/// Type of the elements of an array type, `None` if `type_` is not an array.
+fn array_item_type(type_: &str) -> Option<&str> {
+	type_
+		.strip_suffix(']')
+		.and_then(|type_| type_.rfind('[').map(|index| &type_[..index]))
+}
+
+/// Type without its array dimensions.
+fn base_type(type_: &str) -> &str {
+	type_.find('[').map_or(type_, |index| &type_[..index])
+}
+
+fn collect_dependencies(
+	typed_data: &TypedData,
+	name: &str,
+	dependencies: &mut BTreeSet<String>,
+) -> Result<(), String> {
+	if !dependencies.insert(name.to_string()) {
+		return Ok(());
+	}
+	for field in struct_fields(typed_data, name)? {
+		let base = base_type(&field.type_);
+		if typed_data.types.contains_key(base) {
+			collect_dependencies(typed_data, base, dependencies)?;
+		}
+	}
+	Ok(())
+}
+
+/// `encodeType` of a struct: the struct followed by its dependencies in alphabetical order.
+fn encode_type(typed_data: &TypedData, name: &str) -> Result<String, String> {
+	let mut dependencies = BTreeSet::new();
+	collect_dependencies(typed_data, name, &mut dependencies)?;
+	dependencies.remove(name);
+
+	let mut encoded = String::new();
+	for name in std::iter::once(name).chain(dependencies.iter().map(String::as_str)) {
+		let fields = struct_fields(typed_data, name)?
+			.iter()
+			.map(|field| format!("{} {}", field.type_, field.name))
+			.collect::<Vec<_>>();
+		encoded.push_str(&format!("{}({})", name, fields.join(",")));
+	}
+	Ok(encoded)
+}
+
+fn hash_struct(
+	typed_data: &TypedData,
+	name: &str,
+	values: &Map<String, Value>,
+) -> Result<[u8; 32], String> {
+	let mut encoded = keccak_256(encode_type(typed_data, name)?.as_bytes()).to_vec();
+	for field in struct_fields(typed_data, name)? {
+		let value = match values.get(&field.name) {
+			Some(Value::Null) | None if typed_data.types.contains_key(&field.type_) => {
+				encoded.extend_from_slice(&[0u8; 32]);
+				continue;
+			}
+			Some(Value::Null) | None => {
+				return Err(format!("missing value for field {}", field.name));
+			}
+			Some(value) => value,
+		};
+		encoded.extend_from_slice(&encode_value(typed_data, &field.type_, value)?);
+	}
+	Ok(keccak_256(&encoded))
+}
+
+fn parse_bytes(value: &Value) -> Result<Vec<u8>, String> {
+	value
+		.as_str()
+		.and_then(|value| value.strip_prefix("0x"))
+		.and_then(|value| hex::decode(value).ok())
+		.ok_or_else(|| format!("invalid bytes {}", value))
+}
+
+/// Absolute value and sign of an integer given as a JSON number, or a decimal or hex string.
+fn parse_integer(value: &Value) -> Result<(U256, bool), String> {
+	let invalid = || format!("invalid integer {}", value);
+	match value {
+		Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+			(Some(number), _) => Ok((number.into(), false)),
+			(None, Some(number)) => Ok((number.unsigned_abs().into(), true)),
+			_ => Err(invalid()),
+		},
+		Value::String(number) => {
+			let (digits, negative) = match number.strip_prefix('-') {
+				Some(digits) => (digits, true),
+				None => (number.as_str(), false),
+			};
+			let absolute = match digits.strip_prefix("0x") {
+				Some(hex) => U256::from_str_radix(hex, 16).map_err(|_| invalid())?,
+				None => U256::from_dec_str(digits).map_err(|_| invalid())?,
+			};
+			Ok((absolute, negative && !absolute.is_zero()))
+		}
+		_ => Err(invalid()),
+	}
+}
+
+fn encode_value(typed_data: &TypedData, type_: &str, value: &Value) -> Result<[u8; 32], String> {
+	if let Some(item_type) = array_item_type(type_) {
+		let items = value
+			.as_array()
+			.ok_or_else(|| format!("expected an array of {}", item_type))?;
+		let mut encoded = Vec::new();
+		for item in items {
+			encoded.extend_from_slice(&encode_value(typed_data, item_type, item)?);
+		}
+		return Ok(keccak_256(&encoded));
+	}
+
+	if typed_data.types.contains_key(type_) {
+		let values = value
+			.as_object()
+			.ok_or_else(|| format!("expected a {} struct", type_))?;
+		return hash_struct(typed_data, type_, values);
+	}
+
+	let mut encoded = [0u8; 32];
+	match type_ {
+		"string" => {
+			let value = value
+				.as_str()
+				.ok_or_else(|| format!("invalid string {}", value))?;
+			encoded = keccak_256(value.as_bytes());
+		}
+		"bytes" => encoded = keccak_256(&parse_bytes(value)?),
+		"bool" => {
+			let value = value
+				.as_bool()
+				.ok_or_else(|| format!("invalid bool {}", value))?;
+			encoded[31] = value as u8;
+		}
+		"address" => {
+			let address = parse_bytes(value)?;
+			if address.len() != 20 {
+				return Err(format!("invalid address {}", value));
+			}
+			encoded[12..].copy_from_slice(&address);
+		}
+		_ if type_.starts_with("bytes") => {
+			let size = type_["bytes".len()..]
+				.parse::<usize>()
+				.ok()
+				.filter(|size| (1..=32).contains(size))
+				.ok_or_else(|| format!("unsupported type {}", type_))?;
+			let bytes = parse_bytes(value)?;
+			if bytes.len() > size {
+				return Err(format!("invalid {} {}", type_, value));
+			}
+			encoded[..bytes.len()].copy_from_slice(&bytes);
+		}
+		_ if type_.starts_with("uint") => {
+			let (absolute, negative) = parse_integer(value)?;
+			if negative {
+				return Err(format!("invalid {} {}", type_, value));
+			}
+			absolute.to_big_endian(&mut encoded);
+		}
+		_ if type_.starts_with("int") => {
+			let (absolute, negative) = parse_integer(value)?;
+			let value = if negative {
+				(!absolute).overflowing_add(U256::one()).0
+			} else {
+				absolute
+			};
+			value.to_big_endian(&mut encoded);
+		}
+		_ => return Err(format!("unsupported type {}", type_)),
+	}
+	Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Example of EIP-712.
+	const MAIL: &str = r#"{
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "version", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" }
+			],
+			"Person": [
+				{ "name": "name", "type": "string" },
+				{ "name": "wallet", "type": "address" }
+			],
+			"Mail": [
+				{ "name": "from", "type": "Person" },
+				{ "name": "to", "type": "Person" },
+				{ "name": "contents", "type": "string" }
+			]
+		},
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+			"to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+			"contents": "Hello, Bob!"
+		}
+	}"#;
+
+	#[test]
+	fn encodes_type_with_dependencies() {
+		let typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+		assert_eq!(
+			encode_type(&typed_data, "Mail").unwrap(),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+		);
+	}
+
+	#[test]
+	fn hashes_typed_data() {
+		let typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+		assert_eq!(
+			hex::encode(hash_struct(&typed_data, DOMAIN_TYPE, &typed_data.domain).unwrap()),
+			"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+		);
+		assert_eq!(
+			hex::encode(hash_struct(&typed_data, "Mail", &typed_data.message).unwrap()),
+			"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+		);
+		assert_eq!(
+			hex::encode(typed_data_hash(&typed_data).unwrap()),
+			"be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+		);
+	}
+
+	#[test]
+	fn rejects_missing_values() {
+		let mut typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+		typed_data.message.remove("contents");
+		assert_eq!(
+			typed_data_hash(&typed_data),
+			Err("missing value for field contents".to_string())
+		);
+	}
+}
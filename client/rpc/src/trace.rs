@@ -0,0 +1,493 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+use ethereum_types::{H160, H256};
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockBackend,
+};
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+};
+// Frontier
+use fc_rpc_core::{types::*, TraceApiServer};
+use fp_evm::{CallFrame, TracerKind, TransactionTrace};
+use fp_rpc::DebugRuntimeApi;
+
+use crate::{
+	debug::{ensure_debug_api, replay_transaction, spawn_replay, tracing_not_supported},
+	frontier_backend_client, internal_err, OverrideHandle,
+};
+
+/// Trace API implementation.
+pub struct Trace<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<fc_db::Backend<B>>,
+	overrides: Arc<OverrideHandle<B>>,
+	/// Maximum number of blocks replayed by a single `trace_filter` request.
+	max_block_range: u64,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> Trace<B, C, BE> {
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<fc_db::Backend<B>>,
+		overrides: Arc<OverrideHandle<B>>,
+		max_block_range: u64,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			overrides,
+			max_block_range,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B: BlockT, C, BE> Clone for Trace<B, C, BE> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			overrides: self.overrides.clone(),
+			max_block_range: self.max_block_range,
+			_marker: PhantomData,
+		}
+	}
+}
+
+/// Position of the traces of a transaction.
+struct TraceLocation {
+	transaction_hash: H256,
+	transaction_position: usize,
+	block_hash: H256,
+	block_number: u64,
+}
+
+impl<B, C, BE> Trace<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	/// Header, extrinsics and Ethereum block hash of the block to replay.
+	fn block(&self, id: BlockId<B>) -> Result<Option<(B::Header, Vec<B::Extrinsic>, H256)>> {
+		let header = match self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+		{
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		let extrinsics = self
+			.client
+			.block_body(header.hash())
+			.map_err(|err| internal_err(format!("fetch block body failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("body not found for block {:?}", id)))?;
+		let schema =
+			frontier_backend_client::onchain_storage_schema::<B, C, BE>(self.client.as_ref(), id);
+		let block = self
+			.overrides
+			.schemas
+			.get(&schema)
+			.unwrap_or(&self.overrides.fallback)
+			.current_block(&id)
+			.ok_or_else(|| internal_err(format!("ethereum block not found for block {:?}", id)))?;
+		Ok(Some((header, extrinsics, block.header.hash())))
+	}
+
+	fn trace_block(&self, id: BlockId<B>) -> Result<Option<Vec<LocalizedTrace>>> {
+		let (header, extrinsics, block_hash) = match self.block(id)? {
+			Some(block) => block,
+			None => return Ok(None),
+		};
+		let block_number = UniqueSaturatedInto::<u64>::unique_saturated_into(*header.number());
+		// The genesis block has no transaction to replay.
+		if block_number == 0 {
+			return Ok(Some(Vec::new()));
+		}
+
+		let parent = BlockId::Hash(*header.parent_hash());
+		let api = self.client.runtime_api();
+		ensure_debug_api::<B, C::Api>(&*api, &parent)?;
+		api.initialize_block(&parent, &header)
+			.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?;
+		let traces = api
+			.trace_block(&parent, extrinsics, TracerKind::CallTracer)
+			.map_err(|err| internal_err(format!("runtime api access error: {:?}", err)))?
+			.map_err(|_| tracing_not_supported())?;
+
+		let mut localized = Vec::new();
+		for (transaction_position, (transaction_hash, trace)) in traces.into_iter().enumerate() {
+			if let TransactionTrace::CallTracer(frame) = trace {
+				let location = TraceLocation {
+					transaction_hash,
+					transaction_position,
+					block_hash,
+					block_number,
+				};
+				flatten_call_frame(frame, Vec::new(), &location, &mut localized);
+			}
+		}
+		Ok(Some(localized))
+	}
+
+	fn trace_transaction(&self, transaction_hash: H256) -> Result<Option<Vec<LocalizedTrace>>> {
+		let (ethereum_block_hash, transaction_position) =
+			match frontier_backend_client::load_transactions::<B, C>(
+				self.client.as_ref(),
+				self.backend.as_ref(),
+				transaction_hash,
+				true,
+			)? {
+				Some(transaction) => transaction,
+				None => return Ok(None),
+			};
+		let id = match frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			ethereum_block_hash,
+		)? {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let (header, extrinsics, block_hash) = match self.block(id)? {
+			Some(block) => block,
+			None => return Ok(None),
+		};
+
+		let frame = match replay_transaction::<B, C>(
+			self.client.as_ref(),
+			&header,
+			extrinsics,
+			transaction_hash,
+			TracerKind::CallTracer,
+		)? {
+			Some(TransactionTrace::CallTracer(frame)) => frame,
+			_ => return Ok(None),
+		};
+
+		let location = TraceLocation {
+			transaction_hash,
+			transaction_position: transaction_position as usize,
+			block_hash,
+			block_number: UniqueSaturatedInto::<u64>::unique_saturated_into(*header.number()),
+		};
+		let mut localized = Vec::new();
+		flatten_call_frame(frame, Vec::new(), &location, &mut localized);
+		Ok(Some(localized))
+	}
+
+	fn block_number(&self, number: BlockNumber) -> Result<u64> {
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)?
+		.ok_or_else(|| internal_err(format!("block {:?} cannot be traced", number)))?;
+		self.client
+			.block_number_from_id(&id)
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			.map(UniqueSaturatedInto::<u64>::unique_saturated_into)
+			.ok_or_else(|| internal_err(format!("header not found for block {:?}", id)))
+	}
+
+	/// Whether `hash` is the hash of the canonical block at height `number`.
+	fn is_canonical(&self, number: u64, hash: H256) -> Result<bool> {
+		Ok(self
+			.client
+			.hash(number.unique_saturated_into())
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			== Some(hash))
+	}
+
+	/// Numbers of the canonical blocks among the given trace index entries.
+	fn canonical_indexed_blocks(&self, blocks: Vec<(u64, H256)>) -> Result<BTreeSet<u64>> {
+		let mut canonical = BTreeSet::new();
+		for (number, hash) in blocks {
+			if self.is_canonical(number, hash)? {
+				canonical.insert(number);
+			}
+		}
+		Ok(canonical)
+	}
+
+	/// Blocks of heights `from..=to` that may contain traces matching the addresses, narrowed
+	/// down with the trace index when it covers the whole range.
+	fn candidate_blocks(
+		&self,
+		from: u64,
+		to: u64,
+		from_addresses: &BTreeSet<H160>,
+		to_addresses: &BTreeSet<H160>,
+	) -> Result<Vec<u64>> {
+		let all_blocks = || (from..=to).collect();
+		if from_addresses.is_empty() && to_addresses.is_empty() {
+			return Ok(all_blocks());
+		}
+
+		let map_err = |err| internal_err(format!("fetch trace index failed: {:?}", err));
+		let trace_index = self.backend.trace_index();
+		let indexed =
+			self.canonical_indexed_blocks(trace_index.indexed_blocks(from, to).map_err(map_err)?)?;
+		if (indexed.len() as u64) < to - from + 1 {
+			return Ok(all_blocks());
+		}
+
+		let mut from_blocks = Vec::new();
+		for address in from_addresses {
+			from_blocks.extend(
+				trace_index
+					.from_address_blocks(address, from, to)
+					.map_err(map_err)?,
+			);
+		}
+		let mut to_blocks = Vec::new();
+		for address in to_addresses {
+			to_blocks.extend(
+				trace_index
+					.to_address_blocks(address, from, to)
+					.map_err(map_err)?,
+			);
+		}
+		let from_blocks = self.canonical_indexed_blocks(from_blocks)?;
+		let to_blocks = self.canonical_indexed_blocks(to_blocks)?;
+
+		Ok(match (from_addresses.is_empty(), to_addresses.is_empty()) {
+			(false, true) => from_blocks.into_iter().collect(),
+			(true, false) => to_blocks.into_iter().collect(),
+			_ => from_blocks.intersection(&to_blocks).copied().collect(),
+		})
+	}
+
+	fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTrace>> {
+		let from = self.block_number(filter.from_block.unwrap_or(BlockNumber::Earliest))?;
+		let to = self.block_number(filter.to_block.unwrap_or(BlockNumber::Latest))?;
+		if from > to {
+			return Err(internal_err(format!(
+				"fromBlock {} is greater than toBlock {}",
+				from, to
+			)));
+		}
+
+		let from_addresses: BTreeSet<H160> = filter
+			.from_address
+			.unwrap_or_default()
+			.into_iter()
+			.collect();
+		let to_addresses: BTreeSet<H160> =
+			filter.to_address.unwrap_or_default().into_iter().collect();
+		let blocks = self.candidate_blocks(from, to, &from_addresses, &to_addresses)?;
+		if blocks.len() as u64 > self.max_block_range {
+			return Err(internal_err(format!(
+				"query exceeds max block range {}",
+				self.max_block_range
+			)));
+		}
+
+		let mut skip = filter.after.unwrap_or(0);
+		let count = filter.count.unwrap_or(usize::MAX);
+		let mut traces = Vec::new();
+		for number in blocks {
+			if traces.len() >= count {
+				break;
+			}
+			let block_traces = self
+				.trace_block(BlockId::Number(number.unique_saturated_into()))?
+				.unwrap_or_default();
+			for trace in block_traces {
+				if !trace_matches(&trace, &from_addresses, &to_addresses) {
+					continue;
+				}
+				if skip > 0 {
+					skip -= 1;
+				} else if traces.len() < count {
+					traces.push(trace);
+				}
+			}
+		}
+		Ok(traces)
+	}
+}
+
+#[async_trait]
+impl<B, C, BE> TraceApiServer for Trace<B, C, BE>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + BlockBackend<B> + HeaderBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: DebugRuntimeApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	async fn trace_block(&self, number: BlockNumber) -> Result<Option<Vec<LocalizedTrace>>> {
+		match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)? {
+			Some(id) => {
+				let trace = self.clone();
+				spawn_replay(move || trace.trace_block(id)).await
+			}
+			None => Ok(None),
+		}
+	}
+
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<LocalizedTrace>>> {
+		let trace = self.clone();
+		spawn_replay(move || trace.trace_transaction(transaction_hash)).await
+	}
+
+	async fn trace_get(
+		&self,
+		transaction_hash: H256,
+		indices: Vec<Index>,
+	) -> Result<Option<LocalizedTrace>> {
+		let trace_address: Vec<usize> = indices.iter().map(Index::value).collect();
+		let trace = self.clone();
+		Ok(
+			spawn_replay(move || trace.trace_transaction(transaction_hash))
+				.await?
+				.and_then(|traces| {
+					traces
+						.into_iter()
+						.find(|trace| trace.trace_address == trace_address)
+				}),
+		)
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTrace>> {
+		let trace = self.clone();
+		spawn_replay(move || trace.trace_filter(filter)).await
+	}
+}
+
+/// Sender and receiver of a trace, as matched by `trace_filter`.
+fn trace_addresses(trace: &LocalizedTrace) -> (H160, Option<H160>) {
+	match (&trace.action, &trace.outcome) {
+		(TraceAction::Call(action), _) => (action.from, Some(action.to)),
+		(TraceAction::Create(action), TraceOutcome::Result(Some(ActionResult::Create(result)))) => {
+			(action.from, Some(result.address))
+		}
+		(TraceAction::Create(action), _) => (action.from, None),
+		(TraceAction::Suicide(action), _) => (action.address, Some(action.refund_address)),
+	}
+}
+
+fn trace_matches(
+	trace: &LocalizedTrace,
+	from_addresses: &BTreeSet<H160>,
+	to_addresses: &BTreeSet<H160>,
+) -> bool {
+	let (from, to) = trace_addresses(trace);
+	(from_addresses.is_empty() || from_addresses.contains(&from))
+		&& (to_addresses.is_empty() || to.map_or(false, |to| to_addresses.contains(&to)))
+}
+
+/// Append the traces of `frame` and of its subcalls, depth first.
+fn flatten_call_frame(
+	frame: CallFrame,
+	trace_address: Vec<usize>,
+	location: &TraceLocation,
+	traces: &mut Vec<LocalizedTrace>,
+) {
+	let to = frame.to.unwrap_or_default();
+	let value = frame.value.unwrap_or_default();
+	let (action, result) = match frame.call_type {
+		fp_evm::CallType::Create | fp_evm::CallType::Create2 => (
+			TraceAction::Create(CreateAction {
+				from: frame.from,
+				gas: frame.gas,
+				init: Bytes(frame.input),
+				value,
+			}),
+			Some(ActionResult::Create(CreateResult {
+				address: to,
+				code: Bytes(frame.output),
+				gas_used: frame.gas_used,
+			})),
+		),
+		fp_evm::CallType::SelfDestruct => (
+			TraceAction::Suicide(SuicideAction {
+				address: frame.from,
+				refund_address: to,
+				balance: value,
+			}),
+			None,
+		),
+		call_type => (
+			TraceAction::Call(CallAction {
+				call_type: match call_type {
+					fp_evm::CallType::CallCode => CallType::CallCode,
+					fp_evm::CallType::DelegateCall => CallType::DelegateCall,
+					fp_evm::CallType::StaticCall => CallType::StaticCall,
+					_ => CallType::Call,
+				},
+				from: frame.from,
+				to,
+				gas: frame.gas,
+				input: Bytes(frame.input),
+				value,
+			}),
+			Some(ActionResult::Call(CallResult {
+				gas_used: frame.gas_used,
+				output: Bytes(frame.output),
+			})),
+		),
+	};
+	let outcome = if frame.reverted {
+		TraceOutcome::Error("Reverted".to_string())
+	} else if let Some(error) = frame.error {
+		TraceOutcome::Error(String::from_utf8_lossy(&error).into_owned())
+	} else {
+		TraceOutcome::Result(result)
+	};
+
+	traces.push(LocalizedTrace {
+		action,
+		outcome,
+		subtraces: frame.calls.len(),
+		trace_address: trace_address.clone(),
+		transaction_hash: location.transaction_hash,
+		transaction_position: location.transaction_position,
+		block_hash: location.block_hash,
+		block_number: location.block_number,
+	});
+	for (index, call) in frame.calls.into_iter().enumerate() {
+		let mut call_address = trace_address.clone();
+		call_address.push(index);
+		flatten_call_frame(call, call_address, location, traces);
+	}
+}
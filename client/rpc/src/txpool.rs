@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::{H256, U64};
+use jsonrpsee::core::RpcResult as Result;
+// Substrate
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::InPoolTransaction;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+// Frontier
+use fc_rpc_core::{types::*, TxPoolApiServer};
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use crate::{eth::transaction_build, internal_err};
+
+/// TxPool API implementation.
+pub struct TxPool<B: BlockT, C, A: ChainApi> {
+	client: Arc<C>,
+	graph: Arc<Pool<A>>,
+	_marker: PhantomData<B>,
+}
+
+impl<B: BlockT, C, A: ChainApi> TxPool<B, C, A> {
+	pub fn new(client: Arc<C>, graph: Arc<Pool<A>>) -> Self {
+		Self {
+			client,
+			graph,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B, C, A> TxPool<B, C, A>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	A: ChainApi<Block = B> + 'static,
+{
+	/// Decode the Ethereum transactions of the ready (pending) and future (queued)
+	/// parts of the pool.
+	fn ethereum_transactions(&self) -> Result<TxPoolResult<Vec<EthereumTransaction>>> {
+		let ready = self
+			.graph
+			.validated_pool()
+			.ready()
+			.map(|in_pool_tx| in_pool_tx.data().clone())
+			.collect::<Vec<<B as BlockT>::Extrinsic>>();
+		let future = self
+			.graph
+			.validated_pool()
+			.futures()
+			.into_iter()
+			.map(|(_hash, extrinsic)| extrinsic)
+			.collect::<Vec<<B as BlockT>::Extrinsic>>();
+
+		let api = self.client.runtime_api();
+		let best_block: BlockId<B> = BlockId::Hash(self.client.info().best_hash);
+		let api_version = if let Ok(Some(api_version)) =
+			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(&best_block)
+		{
+			api_version
+		} else {
+			return Err(internal_err("failed to retrieve Runtime Api version"));
+		};
+
+		let filter = |xts: Vec<<B as BlockT>::Extrinsic>| -> Result<Vec<EthereumTransaction>> {
+			if api_version > 1 {
+				api.extrinsic_filter(&best_block, xts).map_err(|err| {
+					internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
+				})
+			} else {
+				#[allow(deprecated)]
+				let legacy = api.extrinsic_filter_before_version_2(&best_block, xts)
+					.map_err(|err| {
+						internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
+					})?;
+				Ok(legacy.into_iter().map(|tx| tx.into()).collect())
+			}
+		};
+
+		Ok(TxPoolResult {
+			pending: filter(ready)?,
+			queued: filter(future)?,
+		})
+	}
+
+	fn map_build<T, F>(&self, f: F) -> Result<TxPoolResult<TransactionMap<T>>>
+	where
+		F: Fn(Transaction) -> T,
+	{
+		let transactions = self.ethereum_transactions()?;
+		let group = |transactions: Vec<EthereumTransaction>| {
+			let mut map = TransactionMap::new();
+			for transaction in transactions {
+				let transaction = transaction_build(transaction, None, None, None);
+				map.entry(transaction.from)
+					.or_insert_with(Default::default)
+					.insert(transaction.nonce.low_u64(), f(transaction));
+			}
+			map
+		};
+
+		Ok(TxPoolResult {
+			pending: group(transactions.pending),
+			queued: group(transactions.queued),
+		})
+	}
+
+	pub fn content(&self) -> Result<TxPoolResult<TransactionMap<Transaction>>> {
+		self.map_build(|transaction| transaction)
+	}
+
+	pub fn inspect(&self) -> Result<TxPoolResult<TransactionMap<Summary>>> {
+		self.map_build(|transaction| Summary::from(&transaction))
+	}
+
+	pub fn status(&self) -> Result<TxPoolStatus> {
+		let transactions = self.ethereum_transactions()?;
+		Ok(TxPoolStatus {
+			pending: U64::from(transactions.pending.len()),
+			queued: U64::from(transactions.queued.len()),
+		})
+	}
+}
+
+impl<B, C, A> TxPoolApiServer for TxPool<B, C, A>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	A: ChainApi<Block = B> + 'static,
+{
+	fn content(&self) -> Result<TxPoolResult<TransactionMap<Transaction>>> {
+		self.content()
+	}
+
+	fn inspect(&self) -> Result<TxPoolResult<TransactionMap<Summary>>> {
+		self.inspect()
+	}
+
+	fn status(&self) -> Result<TxPoolStatus> {
+		self.status()
+	}
+}
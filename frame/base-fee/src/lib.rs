@@ -24,6 +24,13 @@ mod tests;
 use frame_support::{traits::Get, weights::Weight};
 use sp_core::U256;
 use sp_runtime::Permill;
+// Frontier
+use fp_evm::BlockGasUsed;
+
+/// Ratio of the EIP-1559 block gas limit to its gas target.
+const ELASTICITY_MULTIPLIER: u64 = 2;
+/// Inverse of the maximum EIP-1559 base fee change between two blocks.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
 
 pub trait BaseFeeThreshold {
 	fn lower() -> Permill;
@@ -51,6 +58,12 @@ pub mod pallet {
 		type Threshold: BaseFeeThreshold;
 		type DefaultBaseFeePerGas: Get<U256>;
 		type DefaultElasticity: Get<Permill>;
+		/// Gas used by the EVM in the current block, as tracked by `pallet_ethereum`. When tracked,
+		/// `BaseFeePerGas` follows the EIP-1559 update rule on the gas used instead of adjusting to
+		/// the block weight within the `Threshold` bands.
+		type BlockGasUsed: BlockGasUsed;
+		/// Block gas limit, the EIP-1559 gas target is half of it.
+		type BlockGasLimit: Get<U256>;
 	}
 
 	#[pallet::genesis_config]
@@ -120,7 +133,7 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_: T::BlockNumber) -> Weight {
 			// Register the Weight used on_finalize.
-			// 	- One storage read to get the block_weight.
+			// 	- One storage read to get the block_weight or the block gas used.
 			// 	- One storage read to get the Elasticity.
 			// 	- One write to BaseFeePerGas.
 			let db_weight = <T as frame_system::Config>::DbWeight::get();
@@ -133,6 +146,11 @@ pub mod pallet {
 				return;
 			}
 
+			if let Some(gas_used) = T::BlockGasUsed::block_gas_used() {
+				Self::adjust_to_gas_used(gas_used);
+				return;
+			}
+
 			let lower = T::Threshold::lower();
 			let upper = T::Threshold::upper();
 			// `target` is the ideal congestion of the network where the base fee should remain unchanged.
@@ -226,4 +244,33 @@ impl<T: Config> Pallet<T> {
 		<Elasticity<T>>::put(value);
 		T::DbWeight::get().writes(1)
 	}
+
+	/// Apply the EIP-1559 base fee update rule to a block using `gas_used`.
+	fn adjust_to_gas_used(gas_used: U256) {
+		let target = T::BlockGasLimit::get() / ELASTICITY_MULTIPLIER;
+		if target.is_zero() {
+			return;
+		}
+
+		<BaseFeePerGas<T>>::mutate(|bf| {
+			if gas_used > target {
+				// Above target, increase by at least 1.
+				if let Some(scaled_basefee) = bf.checked_mul(gas_used - target) {
+					let increase = (scaled_basefee / target / BASE_FEE_MAX_CHANGE_DENOMINATOR)
+						.max(U256::one());
+					*bf = bf.saturating_add(increase);
+				} else {
+					Self::deposit_event(Event::BaseFeeOverflow);
+				}
+			} else if gas_used < target {
+				// Below target, decrease.
+				if let Some(scaled_basefee) = bf.checked_mul(target - gas_used) {
+					let decrease = scaled_basefee / target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+					*bf = bf.saturating_sub(decrease);
+				} else {
+					Self::deposit_event(Event::BaseFeeOverflow);
+				}
+			}
+		});
+	}
 }
@@ -72,6 +72,15 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = U256::from(100_000_000_000 as u128);
 	pub DefaultElasticity: Permill = Permill::from_parts(125_000);
+	pub BlockGasLimit: U256 = U256::from(30_000_000);
+	pub static MockGasUsed: Option<U256> = None;
+}
+
+pub struct MockBlockGasUsed;
+impl BlockGasUsed for MockBlockGasUsed {
+	fn block_gas_used() -> Option<U256> {
+		MockGasUsed::get()
+	}
 }
 
 pub struct BaseFeeThreshold;
@@ -92,6 +101,8 @@ impl Config for Test {
 	type Threshold = BaseFeeThreshold;
 	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 	type DefaultElasticity = DefaultElasticity;
+	type BlockGasUsed = MockBlockGasUsed;
+	type BlockGasLimit = BlockGasLimit;
 }
 
 frame_support::construct_runtime!(
@@ -284,6 +295,65 @@ fn should_idle_base_fee() {
 	});
 }
 
+#[test]
+fn should_follow_eip1559_on_gas_used() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		// Full block, twice the 15M gas target.
+		MockGasUsed::set(Some(U256::from(30_000_000)));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1125000000));
+
+		// At target, the base fee remains unchanged.
+		MockGasUsed::set(Some(U256::from(15_000_000)));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1125000000));
+
+		// 75% full block: 1125000000 * 7500000 / 15000000 / 8.
+		MockGasUsed::set(Some(U256::from(22_500_000)));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1195312500));
+
+		// Empty block.
+		MockGasUsed::set(Some(U256::zero()));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1045898438));
+	});
+}
+
+#[test]
+fn should_ignore_block_weight_when_gas_used_is_tracked() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		MockGasUsed::set(Some(U256::from(15_000_000)));
+		System::register_extra_weight_unchecked(
+			Weight::from_ref_time(1000000000000),
+			DispatchClass::Normal,
+		);
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1000000000));
+	});
+}
+
+#[test]
+fn should_increase_base_fee_by_at_least_one_above_gas_target() {
+	new_test_ext(Some(U256::one()), None).execute_with(|| {
+		MockGasUsed::set(Some(U256::from(15_000_001)));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(2));
+	});
+}
+
+#[test]
+fn should_not_overflow_u256_on_gas_used() {
+	let base_fee = U256::max_value();
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		MockGasUsed::set(Some(U256::from(30_000_000)));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFee::base_fee_per_gas(), base_fee);
+	});
+}
+
 #[test]
 fn set_base_fee_per_gas_dispatchable() {
 	let base_fee = U256::from(1_000_000_000);
@@ -36,7 +36,8 @@ use fp_ethereum::{
 	TransactionData, TransactionValidationError, ValidatedTransaction as ValidatedTransactionT,
 };
 use fp_evm::{
-	CallOrCreateInfo, CheckEvmTransaction, CheckEvmTransactionConfig, InvalidEvmTransactionError,
+	BlockGasUsed, CallOrCreateInfo, CheckEvmTransaction, CheckEvmTransactionConfig,
+	InvalidEvmTransactionError,
 };
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
 use frame_support::{
@@ -879,6 +880,29 @@ impl<T: Config> BlockHashMapping for EthereumBlockHashMapping<T> {
 	}
 }
 
+impl<T: Config> BlockGasUsed for Pallet<T> {
+	fn block_gas_used() -> Option<U256> {
+		// `on_finalize` moves the pending receipts into the current block, the caller may run
+		// before or after it.
+		let number = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(
+			frame_system::Pallet::<T>::block_number(),
+		));
+		match CurrentBlock::<T>::get() {
+			Some(block) if block.header.number == number => Some(block.header.gas_used),
+			_ => Some(
+				Pending::<T>::get().last().map_or_else(
+					U256::zero,
+					|(_, _, receipt)| match receipt {
+						Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
+							d.used_gas
+						}
+					},
+				),
+			),
+		}
+	}
+}
+
 pub struct InvalidTransactionWrapper(InvalidTransaction);
 
 impl From<InvalidEvmTransactionError> for InvalidTransactionWrapper {
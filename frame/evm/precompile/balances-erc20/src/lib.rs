@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Balances ERC-20 precompile
+//!
+//! Presents the currency of `pallet_evm` as an ERC-20 token. Balances are the free balances of
+//! the accounts the addresses map to, and approvals are stored by the pallet of this crate.
+//!
+//! The precompile spends the funds of its caller, so it must not accept delegate calls.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::format;
+use core::marker::PhantomData;
+use fp_evm::{ExitSucceed, Precompile, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use frame_support::traits::{Currency, ExistenceRequirement, Get};
+use pallet_evm::AddressMapping;
+use precompile_utils::{
+	revert, Address, Bytes, EvmDataWriter, EvmResult, FunctionModifier, LogsBuilder,
+	PrecompileHandleExt,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Bounded, CheckedSub, Zero};
+use sp_std::vec::Vec;
+
+pub use self::pallet::*;
+
+/// Topic of the `Transfer(address,address,uint256)` event.
+pub const SELECTOR_LOG_TRANSFER: H256 = H256([
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+]);
+
+/// Topic of the `Approval(address,address,uint256)` event.
+pub const SELECTOR_LOG_APPROVAL: H256 = H256([
+	0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84, 0xf3,
+	0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1e, 0x5b, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9, 0x25,
+]);
+
+type BalanceOf<T> = <<T as pallet_evm::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::BalanceOf;
+	use frame_support::pallet_prelude::*;
+	use sp_core::H160;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// Returned by `name()`.
+		type Name: Get<&'static str>;
+		/// Returned by `symbol()`.
+		type Symbol: Get<&'static str>;
+		/// Returned by `decimals()`.
+		type Decimals: Get<u8>;
+	}
+
+	/// The amounts spenders are allowed to transfer on behalf of owners, by owner and spender.
+	#[pallet::storage]
+	pub type Approves<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		H160,
+		Blake2_128Concat,
+		H160,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+}
+
+/// The functions of the precompile, by selector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Action {
+	/// `totalSupply()`
+	TotalSupply = 0x18160ddd,
+	/// `balanceOf(address)`
+	BalanceOf = 0x70a08231,
+	/// `allowance(address,address)`
+	Allowance = 0xdd62ed3e,
+	/// `transfer(address,uint256)`
+	Transfer = 0xa9059cbb,
+	/// `approve(address,uint256)`
+	Approve = 0x095ea7b3,
+	/// `transferFrom(address,address,uint256)`
+	TransferFrom = 0x23b872dd,
+	/// `name()`
+	Name = 0x06fdde03,
+	/// `symbol()`
+	Symbol = 0x95d89b41,
+	/// `decimals()`
+	Decimals = 0x313ce567,
+}
+
+impl TryFrom<u32> for Action {
+	type Error = ();
+
+	fn try_from(selector: u32) -> Result<Self, ()> {
+		[
+			Action::TotalSupply,
+			Action::BalanceOf,
+			Action::Allowance,
+			Action::Transfer,
+			Action::Approve,
+			Action::TransferFrom,
+			Action::Name,
+			Action::Symbol,
+			Action::Decimals,
+		]
+		.into_iter()
+		.find(|action| *action as u32 == selector)
+		.ok_or(())
+	}
+}
+
+/// The ERC-20 precompile of the currency of `pallet_evm`.
+pub struct BalancesErc20<T>(PhantomData<T>);
+
+impl<T> Precompile for BalancesErc20<T>
+where
+	T: Config,
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// A delegate call would spend the funds of the caller of the calling contract.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+
+		let action: Action = handle.read_selector()?;
+		handle.check_function_modifier(match action {
+			Action::Transfer | Action::Approve | Action::TransferFrom => {
+				FunctionModifier::NonPayable
+			}
+			_ => FunctionModifier::View,
+		})?;
+
+		let output = match action {
+			Action::TotalSupply => Self::total_supply(handle)?,
+			Action::BalanceOf => Self::balance_of(handle)?,
+			Action::Allowance => Self::allowance(handle)?,
+			Action::Transfer => Self::transfer(handle)?,
+			Action::Approve => Self::approve(handle)?,
+			Action::TransferFrom => Self::transfer_from(handle)?,
+			Action::Name => EvmDataWriter::new()
+				.write(Bytes::from(T::Name::get()))
+				.build(),
+			Action::Symbol => EvmDataWriter::new()
+				.write(Bytes::from(T::Symbol::get()))
+				.build(),
+			Action::Decimals => EvmDataWriter::new().write(T::Decimals::get()).build(),
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output,
+		})
+	}
+}
+
+impl<T> BalancesErc20<T>
+where
+	T: Config,
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		handle.record_db_read::<T>()?;
+
+		let total_issuance: U256 = T::Currency::total_issuance().into();
+		Ok(EvmDataWriter::new().write(total_issuance).build())
+	}
+
+	fn balance_of(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		handle.record_db_read::<T>()?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+		let owner: H160 = input.read::<Address>()?.into();
+
+		let balance: U256 =
+			T::Currency::free_balance(&T::AddressMapping::into_account_id(owner)).into();
+		Ok(EvmDataWriter::new().write(balance).build())
+	}
+
+	fn allowance(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		handle.record_db_read::<T>()?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+		let owner: H160 = input.read::<Address>()?.into();
+		let spender: H160 = input.read::<Address>()?.into();
+
+		let allowance: U256 = Approves::<T>::get(owner, spender).into();
+		Ok(EvmDataWriter::new().write(allowance).build())
+	}
+
+	fn approve(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		handle.record_db_write::<T>()?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+		let spender: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+
+		let owner = handle.context().caller;
+		// Approvals over the largest balance, such as `type(uint256).max`, are unlimited.
+		let amount = BalanceOf::<T>::try_from(value).unwrap_or_else(|_| Bounded::max_value());
+		if amount.is_zero() {
+			Approves::<T>::remove(owner, spender);
+		} else {
+			Approves::<T>::insert(owner, spender, amount);
+		}
+
+		LogsBuilder::new(handle.context().address)
+			.log3(
+				SELECTOR_LOG_APPROVAL,
+				owner,
+				spender,
+				EvmDataWriter::new().write(value).build(),
+			)
+			.record(handle)?;
+
+		Ok(EvmDataWriter::new().write(true).build())
+	}
+
+	fn transfer(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		Self::record_transfer_cost(handle)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+		let to: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+
+		let from = handle.context().caller;
+		Self::transfer_balance(from, to, value)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log3(
+				SELECTOR_LOG_TRANSFER,
+				from,
+				to,
+				EvmDataWriter::new().write(value).build(),
+			)
+			.record(handle)?;
+
+		Ok(EvmDataWriter::new().write(true).build())
+	}
+
+	fn transfer_from(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u8>> {
+		Self::record_transfer_cost(handle)?;
+		handle.record_db_read::<T>()?;
+		handle.record_db_write::<T>()?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+		let from: H160 = input.read::<Address>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+
+		let spender = handle.context().caller;
+		if spender != from {
+			let amount = Self::amount(value)?;
+			Approves::<T>::try_mutate(from, spender, |allowance| -> EvmResult {
+				// Unlimited approvals are never spent.
+				if *allowance != BalanceOf::<T>::max_value() {
+					*allowance = allowance
+						.checked_sub(&amount)
+						.ok_or_else(|| revert("Trying to spend more than allowed"))?;
+				}
+				Ok(())
+			})?;
+		}
+
+		Self::transfer_balance(from, to, value)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log3(
+				SELECTOR_LOG_TRANSFER,
+				from,
+				to,
+				EvmDataWriter::new().write(value).build(),
+			)
+			.record(handle)?;
+
+		Ok(EvmDataWriter::new().write(true).build())
+	}
+
+	/// Records the cost of reading and writing the accounts of a transfer. Its log is charged when
+	/// recorded.
+	fn record_transfer_cost(handle: &mut impl PrecompileHandle) -> EvmResult {
+		for _ in 0..2 {
+			handle.record_db_read::<T>()?;
+			handle.record_db_write::<T>()?;
+		}
+		Ok(())
+	}
+
+	fn transfer_balance(from: H160, to: H160, value: U256) -> EvmResult {
+		T::Currency::transfer(
+			&T::AddressMapping::into_account_id(from),
+			&T::AddressMapping::into_account_id(to),
+			Self::amount(value)?,
+			ExistenceRequirement::AllowDeath,
+		)
+		.map_err(|e| revert(format!("Transfer failed with error: {:?}", e)))
+	}
+
+	fn amount(value: U256) -> EvmResult<BalanceOf<T>> {
+		BalanceOf::<T>::try_from(value).map_err(|_| revert("Value is too large for balance type"))
+	}
+}
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use fp_evm::PrecompileFailure;
+use precompile_utils::{
+	keccak_selector, log_costs,
+	testing::{read_output, MockHandle, PrecompileTester},
+	Log, RuntimeHelper,
+};
+use sp_core::{H160, U256};
+
+const PRECOMPILE: H160 = H160::repeat_byte(0xee);
+const ALICE: H160 = H160::repeat_byte(0xaa);
+const BOB: H160 = H160::repeat_byte(0xbb);
+const CHARLIE: H160 = H160::repeat_byte(0xcc);
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1000), (BOB, 500)],
+	}
+	.assimilate_storage(&mut t)
+	.expect("Pallet balances storage can be assimilated");
+	t.into()
+}
+
+fn call(caller: H160, input: Vec<u8>) -> (PrecompileResult, Vec<Log>) {
+	PrecompileTester::<BalancesErc20<Test>>::new(PRECOMPILE).call(caller, input)
+}
+
+fn balance_of(owner: H160) -> U256 {
+	let input = EvmDataWriter::new_with_selector(Action::BalanceOf as u32)
+		.write(Address(owner))
+		.build();
+	read_output(call(ALICE, input).0)
+}
+
+fn allowance(owner: H160, spender: H160) -> U256 {
+	let input = EvmDataWriter::new_with_selector(Action::Allowance as u32)
+		.write(Address(owner))
+		.write(Address(spender))
+		.build();
+	read_output(call(ALICE, input).0)
+}
+
+fn approve(owner: H160, spender: H160, value: U256) {
+	let input = EvmDataWriter::new_with_selector(Action::Approve as u32)
+		.write(Address(spender))
+		.write(value)
+		.build();
+	let (result, logs) = call(owner, input);
+	assert!(read_output::<bool>(result));
+	assert_eq!(
+		logs,
+		vec![LogsBuilder::new(PRECOMPILE).log3(
+			SELECTOR_LOG_APPROVAL,
+			owner,
+			spender,
+			EvmDataWriter::new().write(value).build(),
+		)]
+	);
+}
+
+fn transfer_from(spender: H160, from: H160, to: H160, value: u64) -> PrecompileResult {
+	let input = EvmDataWriter::new_with_selector(Action::TransferFrom as u32)
+		.write(Address(from))
+		.write(Address(to))
+		.write(U256::from(value))
+		.build();
+	call(spender, input).0
+}
+
+#[test]
+fn selectors_match_the_signatures() {
+	assert_eq!(Action::TotalSupply as u32, keccak_selector("totalSupply()"));
+	assert_eq!(
+		Action::BalanceOf as u32,
+		keccak_selector("balanceOf(address)")
+	);
+	assert_eq!(
+		Action::Allowance as u32,
+		keccak_selector("allowance(address,address)")
+	);
+	assert_eq!(
+		Action::Transfer as u32,
+		keccak_selector("transfer(address,uint256)")
+	);
+	assert_eq!(
+		Action::Approve as u32,
+		keccak_selector("approve(address,uint256)")
+	);
+	assert_eq!(
+		Action::TransferFrom as u32,
+		keccak_selector("transferFrom(address,address,uint256)")
+	);
+	assert_eq!(Action::Name as u32, keccak_selector("name()"));
+	assert_eq!(Action::Symbol as u32, keccak_selector("symbol()"));
+	assert_eq!(Action::Decimals as u32, keccak_selector("decimals()"));
+
+	assert_eq!(
+		SELECTOR_LOG_TRANSFER,
+		H256(sp_io::hashing::keccak_256(
+			b"Transfer(address,address,uint256)"
+		))
+	);
+	assert_eq!(
+		SELECTOR_LOG_APPROVAL,
+		H256(sp_io::hashing::keccak_256(
+			b"Approval(address,address,uint256)"
+		))
+	);
+}
+
+#[test]
+fn metadata_comes_from_the_config() {
+	new_test_ext().execute_with(|| {
+		let name: Bytes =
+			read_output(call(ALICE, keccak_selector("name()").to_be_bytes().to_vec()).0);
+		assert_eq!(name.as_str(), Ok("Unit"));
+		let symbol: Bytes =
+			read_output(call(ALICE, keccak_selector("symbol()").to_be_bytes().to_vec()).0);
+		assert_eq!(symbol.as_str(), Ok("UNIT"));
+		let decimals: u8 =
+			read_output(call(ALICE, keccak_selector("decimals()").to_be_bytes().to_vec()).0);
+		assert_eq!(decimals, 18);
+	});
+}
+
+#[test]
+fn balances_are_the_free_balances() {
+	new_test_ext().execute_with(|| {
+		let total_supply: U256 = read_output(
+			call(
+				ALICE,
+				keccak_selector("totalSupply()").to_be_bytes().to_vec(),
+			)
+			.0,
+		);
+		assert_eq!(total_supply, U256::from(1500));
+		assert_eq!(balance_of(ALICE), U256::from(1000));
+		assert_eq!(balance_of(CHARLIE), U256::zero());
+	});
+}
+
+#[test]
+fn transfer_moves_the_balance() {
+	new_test_ext().execute_with(|| {
+		let input = EvmDataWriter::new_with_selector(Action::Transfer as u32)
+			.write(Address(CHARLIE))
+			.write(U256::from(400))
+			.build();
+		let (result, logs) = call(ALICE, input);
+
+		assert!(read_output::<bool>(result));
+		assert_eq!(balance_of(ALICE), U256::from(600));
+		assert_eq!(balance_of(CHARLIE), U256::from(400));
+		assert_eq!(
+			logs,
+			vec![LogsBuilder::new(PRECOMPILE).log3(
+				SELECTOR_LOG_TRANSFER,
+				ALICE,
+				CHARLIE,
+				EvmDataWriter::new().write(U256::from(400)).build(),
+			)]
+		);
+	});
+}
+
+#[test]
+fn transfer_fails_over_the_balance() {
+	new_test_ext().execute_with(|| {
+		let input = EvmDataWriter::new_with_selector(Action::Transfer as u32)
+			.write(Address(CHARLIE))
+			.write(U256::from(1001))
+			.build();
+		let (result, logs) = call(ALICE, input);
+
+		assert!(matches!(result, Err(PrecompileFailure::Revert { .. })));
+		assert!(logs.is_empty());
+		assert_eq!(balance_of(ALICE), U256::from(1000));
+
+		let input = EvmDataWriter::new_with_selector(Action::Transfer as u32)
+			.write(Address(CHARLIE))
+			.write(U256::from(u128::MAX))
+			.build();
+		assert_eq!(
+			call(ALICE, input).0,
+			Err(revert("Value is too large for balance type"))
+		);
+	});
+}
+
+#[test]
+fn transfer_from_spends_the_allowance() {
+	new_test_ext().execute_with(|| {
+		approve(ALICE, BOB, U256::from(300));
+		assert_eq!(allowance(ALICE, BOB), U256::from(300));
+
+		assert!(read_output::<bool>(transfer_from(BOB, ALICE, CHARLIE, 200)));
+		assert_eq!(allowance(ALICE, BOB), U256::from(100));
+		assert_eq!(balance_of(ALICE), U256::from(800));
+		assert_eq!(balance_of(CHARLIE), U256::from(200));
+
+		assert_eq!(
+			transfer_from(BOB, ALICE, CHARLIE, 101),
+			Err(revert("Trying to spend more than allowed"))
+		);
+		assert_eq!(allowance(ALICE, BOB), U256::from(100));
+
+		// Owners don't need an approval.
+		assert!(read_output::<bool>(transfer_from(
+			ALICE, ALICE, CHARLIE, 500
+		)));
+		assert_eq!(balance_of(CHARLIE), U256::from(700));
+
+		approve(ALICE, BOB, U256::zero());
+		assert_eq!(allowance(ALICE, BOB), U256::zero());
+		assert!(!Approves::<Test>::contains_key(ALICE, BOB));
+	});
+}
+
+#[test]
+fn unlimited_approvals_are_not_spent() {
+	new_test_ext().execute_with(|| {
+		approve(ALICE, BOB, U256::max_value());
+		assert_eq!(allowance(ALICE, BOB), U256::from(u64::MAX));
+
+		assert!(read_output::<bool>(transfer_from(BOB, ALICE, CHARLIE, 200)));
+		assert_eq!(allowance(ALICE, BOB), U256::from(u64::MAX));
+	});
+}
+
+#[test]
+fn transfer_and_approve_charge_their_log_once() {
+	new_test_ext().execute_with(|| {
+		let db_read = RuntimeHelper::<Test>::db_read_gas_cost();
+		let db_write = RuntimeHelper::<Test>::db_write_gas_cost();
+		let log = log_costs(3, 32).unwrap();
+
+		let input = EvmDataWriter::new_with_selector(Action::Transfer as u32)
+			.write(Address(CHARLIE))
+			.write(U256::from(400))
+			.build();
+		let mut handle = MockHandle::new(PRECOMPILE, ALICE, input);
+		assert!(BalancesErc20::<Test>::execute(&mut handle).is_ok());
+		assert_eq!(handle.gas_used, 2 * (db_read + db_write) + log);
+
+		let input = EvmDataWriter::new_with_selector(Action::Approve as u32)
+			.write(Address(BOB))
+			.write(U256::from(100))
+			.build();
+		let mut handle = MockHandle::new(PRECOMPILE, ALICE, input);
+		assert!(BalancesErc20::<Test>::execute(&mut handle).is_ok());
+		assert_eq!(handle.gas_used, db_write + log);
+	});
+}
+
+#[test]
+fn rejects_value_static_and_delegate_calls() {
+	new_test_ext().execute_with(|| {
+		let input = EvmDataWriter::new_with_selector(Action::Transfer as u32)
+			.write(Address(CHARLIE))
+			.write(U256::from(1))
+			.build();
+
+		let mut handle = MockHandle::new(PRECOMPILE, ALICE, input.clone());
+		handle.context.apparent_value = U256::one();
+		assert_eq!(
+			BalancesErc20::<Test>::execute(&mut handle),
+			Err(revert("Function is not payable"))
+		);
+
+		let mut handle = MockHandle::new(PRECOMPILE, ALICE, input.clone());
+		handle.is_static = true;
+		assert_eq!(
+			BalancesErc20::<Test>::execute(&mut handle),
+			Err(revert("Can't call non-static function in static context"))
+		);
+
+		let mut handle = MockHandle::new(PRECOMPILE, ALICE, input);
+		handle.context.address = BOB;
+		assert_eq!(
+			BalancesErc20::<Test>::execute(&mut handle),
+			Err(revert("Cannot be called with DELEGATECALL or CALLCODE"))
+		);
+
+		assert_eq!(balance_of(ALICE), U256::from(1000));
+	});
+}
@@ -0,0 +1,563 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 precompiles of [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ark_bls12_381::{
+	g1, g2, Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	pairing::Pairing,
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInteger, BigInteger384, PrimeField, Zero};
+use fp_evm::{
+	ExitError, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+	PrecompileResult,
+};
+
+/// Length of a field element once encoded, padded with 16 zero bytes.
+const FP_LENGTH: usize = 64;
+/// Length of a field element without its padding.
+const FP_UNPADDED_LENGTH: usize = 48;
+const FP2_LENGTH: usize = 2 * FP_LENGTH;
+const G1_LENGTH: usize = 2 * FP_LENGTH;
+const G2_LENGTH: usize = 2 * FP2_LENGTH;
+const SCALAR_LENGTH: usize = 32;
+
+/// Divisor of the MSM discounts.
+const MSM_MULTIPLIER: u64 = 1000;
+
+/// Discounts of a G1 MSM by number of pairs, the last one applying to any larger MSM.
+const G1_MSM_DISCOUNTS: [u16; 128] = [
+	1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665,
+	661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615,
+	613, 611, 609, 608, 606, 604, 603, 601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585,
+	584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564, 563,
+	562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547, 547, 546, 545,
+	544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529,
+	528, 528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Discounts of a G2 MSM by number of pairs, the last one applying to any larger MSM.
+const G2_MSM_DISCOUNTS: [u16; 128] = [
+	1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704,
+	699, 693, 688, 683, 679, 674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632,
+	629, 627, 624, 622, 620, 618, 615, 613, 611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593,
+	592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576, 575, 574, 573, 571, 570, 569, 568,
+	567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552, 551, 550, 549,
+	548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534,
+	533, 532, 532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+fn error(message: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error {
+		exit_status: ExitError::Other(message.into()),
+	}
+}
+
+/// Gas cost of a MSM of `pairs` pairs.
+fn msm_gas_cost(pairs: usize, multiplication_cost: u64, discounts: &[u16; 128]) -> u64 {
+	let discount = discounts[pairs.min(discounts.len()) - 1];
+	(pairs as u64)
+		.saturating_mul(multiplication_cost)
+		.saturating_mul(discount.into())
+		/ MSM_MULTIPLIER
+}
+
+/// Number of `pair_length` long pairs in `input`, which must hold at least one.
+fn pairs_count(input: &[u8], pair_length: usize) -> Result<usize, PrecompileFailure> {
+	if input.is_empty() || input.len() % pair_length != 0 {
+		return Err(error("Invalid input length"));
+	}
+	Ok(input.len() / pair_length)
+}
+
+fn read_fq(input: &[u8]) -> Result<Fq, PrecompileFailure> {
+	let (padding, bytes) = input.split_at(FP_LENGTH - FP_UNPADDED_LENGTH);
+	if padding.iter().any(|byte| *byte != 0) {
+		return Err(error("Invalid field element padding"));
+	}
+
+	let mut limbs = [0u64; 6];
+	for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunks are 8 bytes long; qed"));
+	}
+	Fq::from_bigint(BigInteger384::new(limbs))
+		.ok_or_else(|| error("Field element is not canonical"))
+}
+
+fn read_fq2(input: &[u8]) -> Result<Fq2, PrecompileFailure> {
+	Ok(Fq2::new(
+		read_fq(&input[..FP_LENGTH])?,
+		read_fq(&input[FP_LENGTH..FP2_LENGTH])?,
+	))
+}
+
+fn write_fq(output: &mut Vec<u8>, fq: &Fq) {
+	output.extend_from_slice(&[0u8; FP_LENGTH - FP_UNPADDED_LENGTH]);
+	output.extend_from_slice(&fq.into_bigint().to_bytes_be());
+}
+
+/// Reads a G1 point, checking that it is on the curve and, with `subgroup_check`, in the
+/// subgroup. The point at infinity is encoded as zeros.
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, PrecompileFailure> {
+	let x = read_fq(&input[..FP_LENGTH])?;
+	let y = read_fq(&input[FP_LENGTH..G1_LENGTH])?;
+	if x.is_zero() && y.is_zero() {
+		return Ok(G1Affine::identity());
+	}
+
+	let point = G1Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err(error("G1 point is not on the curve"));
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(error("G1 point is not in the subgroup"));
+	}
+	Ok(point)
+}
+
+/// Reads a G2 point, checking that it is on the curve and, with `subgroup_check`, in the
+/// subgroup. The point at infinity is encoded as zeros.
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, PrecompileFailure> {
+	let x = read_fq2(&input[..FP2_LENGTH])?;
+	let y = read_fq2(&input[FP2_LENGTH..G2_LENGTH])?;
+	if x.is_zero() && y.is_zero() {
+		return Ok(G2Affine::identity());
+	}
+
+	let point = G2Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err(error("G2 point is not on the curve"));
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(error("G2 point is not in the subgroup"));
+	}
+	Ok(point)
+}
+
+fn encode_g1(point: G1Affine) -> Vec<u8> {
+	let mut output = Vec::with_capacity(G1_LENGTH);
+	match point.xy() {
+		Some((x, y)) => {
+			write_fq(&mut output, x);
+			write_fq(&mut output, y);
+		}
+		None => output.resize(G1_LENGTH, 0),
+	}
+	output
+}
+
+fn encode_g2(point: G2Affine) -> Vec<u8> {
+	let mut output = Vec::with_capacity(G2_LENGTH);
+	match point.xy() {
+		Some((x, y)) => {
+			write_fq(&mut output, &x.c0);
+			write_fq(&mut output, &x.c1);
+			write_fq(&mut output, &y.c0);
+			write_fq(&mut output, &y.c1);
+		}
+		None => output.resize(G2_LENGTH, 0),
+	}
+	output
+}
+
+fn read_scalar(input: &[u8]) -> Fr {
+	// Points are in the subgroup, so reducing the scalar does not change the product.
+	Fr::from_be_bytes_mod_order(input)
+}
+
+fn returned(output: Vec<u8>) -> PrecompileResult {
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output,
+	})
+}
+
+/// The BLS12_G1ADD builtin
+pub struct Bls12381G1Add;
+
+impl Bls12381G1Add {
+	const GAS_COST: u64 = 375;
+}
+
+impl Precompile for Bls12381G1Add {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G1Add::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != 2 * G1_LENGTH {
+			return Err(error("Invalid input length"));
+		}
+
+		let p1 = read_g1(&input[..G1_LENGTH], false)?;
+		let p2 = read_g1(&input[G1_LENGTH..], false)?;
+		returned(encode_g1((p1 + p2).into_affine()))
+	}
+}
+
+/// The BLS12_G1MSM builtin
+pub struct Bls12381G1Msm;
+
+impl Bls12381G1Msm {
+	const MULTIPLICATION_GAS_COST: u64 = 12_000;
+	const PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+}
+
+impl Precompile for Bls12381G1Msm {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pairs = pairs_count(handle.input(), Bls12381G1Msm::PAIR_LENGTH)?;
+		handle.record_cost(msm_gas_cost(
+			pairs,
+			Bls12381G1Msm::MULTIPLICATION_GAS_COST,
+			&G1_MSM_DISCOUNTS,
+		))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in handle.input().chunks(Bls12381G1Msm::PAIR_LENGTH) {
+			bases.push(read_g1(&pair[..G1_LENGTH], true)?);
+			scalars.push(read_scalar(&pair[G1_LENGTH..]));
+		}
+
+		let product = G1Projective::msm(&bases, &scalars)
+			.map_err(|_| error("Mismatched points and scalars"))?;
+		returned(encode_g1(product.into_affine()))
+	}
+}
+
+/// The BLS12_G2ADD builtin
+pub struct Bls12381G2Add;
+
+impl Bls12381G2Add {
+	const GAS_COST: u64 = 600;
+}
+
+impl Precompile for Bls12381G2Add {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G2Add::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != 2 * G2_LENGTH {
+			return Err(error("Invalid input length"));
+		}
+
+		let p1 = read_g2(&input[..G2_LENGTH], false)?;
+		let p2 = read_g2(&input[G2_LENGTH..], false)?;
+		returned(encode_g2((p1 + p2).into_affine()))
+	}
+}
+
+/// The BLS12_G2MSM builtin
+pub struct Bls12381G2Msm;
+
+impl Bls12381G2Msm {
+	const MULTIPLICATION_GAS_COST: u64 = 22_500;
+	const PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+}
+
+impl Precompile for Bls12381G2Msm {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pairs = pairs_count(handle.input(), Bls12381G2Msm::PAIR_LENGTH)?;
+		handle.record_cost(msm_gas_cost(
+			pairs,
+			Bls12381G2Msm::MULTIPLICATION_GAS_COST,
+			&G2_MSM_DISCOUNTS,
+		))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in handle.input().chunks(Bls12381G2Msm::PAIR_LENGTH) {
+			bases.push(read_g2(&pair[..G2_LENGTH], true)?);
+			scalars.push(read_scalar(&pair[G2_LENGTH..]));
+		}
+
+		let product = G2Projective::msm(&bases, &scalars)
+			.map_err(|_| error("Mismatched points and scalars"))?;
+		returned(encode_g2(product.into_affine()))
+	}
+}
+
+/// The BLS12_PAIRING_CHECK builtin
+pub struct Bls12381Pairing;
+
+impl Bls12381Pairing {
+	const BASE_GAS_COST: u64 = 37_700;
+	const GAS_COST_PER_PAIRING: u64 = 32_600;
+	const PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+}
+
+impl Precompile for Bls12381Pairing {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pairs = pairs_count(handle.input(), Bls12381Pairing::PAIR_LENGTH)?;
+		handle
+			.record_cost(Bls12381Pairing::BASE_GAS_COST.saturating_add(
+				Bls12381Pairing::GAS_COST_PER_PAIRING.saturating_mul(pairs as u64),
+			))?;
+
+		let mut g1_points = Vec::with_capacity(pairs);
+		let mut g2_points = Vec::with_capacity(pairs);
+		for pair in handle.input().chunks(Bls12381Pairing::PAIR_LENGTH) {
+			g1_points.push(read_g1(&pair[..G1_LENGTH], true)?);
+			g2_points.push(read_g2(&pair[G1_LENGTH..], true)?);
+		}
+
+		let mut output = [0u8; 32];
+		if Bls12_381::multi_pairing(g1_points, g2_points).is_zero() {
+			output[31] = 1;
+		}
+		returned(output.to_vec())
+	}
+}
+
+/// The BLS12_MAP_FP_TO_G1 builtin
+pub struct Bls12381MapG1;
+
+impl Bls12381MapG1 {
+	const GAS_COST: u64 = 5_500;
+}
+
+impl Precompile for Bls12381MapG1 {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG1::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != FP_LENGTH {
+			return Err(error("Invalid input length"));
+		}
+
+		let fq = read_fq(input)?;
+		let point = WBMap::<g1::Config>::new()
+			.and_then(|map| map.map_to_curve(fq))
+			.map_err(|_| error("Cannot map the field element to G1"))?;
+		returned(encode_g1(point.clear_cofactor()))
+	}
+}
+
+/// The BLS12_MAP_FP2_TO_G2 builtin
+pub struct Bls12381MapG2;
+
+impl Bls12381MapG2 {
+	const GAS_COST: u64 = 23_800;
+}
+
+impl Precompile for Bls12381MapG2 {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG2::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != FP2_LENGTH {
+			return Err(error("Invalid input length"));
+		}
+
+		let fq2 = read_fq2(input)?;
+		let point = WBMap::<g2::Config>::new()
+			.and_then(|map| map.map_to_curve(fq2))
+			.map_err(|_| error("Cannot map the field element to G2"))?;
+		returned(encode_g2(point.clear_cofactor()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fp_evm::Context;
+	use pallet_evm_test_vector_support::{
+		test_precompile_failure_test_vectors, test_precompile_test_vectors, MockHandle,
+	};
+
+	/// A point of the G1 curve outside of the subgroup.
+	const G1_NOT_IN_SUBGROUP: &str = "\
+		00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004\
+		000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c";
+
+	fn execute<P: Precompile>(input: Vec<u8>) -> (PrecompileResult, u64) {
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		let mut handle = MockHandle::new(input, Some(10_000_000), context);
+		let result = P::execute(&mut handle);
+		(result, handle.gas_used)
+	}
+
+	#[test]
+	fn process_consensus_tests_for_g1_add() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G1Add>("../testdata/bls12381G1Add.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_g1_msm() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G1Msm>("../testdata/bls12381G1Msm.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_g2_add() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G2Add>("../testdata/bls12381G2Add.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_g2_msm() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G2Msm>("../testdata/bls12381G2Msm.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_pairing() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381Pairing>("../testdata/bls12381Pairing.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_map_fp_to_g1() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381MapG1>("../testdata/bls12381MapG1.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_tests_for_map_fp2_to_g2() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381MapG2>("../testdata/bls12381MapG2.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_failure_tests() -> Result<(), String> {
+		test_precompile_failure_test_vectors::<Bls12381G1Add>(
+			"../testdata/fail-bls12381G1Add.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381G1Msm>(
+			"../testdata/fail-bls12381G1Msm.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381G2Add>(
+			"../testdata/fail-bls12381G2Add.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381G2Msm>(
+			"../testdata/fail-bls12381G2Msm.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381Pairing>(
+			"../testdata/fail-bls12381Pairing.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381MapG1>(
+			"../testdata/fail-bls12381MapG1.json",
+		)?;
+		test_precompile_failure_test_vectors::<Bls12381MapG2>(
+			"../testdata/fail-bls12381MapG2.json",
+		)?;
+		Ok(())
+	}
+
+	#[test]
+	fn msm_discount_is_capped() {
+		assert_eq!(
+			msm_gas_cost(128, 12_000, &G1_MSM_DISCOUNTS),
+			128 * 12_000 * 519 / 1000
+		);
+		assert_eq!(
+			msm_gas_cost(200, 12_000, &G1_MSM_DISCOUNTS),
+			200 * 12_000 * 519 / 1000
+		);
+		assert_eq!(
+			msm_gas_cost(200, 22_500, &G2_MSM_DISCOUNTS),
+			200 * 22_500 * 524 / 1000
+		);
+	}
+
+	#[test]
+	fn map_fp_to_g1_works() {
+		let mut input = vec![0u8; FP_LENGTH];
+		input[FP_LENGTH - 1] = 7;
+		let (result, gas_used) = execute::<Bls12381MapG1>(input.clone());
+		let output = result.expect("a canonical field element is mapped").output;
+		assert_eq!(gas_used, 5_500);
+
+		let point = read_g1(&output, true).expect("the mapped point is in G1");
+		assert!(!point.is_zero());
+		// The map is deterministic.
+		assert_eq!(execute::<Bls12381MapG1>(input).0.unwrap().output, output);
+	}
+
+	#[test]
+	fn map_fp2_to_g2_works() {
+		let mut input = vec![0u8; FP2_LENGTH];
+		input[FP_LENGTH - 1] = 7;
+		input[FP2_LENGTH - 1] = 11;
+		let (result, gas_used) = execute::<Bls12381MapG2>(input);
+		let output = result.expect("a canonical field element is mapped").output;
+		assert_eq!(gas_used, 23_800);
+
+		let point = read_g2(&output, true).expect("the mapped point is in G2");
+		assert!(!point.is_zero());
+	}
+
+	#[test]
+	fn non_canonical_field_elements_are_rejected() {
+		// The field modulus.
+		let modulus = hex::decode(
+			"000000000000000000000000000000001a0111ea397fe69a4b1ba7b6434bacd7\
+			64774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+		)
+		.unwrap();
+		assert!(execute::<Bls12381MapG1>(modulus).0.is_err());
+
+		let mut padded = vec![0u8; FP_LENGTH];
+		padded[0] = 1;
+		assert!(execute::<Bls12381MapG1>(padded).0.is_err());
+	}
+
+	#[test]
+	fn invalid_inputs_are_rejected() {
+		assert!(execute::<Bls12381G1Add>(vec![0u8; G1_LENGTH]).0.is_err());
+		assert!(execute::<Bls12381G1Msm>(Vec::new()).0.is_err());
+		assert!(execute::<Bls12381Pairing>(Vec::new()).0.is_err());
+
+		// (1, 1) is not on the curve.
+		let mut off_curve = vec![0u8; G1_LENGTH];
+		off_curve[FP_LENGTH - 1] = 1;
+		off_curve[G1_LENGTH - 1] = 1;
+		let mut input = off_curve;
+		input.extend_from_slice(&[0u8; G1_LENGTH]);
+		assert!(execute::<Bls12381G1Add>(input).0.is_err());
+	}
+
+	#[test]
+	fn subgroup_is_only_checked_by_msm_and_pairing() {
+		let point = hex::decode(G1_NOT_IN_SUBGROUP).unwrap();
+
+		let mut input = point.clone();
+		input.extend_from_slice(&[0u8; G1_LENGTH]);
+		let output = execute::<Bls12381G1Add>(input).0.unwrap().output;
+		assert_eq!(output, point);
+
+		let mut input = point;
+		input.extend_from_slice(&[0u8; SCALAR_LENGTH]);
+		assert_eq!(
+			execute::<Bls12381G1Msm>(input).0,
+			Err(error("G1 point is not in the subgroup"))
+		);
+	}
+}
@@ -36,20 +36,66 @@ use frame_support::{
 	traits::{ConstU32, Get},
 };
 use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_core::H160;
+
+/// What the `Dispatch` precompile lets callers dispatch, and how.
+pub trait DispatchValidateT<T>
+where
+	T: pallet_evm::Config,
+	T::RuntimeCall: GetDispatchInfo,
+	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<T::AccountId>>,
+{
+	/// Checks `call` may be dispatched on behalf of `caller`, failing the precompile otherwise.
+	/// By default, only the calls paying fees in the normal dispatch class are accepted. Calls
+	/// from contracts can be told apart by the code `pallet_evm::AccountCodes` stores for `caller`.
+	fn validate_before_dispatch(_caller: H160, call: &T::RuntimeCall) -> Option<PrecompileFailure> {
+		let info = call.get_dispatch_info();
+		if info.pays_fee != Pays::Yes || info.class != DispatchClass::Normal {
+			return Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid call".into()),
+			});
+		}
+
+		None
+	}
+
+	/// Gas charged on top of the weight of `call`, e.g. for the storage read by the validation.
+	fn gas_surcharge(_call: &T::RuntimeCall) -> u64 {
+		0
+	}
+
+	/// The origin `call` is dispatched from. By default, signed by the account `caller` maps to.
+	fn dispatch_origin(
+		caller: H160,
+		_call: &T::RuntimeCall,
+	) -> <T::RuntimeCall as Dispatchable>::RuntimeOrigin {
+		Some(T::AddressMapping::into_account_id(caller)).into()
+	}
+}
+
+impl<T> DispatchValidateT<T> for ()
+where
+	T: pallet_evm::Config,
+	T::RuntimeCall: GetDispatchInfo,
+	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<T::AccountId>>,
+{
+}
 
 // `DecodeLimit` specifies the max depth a call can use when decoding, as unbounded depth
 // can be used to overflow the stack.
 // Default value is 8, which is the same as in XCM call decoding.
-pub struct Dispatch<T, DecodeLimit = ConstU32<8>> {
-	_marker: PhantomData<(T, DecodeLimit)>,
+// `DispatchValidator` restricts the calls and chooses their origin, see `DispatchValidateT`.
+pub struct Dispatch<T, DecodeLimit = ConstU32<8>, DispatchValidator = ()> {
+	_marker: PhantomData<(T, DecodeLimit, DispatchValidator)>,
 }
 
-impl<T, DecodeLimit> Precompile for Dispatch<T, DecodeLimit>
+impl<T, DecodeLimit, DispatchValidator> Precompile for Dispatch<T, DecodeLimit, DispatchValidator>
 where
 	T: pallet_evm::Config,
 	T::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
 	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<T::AccountId>>,
 	DecodeLimit: Get<u32>,
+	DispatchValidator: DispatchValidateT<T>,
 {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		let input = handle.input();
@@ -62,16 +108,18 @@ where
 			})?;
 		let info = call.get_dispatch_info();
 
-		let valid_call = info.pays_fee == Pays::Yes && info.class == DispatchClass::Normal;
-		if !valid_call {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid call".into()),
-			});
+		if let Some(failure) = DispatchValidator::validate_before_dispatch(context.caller, &call) {
+			return Err(failure);
 		}
 
+		let origin = DispatchValidator::dispatch_origin(context.caller, &call);
+		let surcharge = DispatchValidator::gas_surcharge(&call);
+		handle.record_cost(surcharge)?;
+
 		if let Some(gas) = target_gas {
-			let valid_weight =
-				info.weight.ref_time() <= T::GasWeightMapping::gas_to_weight(gas, false).ref_time();
+			let valid_weight = info.weight.ref_time()
+				<= T::GasWeightMapping::gas_to_weight(gas.saturating_sub(surcharge), false)
+					.ref_time();
 			if !valid_weight {
 				return Err(PrecompileFailure::Error {
 					exit_status: ExitError::OutOfGas,
@@ -79,9 +127,7 @@ where
 			}
 		}
 
-		let origin = T::AddressMapping::into_account_id(context.caller);
-
-		match call.dispatch(Some(origin).into()) {
+		match call.dispatch(origin) {
 			Ok(post_info) => {
 				let cost = T::GasWeightMapping::weight_to_gas(
 					post_info.actual_weight.unwrap_or(info.weight),
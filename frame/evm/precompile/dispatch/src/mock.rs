@@ -30,12 +30,14 @@ use sp_runtime::{
 };
 use sp_std::{boxed::Box, prelude::*, str::FromStr};
 
-use fp_evm::{ExitError, ExitReason, Transfer};
+use fp_evm::{ExitError, ExitReason, PrecompileFailure, Transfer};
 use pallet_evm::{
 	Context, EnsureAddressNever, EnsureAddressRoot, FeeCalculator, IdentityAddressMapping,
 	PrecompileHandle,
 };
 
+use crate::DispatchValidateT;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -158,11 +160,55 @@ impl pallet_evm::Config for Test {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type FindAuthor = FindAuthorTruncated;
+	type Hardforks = ();
+}
+
+/// Dispatches from the root origin with `RestrictedDispatchValidator`.
+pub const ROOT_CALLER: H160 = H160::repeat_byte(0xaa);
+/// Gas charged on top of the weight of the remarks by `RestrictedDispatchValidator`.
+pub const REMARK_SURCHARGE: u64 = 1_000;
+
+/// Only accepts the calls of `frame_system` and `Balances::set_balance`, from externally owned
+/// accounts.
+pub struct RestrictedDispatchValidator;
+
+impl DispatchValidateT<Test> for RestrictedDispatchValidator {
+	fn validate_before_dispatch(caller: H160, call: &RuntimeCall) -> Option<PrecompileFailure> {
+		if pallet_evm::AccountCodes::<Test>::decode_len(caller).unwrap_or(0) > 0 {
+			return Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("caller is a contract".into()),
+			});
+		}
+
+		match call {
+			RuntimeCall::System(_)
+			| RuntimeCall::Balances(pallet_balances::Call::set_balance { .. }) => None,
+			_ => Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("call filtered out".into()),
+			}),
+		}
+	}
+
+	fn gas_surcharge(call: &RuntimeCall) -> u64 {
+		match call {
+			RuntimeCall::System(frame_system::Call::remark { .. }) => REMARK_SURCHARGE,
+			_ => 0,
+		}
+	}
+
+	fn dispatch_origin(caller: H160, _call: &RuntimeCall) -> RuntimeOrigin {
+		if caller == ROOT_CALLER {
+			RuntimeOrigin::root()
+		} else {
+			RuntimeOrigin::signed(caller)
+		}
+	}
 }
 
 pub(crate) struct MockHandle {
 	pub input: Vec<u8>,
 	pub context: Context,
+	pub gas_used: u64,
 }
 
 impl PrecompileHandle for MockHandle {
@@ -178,10 +224,22 @@ impl PrecompileHandle for MockHandle {
 		unimplemented!()
 	}
 
-	fn record_cost(&mut self, _: u64) -> Result<(), ExitError> {
+	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.gas_used += cost;
 		Ok(())
 	}
 
+	fn record_external_cost(
+		&mut self,
+		_: Option<u64>,
+		_: Option<u64>,
+		_: Option<u64>,
+	) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
 	fn remaining_gas(&self) -> u64 {
 		unimplemented!()
 	}
@@ -99,6 +99,7 @@ fn decode_limit_too_high() {
 				caller: H160::default(),
 				apparent_value: U256::default(),
 			},
+			gas_used: 0,
 		};
 
 		assert_eq!(
@@ -130,8 +131,96 @@ fn decode_limit_ok() {
 				caller: H160::default(),
 				apparent_value: U256::default(),
 			},
+			gas_used: 0,
 		};
 
 		assert_ok!(Dispatch::<Test>::execute(&mut handle));
 	});
 }
+
+fn restricted_dispatch(caller: H160, call: RuntimeCall) -> (PrecompileResult, u64) {
+	let mut handle = MockHandle {
+		input: call.encode(),
+		context: Context {
+			address: H160::default(),
+			caller,
+			apparent_value: U256::default(),
+		},
+		gas_used: 0,
+	};
+
+	let result = Dispatch::<Test, ConstU32<8>, RestrictedDispatchValidator>::execute(&mut handle);
+	(result, handle.gas_used)
+}
+
+#[test]
+fn filtered_call_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+			index: 0,
+			call: Box::new(RuntimeCall::System(frame_system::Call::remark {
+				remark: Vec::new(),
+			})),
+		});
+
+		assert_eq!(
+			restricted_dispatch(H160::default(), call).0,
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("call filtered out".into())
+			})
+		);
+	});
+}
+
+#[test]
+fn contract_caller_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() });
+
+		assert_eq!(
+			restricted_dispatch(contract, call).0,
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("caller is a contract".into())
+			})
+		);
+	});
+}
+
+#[test]
+fn gas_surcharge_is_recorded() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() });
+		let weight_gas = <Test as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			call.get_dispatch_info().weight,
+		);
+
+		let (result, gas_used) = restricted_dispatch(H160::default(), call);
+		assert_ok!(result);
+		assert_eq!(gas_used, REMARK_SURCHARGE + weight_gas);
+	});
+}
+
+#[test]
+fn custom_origin_is_used() {
+	new_test_ext().execute_with(|| {
+		let who = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let call = RuntimeCall::Balances(pallet_balances::Call::set_balance {
+			who,
+			new_free: 42,
+			new_reserved: 0,
+		});
+
+		// Signed origins can't set balances.
+		assert_eq!(
+			restricted_dispatch(H160::default(), call.clone()).0,
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("dispatch execution failed".into())
+			})
+		);
+		assert_eq!(Balances::free_balance(who), 0);
+
+		assert_ok!(restricted_dispatch(ROOT_CALLER, call).0);
+		assert_eq!(Balances::free_balance(who), 42);
+	});
+}
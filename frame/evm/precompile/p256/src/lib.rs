@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use fp_evm::{ExitSucceed, Precompile, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use p256::{
+	ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+	EncodedPoint, FieldBytes,
+};
+
+/// The P256VERIFY builtin of [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md).
+///
+/// Input is the message hash, the `r` and `s` signature components and the `x` and `y` public
+/// key coordinates, 32 bytes each. Output is 1 as a 32 bytes word when the signature is valid,
+/// and empty otherwise.
+pub struct P256Verify;
+
+impl P256Verify {
+	const GAS_COST: u64 = 3_450;
+	const INPUT_LENGTH: usize = 160;
+}
+
+/// Whether `input` holds a valid signature of its message hash.
+fn verify(input: &[u8]) -> bool {
+	if input.len() != P256Verify::INPUT_LENGTH {
+		return false;
+	}
+
+	let hash = &input[0..32];
+	// Rejects the zero components and the ones above the curve order. High `s` values are valid.
+	let signature = match Signature::from_slice(&input[32..96]) {
+		Ok(signature) => signature,
+		Err(_) => return false,
+	};
+	// Rejects the coordinates out of the field, the points off the curve and the identity.
+	let point = EncodedPoint::from_affine_coordinates(
+		FieldBytes::from_slice(&input[96..128]),
+		FieldBytes::from_slice(&input[128..160]),
+		false,
+	);
+	let key = match VerifyingKey::from_encoded_point(&point) {
+		Ok(key) => key,
+		Err(_) => return false,
+	};
+
+	key.verify_prehash(hash, &signature).is_ok()
+}
+
+impl Precompile for P256Verify {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(P256Verify::GAS_COST)?;
+
+		let output = if verify(handle.input()) {
+			let mut buf = [0u8; 32];
+			buf[31] = 1;
+			buf.to_vec()
+		} else {
+			Vec::new()
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+
+	#[test]
+	fn process_consensus_tests() -> Result<(), String> {
+		test_precompile_test_vectors::<P256Verify>("../testdata/p256Verify.json")?;
+		Ok(())
+	}
+}
@@ -0,0 +1,396 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ABI encoding of the Solidity types, see <https://docs.soliditylang.org/en/latest/abi-spec.html>.
+
+use core::ops::Range;
+use sp_core::{H160, H256, U256};
+use sp_std::vec::Vec;
+
+use crate::{revert, EvmResult};
+
+/// The `address` type, a 20 bytes value left padded to a word.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Address(pub H160);
+
+impl From<H160> for Address {
+	fn from(address: H160) -> Self {
+		Self(address)
+	}
+}
+
+impl From<Address> for H160 {
+	fn from(address: Address) -> Self {
+		address.0
+	}
+}
+
+/// The dynamic `bytes` and `string` types.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.0
+	}
+
+	pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+		core::str::from_utf8(&self.0)
+	}
+}
+
+impl From<&[u8]> for Bytes {
+	fn from(bytes: &[u8]) -> Self {
+		Self(bytes.to_vec())
+	}
+}
+
+impl From<&str> for Bytes {
+	fn from(string: &str) -> Self {
+		Self(string.as_bytes().to_vec())
+	}
+}
+
+impl From<Vec<u8>> for Bytes {
+	fn from(bytes: Vec<u8>) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<Bytes> for Vec<u8> {
+	fn from(bytes: Bytes) -> Self {
+		bytes.0
+	}
+}
+
+/// A type which can be read from and written to ABI encoded data.
+pub trait EvmData: Sized {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self>;
+
+	fn write(writer: &mut EvmDataWriter, value: Self);
+}
+
+/// Reads ABI encoded data, such as the arguments of a call.
+#[derive(Clone, Copy, Debug)]
+pub struct EvmDataReader<'a> {
+	input: &'a [u8],
+	cursor: usize,
+}
+
+impl<'a> EvmDataReader<'a> {
+	pub fn new(input: &'a [u8]) -> Self {
+		Self { input, cursor: 0 }
+	}
+
+	/// Creates a reader over the arguments of a call, skipping its 4 bytes selector.
+	pub fn new_skip_selector(input: &'a [u8]) -> EvmResult<Self> {
+		if input.len() < 4 {
+			return Err(revert("Tried to read selector out of bounds"));
+		}
+
+		Ok(Self::new(&input[4..]))
+	}
+
+	/// Reads the selector of a call and converts it to `T`.
+	pub fn read_selector<T: TryFrom<u32>>(input: &'a [u8]) -> EvmResult<T> {
+		if input.len() < 4 {
+			return Err(revert("Tried to read selector out of bounds"));
+		}
+
+		let selector = u32::from_be_bytes([input[0], input[1], input[2], input[3]]);
+		T::try_from(selector).map_err(|_| revert("Unknown selector"))
+	}
+
+	/// Checks there are at least `args` words left to read.
+	pub fn expect_arguments(&self, args: usize) -> EvmResult {
+		let remaining = self.input.len().saturating_sub(self.cursor);
+		if remaining / 32 < args {
+			return Err(revert("Expected more arguments"));
+		}
+
+		Ok(())
+	}
+
+	pub fn read<T: EvmData>(&mut self) -> EvmResult<T> {
+		T::read(self)
+	}
+
+	/// Reads `len` bytes as they are.
+	pub fn read_raw_bytes(&mut self, len: usize) -> EvmResult<&'a [u8]> {
+		let range = self.move_cursor(len)?;
+		Ok(&self.input[range])
+	}
+
+	/// Reads the offset of a dynamic value and returns a reader starting at the value. Offsets
+	/// are relative to the start of the data of this reader.
+	pub fn read_pointer(&mut self) -> EvmResult<Self> {
+		let offset: usize = self
+			.read::<U256>()
+			.map_err(|_| revert("Tried to read pointer out of bounds"))?
+			.try_into()
+			.map_err(|_| revert("Pointer is too large"))?;
+
+		if offset >= self.input.len() {
+			return Err(revert("Pointer points out of bounds"));
+		}
+
+		Ok(Self::new(&self.input[offset..]))
+	}
+
+	/// Returns a reader over the data left to read, with offsets relative to the cursor.
+	fn remaining(&self) -> Self {
+		Self::new(&self.input[self.cursor..])
+	}
+
+	fn move_cursor(&mut self, len: usize) -> EvmResult<Range<usize>> {
+		let start = self.cursor;
+		let end = start
+			.checked_add(len)
+			.ok_or_else(|| revert("Data reading overflow"))?;
+
+		if end > self.input.len() {
+			return Err(revert("Tried to read out of bounds"));
+		}
+
+		self.cursor = end;
+		Ok(start..end)
+	}
+
+	fn read_word(&mut self) -> EvmResult<&'a [u8]> {
+		self.read_raw_bytes(32)
+	}
+}
+
+/// Writes ABI encoded data, such as the output of a call.
+#[derive(Clone, Debug, Default)]
+pub struct EvmDataWriter {
+	data: Vec<u8>,
+	offset_data: Vec<OffsetDatum>,
+	selector: Option<u32>,
+}
+
+/// A dynamic value, written after the static part of the data once its offset is known.
+#[derive(Clone, Debug)]
+struct OffsetDatum {
+	/// Position of the offset word in the data.
+	offset_position: usize,
+	/// The value pointed by the offset.
+	data: Vec<u8>,
+	/// Subtracted from the offset, as the offsets of the elements of an array start after its
+	/// length.
+	offset_shift: usize,
+}
+
+impl EvmDataWriter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a writer whose data is prefixed by `selector`, to encode a call or a revert.
+	pub fn new_with_selector(selector: u32) -> Self {
+		Self {
+			selector: Some(selector),
+			..Default::default()
+		}
+	}
+
+	pub fn build(mut self) -> Vec<u8> {
+		Self::bake_offsets(&mut self.data, self.offset_data);
+
+		match self.selector {
+			Some(selector) => {
+				let mut output = selector.to_be_bytes().to_vec();
+				output.append(&mut self.data);
+				output
+			}
+			None => self.data,
+		}
+	}
+
+	pub fn write<T: EvmData>(mut self, value: T) -> Self {
+		T::write(&mut self, value);
+		self
+	}
+
+	/// Writes `value` as it is.
+	pub fn write_raw_bytes(mut self, value: &[u8]) -> Self {
+		self.data.extend_from_slice(value);
+		self
+	}
+
+	/// Fills the offset words and appends the dynamic values.
+	fn bake_offsets(output: &mut Vec<u8>, offsets: Vec<OffsetDatum>) {
+		for mut offset_datum in offsets {
+			let offset = output.len() - offset_datum.offset_shift;
+			let position = offset_datum.offset_position;
+			U256::from(offset).to_big_endian(&mut output[position..position + 32]);
+			output.append(&mut offset_datum.data);
+		}
+	}
+
+	/// Writes a placeholder for the offset of `data`, which is appended when building.
+	fn write_pointer(&mut self, data: Vec<u8>) {
+		let offset_position = self.data.len();
+		self.data.extend_from_slice(&[0u8; 32]);
+		self.offset_data.push(OffsetDatum {
+			offset_position,
+			data,
+			offset_shift: 0,
+		});
+	}
+}
+
+impl EvmData for U256 {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		Ok(U256::from_big_endian(reader.read_word()?))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		let mut buffer = [0u8; 32];
+		value.to_big_endian(&mut buffer);
+		writer.data.extend_from_slice(&buffer);
+	}
+}
+
+macro_rules! impl_evmdata_for_uints {
+	($($uint:ty),*) => {
+		$(
+			impl EvmData for $uint {
+				fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+					let word = reader.read_word()?;
+					let start = 32 - core::mem::size_of::<$uint>();
+					if word[..start].iter().any(|byte| *byte != 0) {
+						return Err(revert(concat!("Value is too large for ", stringify!($uint))));
+					}
+
+					let mut buffer = [0u8; core::mem::size_of::<$uint>()];
+					buffer.copy_from_slice(&word[start..]);
+					Ok(<$uint>::from_be_bytes(buffer))
+				}
+
+				fn write(writer: &mut EvmDataWriter, value: Self) {
+					let mut buffer = [0u8; 32];
+					let start = 32 - core::mem::size_of::<$uint>();
+					buffer[start..].copy_from_slice(&value.to_be_bytes());
+					writer.data.extend_from_slice(&buffer);
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_uints!(u8, u16, u32, u64, u128);
+
+impl EvmData for bool {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		match reader.read::<U256>()? {
+			value if value.is_zero() => Ok(false),
+			value if value == U256::one() => Ok(true),
+			_ => Err(revert("Value is too large for bool")),
+		}
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		U256::write(writer, if value { U256::one() } else { U256::zero() });
+	}
+}
+
+impl EvmData for H256 {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		Ok(H256::from_slice(reader.read_word()?))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		writer.data.extend_from_slice(value.as_bytes());
+	}
+}
+
+impl EvmData for Address {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		Ok(Address(H160::from_slice(&reader.read_word()?[12..])))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		H256::write(writer, value.0.into());
+	}
+}
+
+impl EvmData for Bytes {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		let mut inner_reader = reader.read_pointer()?;
+		let len: usize = inner_reader
+			.read::<U256>()?
+			.try_into()
+			.map_err(|_| revert("Bytes length is too large"))?;
+
+		Ok(Bytes(inner_reader.read_raw_bytes(len)?.to_vec()))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		let len = value.0.len();
+		// The bytes are right padded to a multiple of 32.
+		let padded_len = (len + 31) / 32 * 32;
+
+		let mut data = Vec::with_capacity(32 + padded_len);
+		let mut length_word = [0u8; 32];
+		U256::from(len).to_big_endian(&mut length_word);
+		data.extend_from_slice(&length_word);
+		data.extend_from_slice(&value.0);
+		data.resize(32 + padded_len, 0);
+
+		writer.write_pointer(data);
+	}
+}
+
+impl<T: EvmData> EvmData for Vec<T> {
+	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+		let mut inner_reader = reader.read_pointer()?;
+		let len: usize = inner_reader
+			.read::<U256>()?
+			.try_into()
+			.map_err(|_| revert("Array length is too large"))?;
+
+		// The offsets of dynamic elements are relative to the first element.
+		let mut elements_reader = inner_reader.remaining();
+		// Each element takes at least a word, which bounds the length before allocating.
+		elements_reader.expect_arguments(len)?;
+
+		let mut array = Vec::with_capacity(len);
+		for _ in 0..len {
+			array.push(elements_reader.read()?);
+		}
+
+		Ok(array)
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		let mut inner_writer = EvmDataWriter::new().write(U256::from(value.len()));
+		for element in value {
+			inner_writer = inner_writer.write(element);
+		}
+
+		// The offsets of dynamic elements are relative to the first element, after the length.
+		let mut offset_data = inner_writer.offset_data;
+		for offset_datum in &mut offset_data {
+			offset_datum.offset_shift += 32;
+		}
+
+		let mut data = inner_writer.data;
+		EvmDataWriter::bake_offsets(&mut data, offset_data);
+		writer.write_pointer(data);
+	}
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utils to write precompiles: ABI encoding of the inputs and outputs, Solidity reverts, logs,
+//! gas costs and a declarative way to build a precompile set.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod data;
+pub mod logs;
+pub mod precompile_set;
+pub mod revert;
+pub mod substrate;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use fp_evm::{PrecompileFailure, PrecompileHandle};
+
+pub use crate::{
+	data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter},
+	logs::{log_costs, Log, LogsBuilder},
+	precompile_set::PrecompileSetBuilder,
+	revert::revert,
+	substrate::RuntimeHelper,
+};
+
+/// Result of the precompile functions, failing with a revert or an error of the EVM.
+pub type EvmResult<T = ()> = Result<T, PrecompileFailure>;
+
+/// Computes the 4 bytes selector of a function from its signature, e.g. `transfer(address,uint256)`.
+pub fn keccak_selector(signature: &str) -> u32 {
+	let hash = sp_io::hashing::keccak_256(signature.as_bytes());
+	u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// State mutability of a Solidity function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FunctionModifier {
+	/// Doesn't modify the state and doesn't accept value.
+	View,
+	/// Modifies the state and doesn't accept value.
+	NonPayable,
+	/// Modifies the state and accepts value.
+	Payable,
+}
+
+/// Helpers on top of the handle given to the precompiles.
+pub trait PrecompileHandleExt: PrecompileHandle {
+	/// Records the cost of a log with `topics` topics and `data_len` bytes of data.
+	fn record_log_costs_manual(&mut self, topics: usize, data_len: usize) -> EvmResult;
+
+	/// Records the cost of reading one storage item of the runtime.
+	fn record_db_read<Runtime: pallet_evm::Config>(&mut self) -> EvmResult;
+
+	/// Records the cost of writing one storage item of the runtime.
+	fn record_db_write<Runtime: pallet_evm::Config>(&mut self) -> EvmResult;
+
+	/// Rejects the calls with value to non payable functions, and the calls to state modifying
+	/// functions in a static context.
+	fn check_function_modifier(&self, modifier: FunctionModifier) -> EvmResult;
+
+	/// Reads the selector of the input and converts it to `T`, usually an enum of the functions
+	/// of the precompile.
+	fn read_selector<S: TryFrom<u32>>(&self) -> EvmResult<S>;
+
+	/// Returns a reader over the arguments of the input, after its selector.
+	fn read_input(&self) -> EvmResult<EvmDataReader<'_>>;
+}
+
+impl<T: PrecompileHandle> PrecompileHandleExt for T {
+	fn record_log_costs_manual(&mut self, topics: usize, data_len: usize) -> EvmResult {
+		self.record_cost(log_costs(topics, data_len)?)?;
+		Ok(())
+	}
+
+	fn record_db_read<Runtime: pallet_evm::Config>(&mut self) -> EvmResult {
+		self.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(())
+	}
+
+	fn record_db_write<Runtime: pallet_evm::Config>(&mut self) -> EvmResult {
+		self.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		Ok(())
+	}
+
+	fn check_function_modifier(&self, modifier: FunctionModifier) -> EvmResult {
+		if modifier != FunctionModifier::Payable && !self.context().apparent_value.is_zero() {
+			return Err(revert("Function is not payable"));
+		}
+		if modifier != FunctionModifier::View && self.is_static() {
+			return Err(revert("Can't call non-static function in static context"));
+		}
+		Ok(())
+	}
+
+	fn read_selector<S: TryFrom<u32>>(&self) -> EvmResult<S> {
+		EvmDataReader::read_selector(self.input())
+	}
+
+	fn read_input(&self) -> EvmResult<EvmDataReader<'_>> {
+		EvmDataReader::new_skip_selector(self.input())
+	}
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Logs emitted by the precompiles and their cost.
+
+use fp_evm::{ExitError, PrecompileFailure, PrecompileHandle};
+use sp_core::{H160, H256};
+use sp_std::{vec, vec::Vec};
+
+use crate::{EvmResult, PrecompileHandleExt};
+
+/// Base cost of a log, see the `LOG0` to `LOG4` opcodes.
+const G_LOG: u64 = 375;
+/// Cost of each topic of a log.
+const G_LOGTOPIC: u64 = 375;
+/// Cost of each byte of the data of a log.
+const G_LOGDATA: u64 = 8;
+
+/// Cost of a log with `topics` topics and `data_len` bytes of data.
+pub fn log_costs(topics: usize, data_len: usize) -> EvmResult<u64> {
+	let out_of_gas = || PrecompileFailure::Error {
+		exit_status: ExitError::OutOfGas,
+	};
+
+	let topics_cost = G_LOGTOPIC
+		.checked_mul(topics as u64)
+		.ok_or_else(out_of_gas)?;
+	let data_cost = G_LOGDATA
+		.checked_mul(data_len as u64)
+		.ok_or_else(out_of_gas)?;
+
+	G_LOG
+		.checked_add(topics_cost)
+		.and_then(|cost| cost.checked_add(data_cost))
+		.ok_or_else(out_of_gas)
+}
+
+/// A log to emit through the handle of a precompile.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Log {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Vec<u8>,
+}
+
+impl Log {
+	/// Cost of the log, to record before it is emitted.
+	pub fn compute_cost(&self) -> EvmResult<u64> {
+		log_costs(self.topics.len(), self.data.len())
+	}
+
+	/// Records the cost of the log and emits it.
+	pub fn record(self, handle: &mut impl PrecompileHandle) -> EvmResult {
+		handle.record_log_costs_manual(self.topics.len(), self.data.len())?;
+		handle.log(self.address, self.topics, self.data)?;
+		Ok(())
+	}
+}
+
+/// Builds the logs emitted from `address`.
+#[derive(Clone, Copy, Debug)]
+pub struct LogsBuilder {
+	address: H160,
+}
+
+impl LogsBuilder {
+	pub fn new(address: H160) -> Self {
+		Self { address }
+	}
+
+	pub fn log0(&self, data: impl Into<Vec<u8>>) -> Log {
+		self.log(Vec::new(), data)
+	}
+
+	pub fn log1(&self, topic0: impl Into<H256>, data: impl Into<Vec<u8>>) -> Log {
+		self.log(vec![topic0.into()], data)
+	}
+
+	pub fn log2(
+		&self,
+		topic0: impl Into<H256>,
+		topic1: impl Into<H256>,
+		data: impl Into<Vec<u8>>,
+	) -> Log {
+		self.log(vec![topic0.into(), topic1.into()], data)
+	}
+
+	pub fn log3(
+		&self,
+		topic0: impl Into<H256>,
+		topic1: impl Into<H256>,
+		topic2: impl Into<H256>,
+		data: impl Into<Vec<u8>>,
+	) -> Log {
+		self.log(vec![topic0.into(), topic1.into(), topic2.into()], data)
+	}
+
+	pub fn log4(
+		&self,
+		topic0: impl Into<H256>,
+		topic1: impl Into<H256>,
+		topic2: impl Into<H256>,
+		topic3: impl Into<H256>,
+		data: impl Into<Vec<u8>>,
+	) -> Log {
+		self.log(
+			vec![topic0.into(), topic1.into(), topic2.into(), topic3.into()],
+			data,
+		)
+	}
+
+	fn log(&self, topics: Vec<H256>, data: impl Into<Vec<u8>>) -> Log {
+		Log {
+			address: self.address,
+			topics,
+			data: data.into(),
+		}
+	}
+}
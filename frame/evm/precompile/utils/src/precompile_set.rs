@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A declarative way to build a precompile set, as a tuple of fragments each matching some
+//! addresses to precompiles, along with the kinds of calls they accept:
+//!
+//! ```ignore
+//! pub type Precompiles<R> = PrecompileSetBuilder<
+//! 	R,
+//! 	(
+//! 		PrecompileAt<AddressU64<1>, ECRecover, (CallableByContract, AcceptDelegateCall)>,
+//! 		PrecompileAt<AddressU64<2048>, Erc20<R>>,
+//! 		PrecompileSetStartingWith<AssetPrefix, Assets<R>, CallableByContract>,
+//! 	),
+//! >;
+//! ```
+
+use core::marker::PhantomData;
+use fp_evm::{Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
+use frame_support::traits::Get;
+use sp_core::H160;
+use sp_std::{vec, vec::Vec};
+
+use crate::{revert, EvmResult, PrecompileHandleExt};
+
+/// The kinds of calls a precompile accepts. Implemented by the markers below and by tuples of
+/// them, where the first marker giving an answer wins. By default delegate calls are rejected,
+/// static calls are accepted and only externally owned accounts can call the precompile.
+pub trait PrecompileChecks {
+	fn accept_delegate_call() -> Option<bool> {
+		None
+	}
+
+	fn accept_static_call() -> Option<bool> {
+		None
+	}
+
+	fn callable_by_contract() -> Option<bool> {
+		None
+	}
+}
+
+/// Accepts `DELEGATECALL` and `CALLCODE`, which run the precompile in the context of the caller.
+/// Only safe for precompiles which don't depend on the address they run at.
+pub struct AcceptDelegateCall;
+
+impl PrecompileChecks for AcceptDelegateCall {
+	fn accept_delegate_call() -> Option<bool> {
+		Some(true)
+	}
+}
+
+/// Rejects `STATICCALL`, as well as any call made in a static context.
+pub struct RejectStaticCall;
+
+impl PrecompileChecks for RejectStaticCall {
+	fn accept_static_call() -> Option<bool> {
+		Some(false)
+	}
+}
+
+/// Accepts calls from smart contracts.
+pub struct CallableByContract;
+
+impl PrecompileChecks for CallableByContract {
+	fn callable_by_contract() -> Option<bool> {
+		Some(true)
+	}
+}
+
+macro_rules! impl_precompile_checks_for_tuples {
+	($($checks:ident),*) => {
+		impl<$($checks: PrecompileChecks),*> PrecompileChecks for ($($checks,)*) {
+			fn accept_delegate_call() -> Option<bool> {
+				None$(.or($checks::accept_delegate_call()))*
+			}
+
+			fn accept_static_call() -> Option<bool> {
+				None$(.or($checks::accept_static_call()))*
+			}
+
+			fn callable_by_contract() -> Option<bool> {
+				None$(.or($checks::callable_by_contract()))*
+			}
+		}
+	};
+}
+
+impl_precompile_checks_for_tuples!();
+impl_precompile_checks_for_tuples!(A);
+impl_precompile_checks_for_tuples!(A, B);
+impl_precompile_checks_for_tuples!(A, B, C);
+
+/// Rejects the calls `Checks` doesn't accept.
+fn check_call<R: pallet_evm::Config, Checks: PrecompileChecks>(
+	handle: &mut impl PrecompileHandle,
+) -> EvmResult {
+	if !Checks::accept_delegate_call().unwrap_or(false)
+		&& handle.code_address() != handle.context().address
+	{
+		return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+	}
+
+	if !Checks::accept_static_call().unwrap_or(true) && handle.is_static() {
+		return Err(revert("Cannot be called with STATICCALL"));
+	}
+
+	if !Checks::callable_by_contract().unwrap_or(false) {
+		// The code of the caller is stored by the runtime.
+		handle.record_db_read::<R>()?;
+		let caller = handle.context().caller;
+		if pallet_evm::AccountCodes::<R>::decode_len(caller).unwrap_or(0) > 0 {
+			return Err(revert("Cannot be called by smart contracts"));
+		}
+	}
+
+	Ok(())
+}
+
+/// A part of a [`PrecompileSetBuilder`].
+pub trait PrecompileSetFragment {
+	fn new() -> Self;
+
+	/// Executes the call when its code address belongs to the fragment.
+	fn execute<R: pallet_evm::Config>(
+		&self,
+		handle: &mut impl PrecompileHandle,
+	) -> Option<PrecompileResult>;
+
+	fn is_precompile(&self, address: H160) -> bool;
+
+	/// The addresses of the fragment, when they can be listed.
+	fn used_addresses(&self) -> Vec<H160>;
+}
+
+/// The address whose low 8 bytes are `N`, as used by the Ethereum precompiles.
+pub struct AddressU64<const N: u64>;
+
+impl<const N: u64> Get<H160> for AddressU64<N> {
+	fn get() -> H160 {
+		H160::from_low_u64_be(N)
+	}
+}
+
+/// The precompile `P` at the address `A`, accepting the calls allowed by `Checks`.
+pub struct PrecompileAt<A, P, Checks = ()>(PhantomData<(A, P, Checks)>);
+
+impl<A, P, Checks> PrecompileSetFragment for PrecompileAt<A, P, Checks>
+where
+	A: Get<H160>,
+	P: Precompile,
+	Checks: PrecompileChecks,
+{
+	fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	fn execute<R: pallet_evm::Config>(
+		&self,
+		handle: &mut impl PrecompileHandle,
+	) -> Option<PrecompileResult> {
+		if handle.code_address() != A::get() {
+			return None;
+		}
+
+		if let Err(failure) = check_call::<R, Checks>(handle) {
+			return Some(Err(failure));
+		}
+
+		Some(P::execute(handle))
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		address == A::get()
+	}
+
+	fn used_addresses(&self) -> Vec<H160> {
+		vec![A::get()]
+	}
+}
+
+/// The precompile set `P` over the range of addresses starting with `Prefix`, accepting the calls
+/// allowed by `Checks`. `P` decides which addresses of the range are precompiles, so they can't
+/// be listed.
+pub struct PrecompileSetStartingWith<Prefix, P, Checks = ()> {
+	precompile_set: P,
+	_marker: PhantomData<(Prefix, Checks)>,
+}
+
+impl<Prefix, P, Checks> PrecompileSetFragment for PrecompileSetStartingWith<Prefix, P, Checks>
+where
+	Prefix: Get<&'static [u8]>,
+	P: PrecompileSet + Default,
+	Checks: PrecompileChecks,
+{
+	fn new() -> Self {
+		Self {
+			precompile_set: P::default(),
+			_marker: PhantomData,
+		}
+	}
+
+	fn execute<R: pallet_evm::Config>(
+		&self,
+		handle: &mut impl PrecompileHandle,
+	) -> Option<PrecompileResult> {
+		if !self.is_precompile(handle.code_address()) {
+			return None;
+		}
+
+		if let Err(failure) = check_call::<R, Checks>(handle) {
+			return Some(Err(failure));
+		}
+
+		self.precompile_set.execute(handle)
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		address.as_bytes().starts_with(Prefix::get()) && self.precompile_set.is_precompile(address)
+	}
+
+	fn used_addresses(&self) -> Vec<H160> {
+		Vec::new()
+	}
+}
+
+macro_rules! impl_fragment_for_tuple {
+	($($fragment:ident),+) => {
+		impl<$($fragment: PrecompileSetFragment),+> PrecompileSetFragment for ($($fragment,)+) {
+			fn new() -> Self {
+				($($fragment::new(),)+)
+			}
+
+			#[allow(non_snake_case)]
+			fn execute<R: pallet_evm::Config>(
+				&self,
+				handle: &mut impl PrecompileHandle,
+			) -> Option<PrecompileResult> {
+				let ($($fragment,)+) = self;
+				$(
+					if let Some(result) = $fragment.execute::<R>(handle) {
+						return Some(result);
+					}
+				)+
+				None
+			}
+
+			#[allow(non_snake_case)]
+			fn is_precompile(&self, address: H160) -> bool {
+				let ($($fragment,)+) = self;
+				$($fragment.is_precompile(address))||+
+			}
+
+			#[allow(non_snake_case)]
+			fn used_addresses(&self) -> Vec<H160> {
+				let ($($fragment,)+) = self;
+				let mut addresses = Vec::new();
+				$(addresses.extend($fragment.used_addresses());)+
+				addresses
+			}
+		}
+	};
+}
+
+macro_rules! impl_fragment_for_tuples {
+	($first:ident $(, $rest:ident)*) => {
+		impl_fragment_for_tuple!($first $(, $rest)*);
+		impl_fragment_for_tuples!($($rest),*);
+	};
+	() => {};
+}
+
+impl_fragment_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// A precompile set made of the fragments `P`, usually a tuple. The fragments are tried in order.
+pub struct PrecompileSetBuilder<R, P> {
+	inner: P,
+	_marker: PhantomData<R>,
+}
+
+impl<R, P: PrecompileSetFragment> PrecompileSetBuilder<R, P> {
+	pub fn new() -> Self {
+		Self {
+			inner: P::new(),
+			_marker: PhantomData,
+		}
+	}
+
+	/// The addresses of the precompiles which can be listed.
+	pub fn used_addresses(&self) -> Vec<H160> {
+		self.inner.used_addresses()
+	}
+}
+
+impl<R, P: PrecompileSetFragment> Default for PrecompileSetBuilder<R, P> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<R, P> PrecompileSet for PrecompileSetBuilder<R, P>
+where
+	R: pallet_evm::Config,
+	P: PrecompileSetFragment,
+{
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		self.inner.execute::<R>(handle)
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		self.inner.is_precompile(address)
+	}
+}
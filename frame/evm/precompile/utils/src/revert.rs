@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Solidity reverts, whose reason is displayed by the tools.
+
+use fp_evm::{ExitRevert, PrecompileFailure};
+use sp_std::vec::Vec;
+
+use crate::{Bytes, EvmDataWriter};
+
+/// Selector of `Error(string)`, the revert reason of `require` and `revert` in Solidity.
+pub const ERROR_SELECTOR: u32 = 0x08c379a0;
+
+/// Reverts with `message` as reason.
+pub fn revert(message: impl AsRef<[u8]>) -> PrecompileFailure {
+	PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: encode_revert_message(message.as_ref()),
+	}
+}
+
+/// Encodes `message` the way Solidity encodes a revert reason.
+pub fn encode_revert_message(message: &[u8]) -> Vec<u8> {
+	EvmDataWriter::new_with_selector(ERROR_SELECTOR)
+		.write(Bytes(message.to_vec()))
+		.build()
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for the precompiles accessing the runtime.
+
+use alloc::format;
+use core::marker::PhantomData;
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+};
+use pallet_evm::GasWeightMapping;
+
+use crate::{revert, EvmResult};
+
+/// Runtime related helpers of the precompiles.
+pub struct RuntimeHelper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime: pallet_evm::Config> RuntimeHelper<Runtime> {
+	/// Gas cost of reading one storage item.
+	pub fn db_read_gas_cost() -> u64 {
+		Runtime::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+		)
+	}
+
+	/// Gas cost of writing one storage item.
+	pub fn db_write_gas_cost() -> u64 {
+		Runtime::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().writes(1),
+		)
+	}
+}
+
+impl<Runtime> RuntimeHelper<Runtime>
+where
+	Runtime: pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+{
+	/// Dispatches `call` from `origin`. The gas matching the weight of the call is recorded
+	/// beforehand, so a call exceeding the gas left is never dispatched.
+	pub fn try_dispatch<Call>(
+		handle: &mut impl PrecompileHandle,
+		origin: <Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: Call,
+	) -> EvmResult<PostDispatchInfo>
+	where
+		Runtime::RuntimeCall: From<Call>,
+	{
+		let call = Runtime::RuntimeCall::from(call);
+		let dispatch_info = call.get_dispatch_info();
+		handle.record_cost(Runtime::GasWeightMapping::weight_to_gas(
+			dispatch_info.weight,
+		))?;
+
+		call.dispatch(origin)
+			.map_err(|e| revert(format!("Dispatched call failed with error: {:?}", e.error)))
+	}
+}
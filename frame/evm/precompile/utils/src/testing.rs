@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to unit test precompiles: a mock handle, a harness calling a precompile with it and
+//! the configuration of a mock runtime with the EVM pallet.
+
+use core::{marker::PhantomData, str::FromStr};
+
+use frame_support::{traits::FindAuthor, weights::Weight, ConsensusEngineId};
+use sp_core::{H160, H256, U256};
+use sp_std::prelude::*;
+
+use fp_evm::{
+	Context, ExitError, ExitReason, Precompile, PrecompileHandle, PrecompileResult, Transfer,
+};
+use pallet_evm::FeeCalculator;
+
+use crate::{EvmData, EvmDataReader, Log};
+
+#[doc(hidden)]
+pub mod __reexports {
+	pub use frame_support;
+	pub use frame_system;
+	pub use pallet_balances;
+	pub use pallet_evm;
+	pub use pallet_timestamp;
+	pub use sp_core;
+	pub use sp_runtime;
+}
+
+/// Handle of a call to a precompile, recording the gas used and the logs.
+pub struct MockHandle {
+	pub input: Vec<u8>,
+	pub context: Context,
+	pub code_address: H160,
+	pub is_static: bool,
+	pub gas_used: u64,
+	pub logs: Vec<Log>,
+}
+
+impl MockHandle {
+	/// A call from `caller` to the precompile at `address`.
+	pub fn new(address: H160, caller: H160, input: Vec<u8>) -> Self {
+		Self {
+			input,
+			context: Context {
+				address,
+				caller,
+				apparent_value: U256::zero(),
+			},
+			code_address: address,
+			is_static: false,
+			gas_used: 0,
+			logs: Vec::new(),
+		}
+	}
+}
+
+impl PrecompileHandle for MockHandle {
+	fn call(
+		&mut self,
+		_: H160,
+		_: Option<Transfer>,
+		_: Vec<u8>,
+		_: Option<u64>,
+		_: bool,
+		_: &Context,
+	) -> (ExitReason, Vec<u8>) {
+		unimplemented!()
+	}
+
+	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.gas_used += cost;
+		Ok(())
+	}
+
+	fn record_external_cost(
+		&mut self,
+		_: Option<u64>,
+		_: Option<u64>,
+		_: Option<u64>,
+	) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
+	fn remaining_gas(&self) -> u64 {
+		unimplemented!()
+	}
+
+	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+		self.logs.push(Log {
+			address,
+			topics,
+			data,
+		});
+		Ok(())
+	}
+
+	fn code_address(&self) -> H160 {
+		self.code_address
+	}
+
+	fn input(&self) -> &[u8] {
+		&self.input
+	}
+
+	fn context(&self) -> &Context {
+		&self.context
+	}
+
+	fn is_static(&self) -> bool {
+		self.is_static
+	}
+
+	fn gas_limit(&self) -> Option<u64> {
+		None
+	}
+}
+
+/// Calls the precompile `P` deployed at a fixed address.
+pub struct PrecompileTester<P> {
+	address: H160,
+	_marker: PhantomData<P>,
+}
+
+impl<P: Precompile> PrecompileTester<P> {
+	pub fn new(address: H160) -> Self {
+		Self {
+			address,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Calls the precompile from `caller`, returning its result and the logs it emitted.
+	pub fn call(&self, caller: H160, input: Vec<u8>) -> (PrecompileResult, Vec<Log>) {
+		let mut handle = MockHandle::new(self.address, caller, input);
+		let result = P::execute(&mut handle);
+		(result, handle.logs)
+	}
+}
+
+/// Reads the output of a successful call, panicking if the call failed.
+pub fn read_output<T: EvmData>(result: PrecompileResult) -> T {
+	let output = result.expect("call succeeds").output;
+	EvmDataReader::new(&output).read().expect("output is valid")
+}
+
+pub struct FixedGasPrice;
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> (U256, Weight) {
+		// Return some meaningful gas price and weight
+		(1_000_000_000u128.into(), Weight::from_ref_time(7u64))
+	}
+}
+
+pub struct FindAuthorTruncated;
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(H160::from_str("1234500000000000000000000000000000000000").unwrap())
+	}
+}
+
+/// Implements the configuration of the `System`, `Balances`, `Timestamp` and `EVM` pallets for a
+/// mock runtime built with `construct_runtime!`. Accounts are `H160` and balances are `u64`.
+#[macro_export]
+macro_rules! impl_mock_runtime {
+	($runtime:ident) => {
+		use $crate::testing::__reexports::{
+			frame_support as __frame_support, frame_system as __frame_system,
+			pallet_balances as __pallet_balances, pallet_evm as __pallet_evm,
+			pallet_timestamp as __pallet_timestamp, sp_core as __sp_core,
+			sp_runtime as __sp_runtime,
+		};
+
+		__frame_support::parameter_types! {
+			pub const BlockHashCount: u64 = 250;
+			pub const MockDbWeight: __frame_support::weights::RuntimeDbWeight =
+				__frame_support::weights::RuntimeDbWeight {
+					read: 1_000_000,
+					write: 2_000_000,
+				};
+		}
+		impl __frame_system::Config for $runtime {
+			type BaseCallFilter = __frame_support::traits::Everything;
+			type BlockWeights = ();
+			type BlockLength = ();
+			type RuntimeOrigin = RuntimeOrigin;
+			type RuntimeCall = RuntimeCall;
+			type Index = u64;
+			type BlockNumber = u64;
+			type Hash = __sp_core::H256;
+			type Hashing = __sp_runtime::traits::BlakeTwo256;
+			type AccountId = __sp_core::H160;
+			type Lookup = __sp_runtime::traits::IdentityLookup<Self::AccountId>;
+			type Header = __sp_runtime::generic::Header<u64, __sp_runtime::traits::BlakeTwo256>;
+			type RuntimeEvent = RuntimeEvent;
+			type BlockHashCount = BlockHashCount;
+			type DbWeight = MockDbWeight;
+			type Version = ();
+			type PalletInfo = PalletInfo;
+			type AccountData = __pallet_balances::AccountData<u64>;
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type SystemWeightInfo = ();
+			type SS58Prefix = ();
+			type OnSetCode = ();
+			type MaxConsumers = __frame_support::traits::ConstU32<16>;
+		}
+
+		__frame_support::parameter_types! {
+			pub const ExistentialDeposit: u64 = 0;
+		}
+		impl __pallet_balances::Config for $runtime {
+			type Balance = u64;
+			type DustRemoval = ();
+			type RuntimeEvent = RuntimeEvent;
+			type ExistentialDeposit = ExistentialDeposit;
+			type AccountStore = System;
+			type WeightInfo = ();
+			type MaxLocks = ();
+			type MaxReserves = ();
+			type ReserveIdentifier = ();
+		}
+
+		__frame_support::parameter_types! {
+			pub const MinimumPeriod: u64 = 1000;
+		}
+		impl __pallet_timestamp::Config for $runtime {
+			type Moment = u64;
+			type OnTimestampSet = ();
+			type MinimumPeriod = MinimumPeriod;
+			type WeightInfo = ();
+		}
+
+		__frame_support::parameter_types! {
+			pub BlockGasLimit: __sp_core::U256 = __sp_core::U256::max_value();
+			pub WeightPerGas: __frame_support::weights::Weight =
+				__frame_support::weights::Weight::from_ref_time(20_000);
+		}
+		impl __pallet_evm::Config for $runtime {
+			type FeeCalculator = $crate::testing::FixedGasPrice;
+			type GasWeightMapping = __pallet_evm::FixedGasWeightMapping<Self>;
+			type WeightPerGas = WeightPerGas;
+
+			type BlockHashMapping = __pallet_evm::SubstrateBlockHashMapping<Self>;
+			type CallOrigin = __pallet_evm::EnsureAddressRoot<Self::AccountId>;
+
+			type WithdrawOrigin = __pallet_evm::EnsureAddressNever<Self::AccountId>;
+			type AddressMapping = __pallet_evm::IdentityAddressMapping;
+			type Currency = Balances;
+
+			type RuntimeEvent = RuntimeEvent;
+			type PrecompilesType = ();
+			type PrecompilesValue = ();
+			type ChainId = ();
+			type BlockGasLimit = BlockGasLimit;
+			type Runner = __pallet_evm::runner::stack::Runner<Self>;
+			type OnChargeTransaction = ();
+			type FindAuthor = $crate::testing::FindAuthorTruncated;
+			type Hardforks = ();
+		}
+	};
+}
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use super::*;
+use crate::{
+	mock::*,
+	precompile_set::{
+		AcceptDelegateCall, AddressU64, CallableByContract, PrecompileAt, RejectStaticCall,
+	},
+	testing::MockHandle,
+};
+
+use fp_evm::{ExitSucceed, Precompile, PrecompileOutput, PrecompileResult, PrecompileSet};
+use sp_core::{H160, H256, U256};
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap()
+		.into()
+}
+
+fn word(value: u64) -> Vec<u8> {
+	let mut word = [0u8; 32];
+	U256::from(value).to_big_endian(&mut word);
+	word.to_vec()
+}
+
+#[test]
+fn selectors_match_the_signatures() {
+	assert_eq!(keccak_selector("transfer(address,uint256)"), 0xa9059cbb);
+	assert_eq!(keccak_selector("Error(string)"), revert::ERROR_SELECTOR);
+}
+
+#[test]
+fn write_and_read_static_types() {
+	let address = Address(H160::repeat_byte(0x11));
+	let output = EvmDataWriter::new()
+		.write(address)
+		.write(U256::from(42))
+		.write(true)
+		.write(7u8)
+		.write(H256::repeat_byte(0x22))
+		.build();
+
+	assert_eq!(output.len(), 5 * 32);
+	assert_eq!(&output[0..12], &[0u8; 12]);
+	assert_eq!(&output[12..32], address.0.as_bytes());
+
+	let mut reader = EvmDataReader::new(&output);
+	assert_eq!(reader.read::<Address>().unwrap(), address);
+	assert_eq!(reader.read::<U256>().unwrap(), U256::from(42));
+	assert!(reader.read::<bool>().unwrap());
+	assert_eq!(reader.read::<u8>().unwrap(), 7);
+	assert_eq!(reader.read::<H256>().unwrap(), H256::repeat_byte(0x22));
+	assert_eq!(
+		reader.read::<U256>(),
+		Err(revert("Tried to read out of bounds"))
+	);
+}
+
+#[test]
+fn read_rejects_values_out_of_range() {
+	let input = word(256);
+	assert_eq!(
+		EvmDataReader::new(&input).read::<u8>(),
+		Err(revert("Value is too large for u8"))
+	);
+	assert_eq!(EvmDataReader::new(&input).read::<u16>(), Ok(256));
+
+	let input = word(2);
+	assert_eq!(
+		EvmDataReader::new(&input).read::<bool>(),
+		Err(revert("Value is too large for bool"))
+	);
+}
+
+#[test]
+fn write_bytes_as_solidity() {
+	let output = EvmDataWriter::new()
+		.write(U256::from(1))
+		.write(Bytes::from("abc"))
+		.build();
+
+	let mut padded = b"abc".to_vec();
+	padded.resize(32, 0);
+	assert_eq!(output, [word(1), word(64), word(3), padded].concat());
+
+	let mut reader = EvmDataReader::new(&output);
+	assert_eq!(reader.read::<U256>().unwrap(), U256::from(1));
+	assert_eq!(reader.read::<Bytes>().unwrap().as_str(), Ok("abc"));
+}
+
+#[test]
+fn write_and_read_nested_arrays() {
+	let value = vec![Bytes::from("a"), Bytes::from(&[0xffu8; 33][..])];
+	let output = EvmDataWriter::new().write(value.clone()).build();
+
+	// Offsets of the elements are relative to the first element, after the length of the array.
+	assert_eq!(&output[0..32], &word(32)[..]);
+	assert_eq!(&output[32..64], &word(2)[..]);
+	assert_eq!(&output[64..96], &word(64)[..]);
+	assert_eq!(&output[96..128], &word(128)[..]);
+
+	let mut reader = EvmDataReader::new(&output);
+	assert_eq!(reader.read::<Vec<Bytes>>().unwrap(), value);
+}
+
+#[test]
+fn read_rejects_lengths_over_the_input() {
+	let input = [word(32), word(1_000_000)].concat();
+	assert_eq!(
+		EvmDataReader::new(&input).read::<Vec<U256>>(),
+		Err(revert("Expected more arguments"))
+	);
+	assert_eq!(
+		EvmDataReader::new(&input).read::<Bytes>(),
+		Err(revert("Tried to read out of bounds"))
+	);
+}
+
+#[test]
+fn revert_encodes_the_reason() {
+	let mut padded = b"abc".to_vec();
+	padded.resize(32, 0);
+
+	assert_eq!(
+		revert("abc"),
+		PrecompileFailure::Revert {
+			exit_status: fp_evm::ExitRevert::Reverted,
+			output: [vec![0x08, 0xc3, 0x79, 0xa0], word(32), word(3), padded].concat(),
+		}
+	);
+}
+
+#[test]
+fn selectors_are_read_from_the_input() {
+	let handle = MockHandle::new(
+		H160::repeat_byte(1),
+		H160::repeat_byte(2),
+		EvmDataWriter::new_with_selector(0xa9059cbb)
+			.write(U256::from(5))
+			.build(),
+	);
+
+	assert_eq!(handle.read_selector::<u32>(), Ok(0xa9059cbb));
+	assert_eq!(
+		handle.read_input().unwrap().read::<U256>(),
+		Ok(U256::from(5))
+	);
+
+	let handle = MockHandle::new(H160::repeat_byte(1), H160::repeat_byte(2), vec![0xa9]);
+	assert_eq!(
+		handle.read_selector::<u32>(),
+		Err(revert("Tried to read selector out of bounds"))
+	);
+}
+
+#[test]
+fn function_modifiers_are_checked() {
+	let mut handle = MockHandle::new(H160::repeat_byte(1), H160::repeat_byte(2), Vec::new());
+	handle.context.apparent_value = U256::one();
+	assert_eq!(
+		handle.check_function_modifier(FunctionModifier::NonPayable),
+		Err(revert("Function is not payable"))
+	);
+	assert_eq!(
+		handle.check_function_modifier(FunctionModifier::Payable),
+		Ok(())
+	);
+
+	handle.context.apparent_value = U256::zero();
+	handle.is_static = true;
+	assert_eq!(
+		handle.check_function_modifier(FunctionModifier::NonPayable),
+		Err(revert("Can't call non-static function in static context"))
+	);
+	assert_eq!(
+		handle.check_function_modifier(FunctionModifier::View),
+		Ok(())
+	);
+}
+
+#[test]
+fn logs_record_their_cost() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(1);
+		let mut handle = MockHandle::new(address, H160::repeat_byte(2), Vec::new());
+
+		let log = LogsBuilder::new(address).log2(
+			H256::repeat_byte(3),
+			H160::repeat_byte(4),
+			EvmDataWriter::new().write(U256::from(5)).build(),
+		);
+		assert_eq!(log.compute_cost(), Ok(375 + 2 * 375 + 32 * 8));
+		log.clone().record(&mut handle).unwrap();
+
+		assert_eq!(handle.gas_used, 375 + 2 * 375 + 32 * 8);
+		assert_eq!(handle.logs, vec![log]);
+
+		handle.record_db_read::<Test>().unwrap();
+		handle.record_db_write::<Test>().unwrap();
+		assert_eq!(handle.gas_used, 375 + 2 * 375 + 32 * 8 + 50 + 100);
+	});
+}
+
+struct Echo;
+
+impl Precompile for Echo {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: handle.input().to_vec(),
+		})
+	}
+}
+
+type TestPrecompiles = PrecompileSetBuilder<
+	Test,
+	(
+		PrecompileAt<AddressU64<1>, Echo, (CallableByContract, AcceptDelegateCall)>,
+		PrecompileAt<AddressU64<2>, Echo>,
+		PrecompileAt<AddressU64<3>, Echo, (CallableByContract, RejectStaticCall)>,
+	),
+>;
+
+fn address(n: u64) -> H160 {
+	H160::from_low_u64_be(n)
+}
+
+#[test]
+fn precompile_set_matches_the_addresses() {
+	new_test_ext().execute_with(|| {
+		let precompiles = TestPrecompiles::new();
+		assert_eq!(
+			precompiles.used_addresses(),
+			vec![address(1), address(2), address(3)]
+		);
+		assert!(precompiles.is_precompile(address(2)));
+		assert!(!precompiles.is_precompile(address(4)));
+
+		let mut handle = MockHandle::new(address(1), H160::repeat_byte(0x10), vec![1, 2, 3]);
+		assert_eq!(
+			precompiles.execute(&mut handle).unwrap().unwrap().output,
+			vec![1, 2, 3]
+		);
+
+		let mut handle = MockHandle::new(address(4), H160::repeat_byte(0x10), vec![1, 2, 3]);
+		assert!(precompiles.execute(&mut handle).is_none());
+	});
+}
+
+#[test]
+fn precompile_set_checks_delegate_calls() {
+	new_test_ext().execute_with(|| {
+		let precompiles = TestPrecompiles::new();
+
+		// Delegate calls run the precompile in the context of the calling contract.
+		let mut handle = MockHandle::new(address(2), H160::repeat_byte(0x10), Vec::new());
+		handle.context.address = H160::repeat_byte(0x20);
+		assert_eq!(
+			precompiles.execute(&mut handle),
+			Some(Err(revert(
+				"Cannot be called with DELEGATECALL or CALLCODE"
+			)))
+		);
+
+		let mut handle = MockHandle::new(address(1), H160::repeat_byte(0x10), Vec::new());
+		handle.context.address = H160::repeat_byte(0x20);
+		assert!(precompiles.execute(&mut handle).unwrap().is_ok());
+	});
+}
+
+#[test]
+fn precompile_set_checks_static_calls() {
+	new_test_ext().execute_with(|| {
+		let precompiles = TestPrecompiles::new();
+
+		let mut handle = MockHandle::new(address(3), H160::repeat_byte(0x10), Vec::new());
+		handle.is_static = true;
+		assert_eq!(
+			precompiles.execute(&mut handle),
+			Some(Err(revert("Cannot be called with STATICCALL")))
+		);
+
+		let mut handle = MockHandle::new(address(1), H160::repeat_byte(0x10), Vec::new());
+		handle.is_static = true;
+		assert!(precompiles.execute(&mut handle).unwrap().is_ok());
+	});
+}
+
+#[test]
+fn precompile_set_checks_contract_callers() {
+	new_test_ext().execute_with(|| {
+		let precompiles = TestPrecompiles::new();
+		let contract = H160::repeat_byte(0x10);
+		pallet_evm::AccountCodes::<Test>::insert(contract, vec![0x00]);
+
+		let mut handle = MockHandle::new(address(2), contract, Vec::new());
+		assert_eq!(
+			precompiles.execute(&mut handle),
+			Some(Err(revert("Cannot be called by smart contracts")))
+		);
+		// Reading the code of the caller is charged.
+		assert_eq!(handle.gas_used, 50);
+
+		let mut handle = MockHandle::new(address(2), H160::repeat_byte(0x11), Vec::new());
+		assert!(precompiles.execute(&mut handle).unwrap().is_ok());
+
+		let mut handle = MockHandle::new(address(1), contract, Vec::new());
+		assert!(precompiles.execute(&mut handle).unwrap().is_ok());
+		assert_eq!(handle.gas_used, 0);
+	});
+}
@@ -48,7 +48,10 @@
 //! the exact same transaction / receipt format. However, given one Ethereum transaction and one Substrate account's
 //! private key, one should be able to convert any Ethereum transaction into a transaction compatible with this pallet.
 //!
-//! The gas configurations are configurable. Right now, a pre-defined London hard fork configuration option is provided.
+//! The gas configurations are configurable. The runtime schedules the hardforks of its chain with `Config::Hardforks`,
+//! activated at a block number or a timestamp, and each block runs with the config of its latest activated hardfork.
+//! The Frontier, Istanbul, Berlin and London configs of the underlying EVM engine are provided, London being the
+//! default.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -75,7 +78,7 @@ use frame_support::{
 use frame_system::RawOrigin;
 use sp_core::{Hasher, H160, H256, U256};
 use sp_runtime::{
-	traits::{BadOrigin, Saturating, UniqueSaturatedInto, Zero},
+	traits::{BadOrigin, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
 	AccountId32, DispatchErrorWithPostInfo,
 };
 use sp_std::{cmp::min, vec::Vec};
@@ -86,9 +89,9 @@ pub use evm::{
 #[cfg(feature = "std")]
 use fp_evm::GenesisAccount;
 pub use fp_evm::{
-	Account, CallInfo, CreateInfo, ExecutionInfo, FeeCalculator, InvalidEvmTransactionError,
-	LinearCostPrecompile, Log, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
-	PrecompileResult, PrecompileSet, Vicinity,
+	Account, AccountOverride, BlockOverride, CallInfo, CreateInfo, ExecutionInfo, FeeCalculator,
+	InvalidEvmTransactionError, LinearCostPrecompile, Log, Precompile, PrecompileFailure,
+	PrecompileHandle, PrecompileOutput, PrecompileResult, PrecompileSet, Vicinity,
 };
 
 pub use self::{
@@ -151,9 +154,15 @@ pub mod pallet {
 		/// Find author for the current block.
 		type FindAuthor: FindAuthor<H160>;
 
-		/// EVM config used in the module.
+		/// Hardforks of the EVM. A block runs with the config of the latest of its active
+		/// hardforks in the order of `EvmHardfork`, whatever their order in the schedule. Blocks
+		/// before the first activation, or every block when the schedule is empty, run with the
+		/// London config.
+		type Hardforks: Get<&'static [(HardforkActivation, EvmHardfork)]>;
+
+		/// EVM config used in the module, the one of the current block by default.
 		fn config() -> &'static EvmConfig {
-			&LONDON_CONFIG
+			Pallet::<Self>::hardfork_config()
 		}
 	}
 
@@ -660,9 +669,73 @@ impl<T: Config> GasWeightMapping for FixedGasWeightMapping<T> {
 	}
 }
 
+environmental::environmental!(block_override: BlockOverride);
+
+static FRONTIER_CONFIG: EvmConfig = EvmConfig::frontier();
+static ISTANBUL_CONFIG: EvmConfig = EvmConfig::istanbul();
+static BERLIN_CONFIG: EvmConfig = EvmConfig::berlin();
 static LONDON_CONFIG: EvmConfig = EvmConfig::london();
+static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
+static CANCUN_CONFIG: EvmConfig = EvmConfig::cancun();
+
+/// Ethereum hardfork whose EVM config is provided by this pallet, ordered as on Ethereum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum EvmHardfork {
+	Frontier,
+	Istanbul,
+	Berlin,
+	London,
+	Shanghai,
+	Cancun,
+}
+
+impl EvmHardfork {
+	/// EVM config of the hardfork.
+	pub fn config(&self) -> &'static EvmConfig {
+		match self {
+			EvmHardfork::Frontier => &FRONTIER_CONFIG,
+			EvmHardfork::Istanbul => &ISTANBUL_CONFIG,
+			EvmHardfork::Berlin => &BERLIN_CONFIG,
+			EvmHardfork::London => &LONDON_CONFIG,
+			EvmHardfork::Shanghai => &SHANGHAI_CONFIG,
+			EvmHardfork::Cancun => &CANCUN_CONFIG,
+		}
+	}
+}
+
+/// First block of a hardfork.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HardforkActivation {
+	/// Active from this block number.
+	Block(u64),
+	/// Active from the first block whose timestamp, in the unit of `pallet_timestamp`, is at
+	/// least this one.
+	Timestamp(u64),
+}
+
+impl HardforkActivation {
+	/// Whether a block with `number` and `timestamp` is at or after the activation.
+	pub fn is_active(&self, number: u64, timestamp: u64) -> bool {
+		match *self {
+			HardforkActivation::Block(activation) => number >= activation,
+			HardforkActivation::Timestamp(activation) => timestamp >= activation,
+		}
+	}
+}
 
 impl<T: Config> Pallet<T> {
+	/// EVM config of the current block, the one of its latest activated hardfork.
+	pub fn hardfork_config() -> &'static EvmConfig {
+		let number: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+		let timestamp: u64 = pallet_timestamp::Pallet::<T>::get().unique_saturated_into();
+		T::Hardforks::get()
+			.iter()
+			.filter(|(activation, _)| activation.is_active(number, timestamp))
+			.map(|(_, hardfork)| hardfork)
+			.max()
+			.map_or(&LONDON_CONFIG, EvmHardfork::config)
+	}
+
 	/// Check whether an account is empty.
 	pub fn is_account_empty(address: &H160) -> bool {
 		let (account, _) = Self::account_basic(address);
@@ -721,8 +794,39 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
-	/// Get the author using the FindAuthor trait.
+	/// Run `f`, a non-transactional execution, in the environment of the current block replaced
+	/// by `block_override`.
+	///
+	/// The block number and timestamp are written to storage, so this must only be used when the
+	/// storage changes are discarded, such as in runtime API calls. The base fee and author are
+	/// only seen by executions made within `f`.
+	pub fn with_block_override<R>(mut block_override: BlockOverride, f: impl FnOnce() -> R) -> R {
+		if let Some(number) = block_override.number {
+			frame_system::Pallet::<T>::set_block_number(number.unique_saturated_into());
+		}
+		if let Some(timestamp) = block_override.timestamp {
+			pallet_timestamp::Now::<T>::put(T::Moment::unique_saturated_from(
+				timestamp.saturating_mul(1000),
+			));
+		}
+		block_override::using(&mut block_override, f)
+	}
+
+	/// Base fee of the current block, from the fee calculator unless overridden.
+	pub fn base_fee() -> (U256, Weight) {
+		match block_override::with(|block_override| block_override.base_fee).flatten() {
+			Some(base_fee) => (base_fee, Weight::zero()),
+			None => T::FeeCalculator::min_gas_price(),
+		}
+	}
+
+	/// Get the author using the FindAuthor trait, unless overridden.
 	pub fn find_author() -> H160 {
+		if let Some(coinbase) =
+			block_override::with(|block_override| block_override.coinbase).flatten()
+		{
+			return coinbase;
+		}
 		let digest = <frame_system::Pallet<T>>::digest();
 		let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
 
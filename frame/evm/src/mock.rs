@@ -32,8 +32,8 @@ use sp_runtime::{
 use sp_std::{boxed::Box, prelude::*, str::FromStr};
 
 use crate::{
-	EnsureAddressNever, EnsureAddressRoot, FeeCalculator, IdentityAddressMapping, PrecompileHandle,
-	PrecompileResult, PrecompileSet,
+	EnsureAddressNever, EnsureAddressRoot, EvmHardfork, FeeCalculator, HardforkActivation,
+	IdentityAddressMapping, PrecompileHandle, PrecompileResult, PrecompileSet,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -130,6 +130,7 @@ parameter_types! {
 	pub BlockGasLimit: U256 = U256::max_value();
 	pub WeightPerGas: Weight = Weight::from_ref_time(20_000);
 	pub MockPrecompiles: MockPrecompileSet = MockPrecompileSet;
+	pub static MockHardforks: &'static [(HardforkActivation, EvmHardfork)] = &[];
 }
 impl crate::Config for Test {
 	type FeeCalculator = FixedGasPrice;
@@ -151,6 +152,7 @@ impl crate::Config for Test {
 	type Runner = crate::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type FindAuthor = FindAuthorTruncated;
+	type Hardforks = MockHardforks;
 }
 
 /// Exemple PrecompileSet with only Identity precompile.
@@ -19,15 +19,14 @@
 
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountStorages, AddressMapping, BalanceOf,
-	BlockHashMapping, Config, Error, Event, FeeCalculator, OnChargeEVMTransaction, Pallet,
-	RunnerError,
+	BlockHashMapping, Config, Error, Event, OnChargeEVMTransaction, Pallet, RunnerError,
 };
 use evm::{
 	backend::Backend as BackendT,
 	executor::stack::{Accessed, StackExecutor, StackState as StackStateT, StackSubstateMetadata},
 	ExitError, ExitReason, Transfer,
 };
-use fp_evm::{CallInfo, CreateInfo, ExecutionInfo, Log, PrecompileSet, Vicinity};
+use fp_evm::{AccountOverride, CallInfo, CreateInfo, ExecutionInfo, Log, PrecompileSet, Vicinity};
 use frame_support::traits::{Currency, ExistenceRequirement, Get};
 use sp_core::{H160, H256, U256};
 use sp_runtime::traits::UniqueSaturatedInto;
@@ -51,6 +50,19 @@ impl<T: Config> Runner<T>
 where
 	BalanceOf<T>: TryFrom<U256> + Into<U256>,
 {
+	/// Apply the `eth_call` state overrides, before a non-transactional execution.
+	pub fn apply_state_override(state_override: Vec<(H160, AccountOverride)>) {
+		let vicinity = Vicinity {
+			gas_price: U256::zero(),
+			origin: H160::zero(),
+		};
+		let metadata = StackSubstateMetadata::new(0, T::config());
+		let mut state = SubstrateStackState::<T>::new(&vicinity, metadata);
+		for (address, account_override) in state_override {
+			state.apply_override(address, account_override);
+		}
+	}
+
 	#[allow(clippy::let_and_return)]
 	/// Execute an already validated EVM operation.
 	fn execute<'config, 'precompiles, F, R>(
@@ -74,7 +86,7 @@ where
 			>,
 		) -> (ExitReason, R),
 	{
-		let (base_fee, weight) = T::FeeCalculator::min_gas_price();
+		let (base_fee, weight) = Pallet::<T>::base_fee();
 
 		#[cfg(feature = "forbid-evm-reentrancy")]
 		if IN_EVM.with(|in_evm| in_evm.replace(true)) {
@@ -281,6 +293,111 @@ where
 			logs: state.substate.logs,
 		})
 	}
+
+	/// Execute a non-transactional call, or a create if `target` is `None`, and return the
+	/// EIP-2930 access list of the addresses and storage keys it accessed.
+	///
+	/// The source, the target and the precompiles are warm anyway, so they are only part of
+	/// the access list when some of their storage keys were accessed.
+	pub fn create_access_list(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		validate: bool,
+		config: &evm::Config,
+	) -> Result<(CallInfo, Vec<(H160, Vec<H256>)>), RunnerError<Error<T>>> {
+		let is_transactional = false;
+		if validate {
+			Self::validate(
+				source,
+				target,
+				input.clone(),
+				value,
+				gas_limit,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.clone(),
+				is_transactional,
+				config,
+			)?;
+		}
+		let precompiles = T::PrecompilesValue::get();
+		let info = Self::execute(
+			source,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			config,
+			&precompiles,
+			is_transactional,
+			|executor| {
+				let (reason, output, target) = match target {
+					Some(target) => {
+						let (reason, output) = executor.transact_call(
+							source,
+							target,
+							value,
+							input,
+							gas_limit,
+							access_list,
+						);
+						(reason, output, target)
+					}
+					None => {
+						let address =
+							executor.create_address(evm::CreateScheme::Legacy { caller: source });
+						let (reason, _) =
+							executor.transact_create(source, value, input, gas_limit, access_list);
+						(reason, Vec::new(), address)
+					}
+				};
+				let access_list = executor
+					.state()
+					.metadata()
+					.accessed()
+					.as_ref()
+					.map(|accessed| Self::access_list_of(accessed, &[source, target], &precompiles))
+					.unwrap_or_default();
+				(reason, (output, access_list))
+			},
+		)?;
+
+		let (output, access_list) = info.value;
+		Ok((
+			CallInfo {
+				exit_reason: info.exit_reason,
+				value: output,
+				used_gas: info.used_gas,
+				logs: info.logs,
+			},
+			access_list,
+		))
+	}
+
+	fn access_list_of(
+		accessed: &Accessed,
+		warm_addresses: &[H160],
+		precompiles: &T::PrecompilesType,
+	) -> Vec<(H160, Vec<H256>)> {
+		let mut access_list: BTreeMap<H160, Vec<H256>> = BTreeMap::new();
+		for (address, key) in accessed.accessed_storage.iter() {
+			access_list.entry(*address).or_default().push(*key);
+		}
+		for address in accessed.accessed_addresses.iter() {
+			if !warm_addresses.contains(address) && !precompiles.is_precompile(*address) {
+				access_list.entry(*address).or_default();
+			}
+		}
+		access_list.into_iter().collect()
+	}
 }
 
 impl<T: Config> RunnerT<T> for Runner<T>
@@ -302,7 +419,7 @@ where
 		is_transactional: bool,
 		evm_config: &evm::Config,
 	) -> Result<(), RunnerError<Self::Error>> {
-		let (base_fee, mut weight) = T::FeeCalculator::min_gas_price();
+		let (base_fee, mut weight) = Pallet::<T>::base_fee();
 		let (source_account, inner_weight) = Pallet::<T>::account_basic(&source);
 		weight = weight.saturating_add(inner_weight);
 
@@ -481,6 +598,8 @@ where
 struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
+	creates: BTreeSet<H160>,
+	transient_storage: BTreeMap<(H160, H256), H256>,
 	logs: Vec<Log>,
 	parent: Option<Box<SubstrateStackSubstate<'config>>>,
 }
@@ -499,6 +618,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 			metadata: self.metadata.spit_child(gas_limit, is_static),
 			parent: None,
 			deletes: BTreeSet::new(),
+			creates: BTreeSet::new(),
+			transient_storage: BTreeMap::new(),
 			logs: Vec::new(),
 		};
 		mem::swap(&mut entering, self);
@@ -515,6 +636,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.metadata.swallow_commit(exited.metadata)?;
 		self.logs.append(&mut exited.logs);
 		self.deletes.append(&mut exited.deletes);
+		self.creates.append(&mut exited.creates);
+		self.transient_storage.append(&mut exited.transient_storage);
 
 		sp_io::storage::commit_transaction();
 		Ok(())
@@ -554,6 +677,36 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.deletes.insert(address);
 	}
 
+	pub fn created(&self, address: H160) -> bool {
+		if self.creates.contains(&address) {
+			return true;
+		}
+
+		if let Some(parent) = self.parent.as_ref() {
+			return parent.created(address);
+		}
+
+		false
+	}
+
+	pub fn set_created(&mut self, address: H160) {
+		self.creates.insert(address);
+	}
+
+	pub fn known_transient_storage(&self, address: H160, index: H256) -> Option<H256> {
+		if let Some(value) = self.transient_storage.get(&(address, index)) {
+			return Some(*value);
+		}
+
+		self.parent
+			.as_ref()
+			.and_then(|parent| parent.known_transient_storage(address, index))
+	}
+
+	pub fn set_transient_storage(&mut self, address: H160, index: H256, value: H256) {
+		self.transient_storage.insert((address, index), value);
+	}
+
 	pub fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
 		self.logs.push(Log {
 			address,
@@ -591,6 +744,8 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 			substate: SubstrateStackSubstate {
 				metadata,
 				deletes: BTreeSet::new(),
+				creates: BTreeSet::new(),
+				transient_storage: BTreeMap::new(),
 				logs: Vec::new(),
 				parent: None,
 			},
@@ -598,6 +753,50 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 			original_storage: BTreeMap::new(),
 		}
 	}
+
+	/// Replace the stored state of `address` with `account_override`.
+	///
+	/// Overrides are written to storage, so this must only be used before non-transactional
+	/// executions whose storage changes are discarded, such as runtime API calls.
+	pub fn apply_override(&mut self, address: H160, account_override: AccountOverride)
+	where
+		BalanceOf<T>: TryFrom<U256> + Into<U256>,
+	{
+		let account_id = T::AddressMapping::into_account_id(address);
+
+		if let Some(balance) = account_override.balance {
+			let _ = T::Currency::make_free_balance_be(&account_id, balance.unique_saturated_into());
+		}
+		if let Some(nonce) = account_override.nonce {
+			let nonce: u128 = nonce.unique_saturated_into();
+			frame_system::Account::<T>::mutate(&account_id, |account| {
+				account.nonce = nonce.unique_saturated_into();
+			});
+		}
+		if let Some(code) = account_override.code {
+			if code.is_empty() {
+				if <AccountCodes<T>>::contains_key(address) {
+					let _ = frame_system::Pallet::<T>::dec_sufficients(&account_id);
+				}
+				<AccountCodes<T>>::remove(address);
+			} else {
+				self.set_code(address, code);
+			}
+		}
+		if let Some(state) = account_override.state {
+			self.reset_storage(address);
+			for (index, value) in state {
+				self.set_storage(address, index, value);
+			}
+		}
+		if let Some(state_diff) = account_override.state_diff {
+			for (index, value) in state_diff {
+				self.set_storage(address, index, value);
+			}
+		}
+		// Overridden values are the original values of the execution.
+		self.original_storage.clear();
+	}
 }
 
 impl<'vicinity, 'config, T: Config> BackendT for SubstrateStackState<'vicinity, 'config, T> {
@@ -634,6 +833,10 @@ impl<'vicinity, 'config, T: Config> BackendT for SubstrateStackState<'vicinity,
 		U256::zero()
 	}
 
+	fn block_randomness(&self) -> Option<H256> {
+		None
+	}
+
 	fn block_gas_limit(&self) -> U256 {
 		T::BlockGasLimit::get()
 	}
@@ -663,6 +866,14 @@ impl<'vicinity, 'config, T: Config> BackendT for SubstrateStackState<'vicinity,
 		<AccountStorages<T>>::get(address, index)
 	}
 
+	fn transient_storage(&self, address: H160, index: H256) -> H256 {
+		// Transient storage only lives in the substates and is dropped with them, so that it
+		// is reverted with the call frame and discarded at the end of the transaction.
+		self.substate
+			.known_transient_storage(address, index)
+			.unwrap_or_default()
+	}
+
 	fn original_storage(&self, address: H160, index: H256) -> Option<H256> {
 		// Not being cached means that it was never changed, which means we
 		// can fetch it from storage.
@@ -675,7 +886,7 @@ impl<'vicinity, 'config, T: Config> BackendT for SubstrateStackState<'vicinity,
 	}
 
 	fn block_base_fee_per_gas(&self) -> sp_core::U256 {
-		let (base_fee, _) = T::FeeCalculator::min_gas_price();
+		let (base_fee, _) = Pallet::<T>::base_fee();
 		base_fee
 	}
 }
@@ -717,9 +928,18 @@ where
 		self.substate.deleted(address)
 	}
 
-	fn inc_nonce(&mut self, address: H160) {
+	fn created(&self, address: H160) -> bool {
+		self.substate.created(address)
+	}
+
+	fn inc_nonce(&mut self, address: H160) -> Result<(), ExitError> {
 		let account_id = T::AddressMapping::into_account_id(address);
 		frame_system::Pallet::<T>::inc_account_nonce(&account_id);
+		Ok(())
+	}
+
+	fn set_transient_storage(&mut self, address: H160, index: H256, value: H256) {
+		self.substate.set_transient_storage(address, index, value)
 	}
 
 	fn set_storage(&mut self, address: H160, index: H256, value: H256) {
@@ -768,6 +988,10 @@ where
 		self.substate.set_deleted(address)
 	}
 
+	fn set_created(&mut self, address: H160) {
+		self.substate.set_created(address)
+	}
+
 	fn set_code(&mut self, address: H160, code: Vec<u8>) {
 		log::debug!(
 			target: "evm",
@@ -611,3 +611,257 @@ fn eip3607_transaction_from_precompile_should_fail() {
 		}
 	});
 }
+
+#[test]
+fn runner_state_override_replaces_account_state() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let slot = |n: u64| H256::from_low_u64_be(n);
+		<AccountStorages<Test>>::insert(address, slot(1), slot(10));
+		<AccountStorages<Test>>::insert(address, slot(2), slot(20));
+
+		crate::runner::stack::Runner::<Test>::apply_state_override(vec![(
+			address,
+			AccountOverride {
+				balance: Some(U256::from(500)),
+				nonce: Some(U256::from(7)),
+				code: Some(vec![0x60, 0x00]),
+				state: Some(vec![(slot(3), slot(30))]),
+				state_diff: None,
+			},
+		)]);
+
+		assert_eq!(Balances::free_balance(&address), 500);
+		assert_eq!(frame_system::Pallet::<Test>::account_nonce(&address), 7);
+		assert_eq!(<AccountCodes<Test>>::get(address), vec![0x60, 0x00]);
+		// `state` replaces the whole storage of the account.
+		assert_eq!(<AccountStorages<Test>>::get(address, slot(1)), H256::zero());
+		assert_eq!(<AccountStorages<Test>>::get(address, slot(3)), slot(30));
+
+		crate::runner::stack::Runner::<Test>::apply_state_override(vec![(
+			address,
+			AccountOverride {
+				code: Some(Vec::new()),
+				state_diff: Some(vec![(slot(4), slot(40))]),
+				..Default::default()
+			},
+		)]);
+
+		assert!(!<AccountCodes<Test>>::contains_key(address));
+		// `stateDiff` only replaces the given slots.
+		assert_eq!(<AccountStorages<Test>>::get(address, slot(3)), slot(30));
+		assert_eq!(<AccountStorages<Test>>::get(address, slot(4)), slot(40));
+	});
+}
+
+#[test]
+fn runner_create_access_list_collects_accessed_accounts() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let other = H160::repeat_byte(0x77);
+		// PUSH1 0x01 SLOAD POP PUSH20 <other> BALANCE POP STOP
+		let mut code = vec![0x60, 0x01, 0x54, 0x50, 0x73];
+		code.extend_from_slice(other.as_bytes());
+		code.extend_from_slice(&[0x31, 0x50, 0x00]);
+		<AccountCodes<Test>>::insert(contract, code);
+
+		let (info, access_list) = crate::runner::stack::Runner::<Test>::create_access_list(
+			H160::default(),
+			Some(contract),
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			true,
+			<Test as Config>::config(),
+		)
+		.expect("access list can be created");
+
+		assert!(info.exit_reason.is_succeed());
+		assert_eq!(
+			access_list,
+			vec![
+				(contract, vec![H256::from_low_u64_be(1)]),
+				(other, Vec::new()),
+			]
+		);
+	});
+}
+
+// PUSH0 POP STOP
+const PUSH0_CODE: [u8; 3] = [0x5f, 0x50, 0x00];
+// PUSH1 1 PUSH1 0 TSTORE PUSH1 0 TLOAD POP STOP
+const TSTORE_CODE: [u8; 10] = [0x60, 0x01, 0x60, 0x00, 0x5d, 0x60, 0x00, 0x5c, 0x50, 0x00];
+// PUSH1 0 PUSH1 0 PUSH1 0 MCOPY STOP
+const MCOPY_CODE: [u8; 8] = [0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x5e, 0x00];
+
+fn call_contract_with_code(code: &[u8]) -> ExitReason {
+	let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+	<AccountCodes<Test>>::insert(contract, code.to_vec());
+
+	<Test as Config>::Runner::call(
+		H160::default(),
+		contract,
+		Vec::new(),
+		U256::zero(),
+		1000000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		true,  // must be validated
+		<Test as Config>::config(),
+	)
+	.expect("call can be executed")
+	.exit_reason
+}
+
+#[test]
+fn hardfork_activated_at_block_enables_its_opcodes() {
+	new_test_ext().execute_with(|| {
+		MockHardforks::set(&[
+			(HardforkActivation::Block(0), EvmHardfork::London),
+			(HardforkActivation::Block(10), EvmHardfork::Shanghai),
+			(HardforkActivation::Block(20), EvmHardfork::Cancun),
+		]);
+
+		System::set_block_number(9);
+		assert!(matches!(
+			call_contract_with_code(&PUSH0_CODE),
+			ExitReason::Error(_)
+		));
+
+		System::set_block_number(10);
+		assert!(call_contract_with_code(&PUSH0_CODE).is_succeed());
+		assert!(matches!(
+			call_contract_with_code(&TSTORE_CODE),
+			ExitReason::Error(_)
+		));
+		assert!(matches!(
+			call_contract_with_code(&MCOPY_CODE),
+			ExitReason::Error(_)
+		));
+
+		System::set_block_number(20);
+		assert!(call_contract_with_code(&TSTORE_CODE).is_succeed());
+		assert!(call_contract_with_code(&MCOPY_CODE).is_succeed());
+	});
+}
+
+#[test]
+fn hardfork_activated_at_timestamp_enables_its_opcodes() {
+	new_test_ext().execute_with(|| {
+		MockHardforks::set(&[
+			(HardforkActivation::Block(0), EvmHardfork::London),
+			(HardforkActivation::Timestamp(12_000), EvmHardfork::Shanghai),
+			(HardforkActivation::Timestamp(13_000), EvmHardfork::Cancun),
+		]);
+
+		Timestamp::set_timestamp(11_000);
+		assert!(matches!(
+			call_contract_with_code(&PUSH0_CODE),
+			ExitReason::Error(_)
+		));
+
+		Timestamp::set_timestamp(12_000);
+		assert!(call_contract_with_code(&PUSH0_CODE).is_succeed());
+		assert!(matches!(
+			call_contract_with_code(&TSTORE_CODE),
+			ExitReason::Error(_)
+		));
+		assert!(matches!(
+			call_contract_with_code(&MCOPY_CODE),
+			ExitReason::Error(_)
+		));
+
+		Timestamp::set_timestamp(13_000);
+		assert!(call_contract_with_code(&TSTORE_CODE).is_succeed());
+		assert!(call_contract_with_code(&MCOPY_CODE).is_succeed());
+	});
+}
+
+#[test]
+fn latest_active_hardfork_wins_whatever_the_schedule_order() {
+	new_test_ext().execute_with(|| {
+		MockHardforks::set(&[
+			(HardforkActivation::Block(20), EvmHardfork::Cancun),
+			(HardforkActivation::Block(0), EvmHardfork::London),
+			(HardforkActivation::Block(10), EvmHardfork::Shanghai),
+		]);
+
+		System::set_block_number(20);
+		assert!(call_contract_with_code(&MCOPY_CODE).is_succeed());
+	});
+}
+
+#[test]
+fn empty_hardfork_schedule_runs_london() {
+	new_test_ext().execute_with(|| {
+		MockHardforks::set(&[]);
+		// BASEFEE POP STOP
+		assert!(call_contract_with_code(&[0x48, 0x50, 0x00]).is_succeed());
+		assert!(matches!(
+			call_contract_with_code(&PUSH0_CODE),
+			ExitReason::Error(_)
+		));
+	});
+}
+
+#[test]
+fn block_override_replaces_block_environment() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000004").unwrap();
+		// Return NUMBER, TIMESTAMP, BASEFEE and COINBASE.
+		<AccountCodes<Test>>::insert(
+			contract,
+			vec![
+				0x43, 0x60, 0x00, 0x52, 0x42, 0x60, 0x20, 0x52, 0x48, 0x60, 0x40, 0x52, 0x41, 0x60,
+				0x60, 0x52, 0x60, 0x80, 0x60, 0x00, 0xf3,
+			],
+		);
+		let coinbase = H160::from_str("2000000000000000000000000000000000000002").unwrap();
+		let block_override = BlockOverride {
+			number: Some(100),
+			timestamp: Some(1_700_000_000),
+			base_fee: Some(U256::from(7)),
+			coinbase: Some(coinbase),
+		};
+
+		let info = EVM::with_block_override(block_override, || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				<Test as Config>::config(),
+			)
+		})
+		.expect("call can be executed");
+		assert!(info.exit_reason.is_succeed());
+		assert_eq!(U256::from_big_endian(&info.value[0..32]), U256::from(100));
+		assert_eq!(
+			U256::from_big_endian(&info.value[32..64]),
+			U256::from(1_700_000_000)
+		);
+		assert_eq!(U256::from_big_endian(&info.value[64..96]), U256::from(7));
+		assert_eq!(H160::from_slice(&info.value[108..128]), coinbase);
+
+		// The base fee and author are only overridden within the closure.
+		assert_eq!(EVM::base_fee().0, U256::from(1_000_000_000));
+		assert_eq!(
+			EVM::find_author(),
+			H160::from_str("1234500000000000000000000000000000000000").unwrap()
+		);
+	});
+}
@@ -18,7 +18,7 @@
 use std::fs;
 
 use evm::{Context, ExitError, ExitReason, ExitSucceed, Transfer};
-use fp_evm::{Precompile, PrecompileHandle};
+use fp_evm::{Precompile, PrecompileFailure, PrecompileHandle};
 use sp_core::{H160, H256};
 
 #[derive(Debug, serde::Deserialize)]
@@ -30,6 +30,14 @@ struct EthConsensusTest {
 	gas: Option<u64>,
 }
 
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EthConsensusFailureTest {
+	input: String,
+	expected_error: String,
+	name: String,
+}
+
 pub struct MockHandle {
 	pub input: Vec<u8>,
 	pub gas_limit: Option<u64>,
@@ -70,6 +78,17 @@ impl PrecompileHandle for MockHandle {
 		Ok(())
 	}
 
+	fn record_external_cost(
+		&mut self,
+		_: Option<u64>,
+		_: Option<u64>,
+		_: Option<u64>,
+	) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
 	fn log(&mut self, _: H160, _: Vec<H256>, _: Vec<u8>) -> Result<(), ExitError> {
 		unimplemented!()
 	}
@@ -151,3 +170,50 @@ pub fn test_precompile_test_vectors<P: Precompile>(filepath: &str) -> Result<(),
 
 	Ok(())
 }
+
+/// Tests that a precompile fails on the test vectors defined in the given file at filepath, with
+/// the error message of each vector. The file is expected to be in JSON format and contain an
+/// array of test vectors, where each vector can be deserialized into an "EthConsensusFailureTest".
+pub fn test_precompile_failure_test_vectors<P: Precompile>(filepath: &str) -> Result<(), String> {
+	let data = fs::read_to_string(filepath).expect("Failed to read json file");
+
+	let tests: Vec<EthConsensusFailureTest> =
+		serde_json::from_str(&data).expect("expected json array");
+
+	for test in tests {
+		let input: Vec<u8> = hex::decode(test.input).expect("Could not hex-decode test input data");
+
+		let cost: u64 = 10000000;
+
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+
+		let mut handle = MockHandle::new(input, Some(cost), context);
+
+		match P::execute(&mut handle) {
+			Ok(..) => {
+				return Err(format!(
+					"Test '{}' succeeded (expected an error)",
+					test.name
+				));
+			}
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other(message),
+			}) => {
+				assert_eq!(
+					message, test.expected_error,
+					"test '{}' failed (different error)",
+					test.name
+				);
+			}
+			Err(err) => {
+				return Err(format!("Test '{}' returned error: {:?}", test.name, err));
+			}
+		}
+	}
+
+	Ok(())
+}
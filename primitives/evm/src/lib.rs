@@ -18,13 +18,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod precompile;
+mod tracer;
 mod validation;
 
 use codec::{Decode, Encode};
 use frame_support::weights::Weight;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_std::vec::Vec;
 
 pub use evm::{
@@ -32,12 +33,15 @@ pub use evm::{
 	Config, ExitReason,
 };
 
+#[cfg(feature = "tracing")]
+pub use self::tracer::Tracer;
 pub use self::{
 	precompile::{
 		Context, ExitError, ExitRevert, ExitSucceed, LinearCostPrecompile, Precompile,
 		PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult, PrecompileSet,
 		Transfer,
 	},
+	tracer::{CallFrame, CallType, StructLog, StructLoggerTrace, TracerKind, TransactionTrace},
 	validation::{
 		CheckEvmTransaction, CheckEvmTransactionConfig, CheckEvmTransactionInput,
 		InvalidEvmTransactionError,
@@ -54,6 +58,36 @@ pub struct Vicinity {
 	pub origin: H160,
 }
 
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+/// State of an account replacing the stored one during a non-transactional call.
+pub struct AccountOverride {
+	/// Fake balance of the account.
+	pub balance: Option<U256>,
+	/// Fake nonce of the account.
+	pub nonce: Option<U256>,
+	/// Fake code of the account.
+	pub code: Option<Vec<u8>>,
+	/// Replaces the whole storage of the account.
+	pub state: Option<Vec<(H256, H256)>>,
+	/// Replaces the given storage slots of the account.
+	pub state_diff: Option<Vec<(H256, H256)>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+/// Block environment replacing the one of the current block during non-transactional calls.
+pub struct BlockOverride {
+	/// Fake block number.
+	pub number: Option<u64>,
+	/// Fake block timestamp, in seconds.
+	pub timestamp: Option<u64>,
+	/// Fake base fee per gas.
+	pub base_fee: Option<U256>,
+	/// Fake block author.
+	pub coinbase: Option<H160>,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 pub struct ExecutionInfo<T> {
@@ -98,3 +132,15 @@ impl FeeCalculator for () {
 		(U256::zero(), Weight::zero())
 	}
 }
+
+/// Trait that outputs the gas used by the EVM transactions of the current block.
+pub trait BlockGasUsed {
+	/// Return the gas used so far in the current block, `None` if it is not tracked.
+	fn block_gas_used() -> Option<U256>;
+}
+
+impl BlockGasUsed for () {
+	fn block_gas_used() -> Option<U256> {
+		None
+	}
+}
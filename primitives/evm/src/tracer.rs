@@ -0,0 +1,511 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EVM execution tracing.
+//!
+//! The types in this module are what the runtime hands back to the client when a transaction
+//! is replayed for `debug_trace*`. The listener that produces them is only available with the
+//! `tracing` feature, as it requires the event hooks of the `evm` crates.
+
+use codec::{Decode, Encode};
+use sp_core::{H160, H256, U256};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// Which tracer to run the transaction with.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum TracerKind {
+	/// Geth's default opcode logger.
+	StructLogger {
+		disable_storage: bool,
+		disable_stack: bool,
+		enable_memory: bool,
+	},
+	/// Geth's `callTracer`.
+	CallTracer,
+}
+
+/// A single executed opcode.
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: u8,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	pub stack: Option<Vec<H256>>,
+	pub memory: Option<Vec<u8>>,
+	pub storage: Option<BTreeMap<H256, H256>>,
+	pub error: Option<Vec<u8>>,
+}
+
+/// Output of the struct logger for a whole transaction.
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct StructLoggerTrace {
+	pub gas: U256,
+	pub failed: bool,
+	pub return_value: Vec<u8>,
+	pub struct_logs: Vec<StructLog>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+/// A node of the `callTracer` call tree.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct CallFrame {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub value: Option<U256>,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	pub error: Option<Vec<u8>>,
+	pub reverted: bool,
+	pub calls: Vec<CallFrame>,
+}
+
+/// Trace of a single transaction, in the shape requested by [`TracerKind`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum TransactionTrace {
+	StructLogger(StructLoggerTrace),
+	CallTracer(CallFrame),
+}
+
+#[cfg(feature = "tracing")]
+pub use self::listener::Tracer;
+
+#[cfg(feature = "tracing")]
+mod listener {
+	use super::*;
+	use core::cell::RefCell;
+	use evm::{
+		tracing::{Event as EvmEvent, EventListener as EvmListener},
+		Capture, ExitError, ExitFatal, ExitReason,
+	};
+	use evm_gasometer::{
+		tracing::{Event as GasometerEvent, EventListener as GasometerListener},
+		Snapshot,
+	};
+	use evm_runtime::tracing::{Event as RuntimeEvent, EventListener as RuntimeListener};
+	use sp_std::rc::Rc;
+
+	/// Collects the events emitted by the EVM while a closure runs, and turns them into a
+	/// [`TransactionTrace`].
+	///
+	/// Only the first top-level transaction executed in the closure is traced, so the closure
+	/// can apply a whole extrinsic (fee withdrawal, validation, ...) and not only the EVM call.
+	pub struct Tracer {
+		kind: TracerKind,
+		depth: u32,
+		done: bool,
+		// Struct logger state.
+		struct_logs: Vec<StructLog>,
+		pending: Option<StructLog>,
+		storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+		// Call tracer state.
+		frames: Vec<CallFrame>,
+		root: Option<CallFrame>,
+		// Outcome of the top-level call.
+		failed: bool,
+		return_value: Vec<u8>,
+	}
+
+	impl Tracer {
+		pub fn new(kind: TracerKind) -> Self {
+			Self {
+				kind,
+				depth: 0,
+				done: false,
+				struct_logs: Vec::new(),
+				pending: None,
+				storage: BTreeMap::new(),
+				frames: Vec::new(),
+				root: None,
+				failed: false,
+				return_value: Vec::new(),
+			}
+		}
+
+		/// Run `f` with this tracer registered as the listener of the EVM events.
+		pub fn trace<R, F: FnOnce() -> R>(self, f: F) -> (R, Self) {
+			let shared = Rc::new(RefCell::new(self));
+			let mut evm_listener = Proxy(shared.clone());
+			let mut runtime_listener = Proxy(shared.clone());
+			let mut gasometer_listener = Proxy(shared.clone());
+
+			let res = evm_gasometer::tracing::using(&mut gasometer_listener, || {
+				evm_runtime::tracing::using(&mut runtime_listener, || {
+					evm::tracing::using(&mut evm_listener, f)
+				})
+			});
+
+			drop((evm_listener, runtime_listener, gasometer_listener));
+			let tracer = match Rc::try_unwrap(shared) {
+				Ok(tracer) => tracer.into_inner(),
+				Err(_) => unreachable!("all listeners were dropped; qed"),
+			};
+			(res, tracer)
+		}
+
+		/// Consume the tracer. `used_gas` is the gas charged for the transaction, as it
+		/// appears in its receipt.
+		pub fn into_trace(mut self, used_gas: U256) -> TransactionTrace {
+			match self.kind {
+				TracerKind::StructLogger { .. } => {
+					self.flush_pending();
+					TransactionTrace::StructLogger(StructLoggerTrace {
+						gas: used_gas,
+						failed: self.failed,
+						return_value: self.return_value,
+						struct_logs: self.struct_logs,
+					})
+				}
+				TracerKind::CallTracer => {
+					let mut root = self.root.take().unwrap_or_else(|| CallFrame {
+						call_type: CallType::Call,
+						from: H160::default(),
+						to: None,
+						value: None,
+						gas: U256::zero(),
+						gas_used: U256::zero(),
+						input: Vec::new(),
+						output: Vec::new(),
+						error: None,
+						reverted: false,
+						calls: Vec::new(),
+					});
+					root.gas_used = used_gas;
+					TransactionTrace::CallTracer(root)
+				}
+			}
+		}
+
+		fn flush_pending(&mut self) {
+			if let Some(log) = self.pending.take() {
+				self.struct_logs.push(log);
+			}
+		}
+
+		fn enter(&mut self, frame: CallFrame) {
+			self.depth += 1;
+			if self.kind == TracerKind::CallTracer {
+				self.frames.push(frame);
+			}
+		}
+
+		fn exit(&mut self, reason: &ExitReason, return_value: &[u8]) {
+			self.depth = self.depth.saturating_sub(1);
+			if self.depth == 0 {
+				self.done = true;
+				self.failed = !reason.is_succeed();
+				self.return_value = return_value.to_vec();
+			}
+
+			if let Some(mut frame) = self.frames.pop() {
+				frame.output = return_value.to_vec();
+				match reason {
+					ExitReason::Succeed(_) => (),
+					ExitReason::Revert(_) => {
+						frame.reverted = true;
+						frame.error = Some(b"execution reverted".to_vec());
+					}
+					ExitReason::Error(e) => frame.error = Some(error_message(e)),
+					ExitReason::Fatal(e) => frame.error = Some(fatal_message(e)),
+				}
+				match self.frames.last_mut() {
+					Some(parent) => parent.calls.push(frame),
+					None => self.root = Some(frame),
+				}
+			}
+		}
+
+		fn on_evm_event(&mut self, event: EvmEvent) {
+			match event {
+				EvmEvent::Call {
+					code_address,
+					transfer,
+					input,
+					target_gas,
+					is_static,
+					context,
+				} => {
+					let call_type = if is_static {
+						CallType::StaticCall
+					} else if context.address != code_address {
+						if transfer.is_some() {
+							CallType::CallCode
+						} else {
+							CallType::DelegateCall
+						}
+					} else {
+						CallType::Call
+					};
+					let from = match call_type {
+						CallType::DelegateCall | CallType::CallCode => context.address,
+						_ => context.caller,
+					};
+					let value = match call_type {
+						CallType::StaticCall => None,
+						CallType::DelegateCall => Some(context.apparent_value),
+						_ => Some(transfer.as_ref().map(|t| t.value).unwrap_or_default()),
+					};
+					self.enter(CallFrame {
+						call_type,
+						from,
+						to: Some(code_address),
+						value,
+						gas: target_gas.unwrap_or_default().into(),
+						gas_used: U256::zero(),
+						input: input.to_vec(),
+						output: Vec::new(),
+						error: None,
+						reverted: false,
+						calls: Vec::new(),
+					});
+				}
+				EvmEvent::Create {
+					caller,
+					address,
+					scheme,
+					value,
+					init_code,
+					target_gas,
+				} => {
+					let call_type = match scheme {
+						evm::CreateScheme::Create2 { .. } => CallType::Create2,
+						_ => CallType::Create,
+					};
+					self.enter(CallFrame {
+						call_type,
+						from: caller,
+						to: Some(address),
+						value: Some(value),
+						gas: target_gas.unwrap_or_default().into(),
+						gas_used: U256::zero(),
+						input: init_code.to_vec(),
+						output: Vec::new(),
+						error: None,
+						reverted: false,
+						calls: Vec::new(),
+					});
+				}
+				EvmEvent::Suicide {
+					address,
+					target,
+					balance,
+				} => {
+					if let Some(parent) = self.frames.last_mut() {
+						parent.calls.push(CallFrame {
+							call_type: CallType::SelfDestruct,
+							from: address,
+							to: Some(target),
+							value: Some(balance),
+							gas: U256::zero(),
+							gas_used: U256::zero(),
+							input: Vec::new(),
+							output: Vec::new(),
+							error: None,
+							reverted: false,
+							calls: Vec::new(),
+						});
+					}
+				}
+				EvmEvent::Exit {
+					reason,
+					return_value,
+				} => self.exit(reason, return_value),
+				_ => (),
+			}
+		}
+
+		fn on_runtime_event(&mut self, event: RuntimeEvent) {
+			let (disable_storage, disable_stack, enable_memory) = match self.kind {
+				TracerKind::StructLogger {
+					disable_storage,
+					disable_stack,
+					enable_memory,
+				} => (disable_storage, disable_stack, enable_memory),
+				TracerKind::CallTracer => return,
+			};
+			match event {
+				RuntimeEvent::Step {
+					context: _,
+					opcode,
+					position,
+					stack,
+					memory,
+				} => {
+					self.flush_pending();
+					self.pending = Some(StructLog {
+						pc: position.as_ref().map(|p| *p as u64).unwrap_or_default(),
+						op: opcode.as_u8(),
+						depth: self.depth,
+						stack: (!disable_stack).then(|| stack.data().clone()),
+						memory: enable_memory.then(|| memory.data().clone()),
+						..Default::default()
+					});
+				}
+				RuntimeEvent::StepResult {
+					result: Err(Capture::Exit(ExitReason::Error(e))),
+					..
+				} => {
+					if let Some(log) = self.pending.as_mut() {
+						log.error = Some(error_message(e));
+					}
+				}
+				RuntimeEvent::SLoad {
+					address,
+					index,
+					value,
+				}
+				| RuntimeEvent::SStore {
+					address,
+					index,
+					value,
+				} => {
+					if disable_storage {
+						return;
+					}
+					let storage = self.storage.entry(address).or_default();
+					storage.insert(index, value);
+					if let Some(log) = self.pending.as_mut() {
+						log.storage = Some(storage.clone());
+					}
+				}
+				_ => (),
+			}
+		}
+
+		fn on_gasometer_event(&mut self, event: GasometerEvent) {
+			let (cost, snapshot) = match event {
+				GasometerEvent::RecordCost { cost, snapshot } => (cost, snapshot),
+				GasometerEvent::RecordDynamicCost {
+					gas_cost,
+					memory_gas,
+					snapshot,
+					..
+				} => {
+					let memory_cost = snapshot
+						.map(|s| memory_gas.saturating_sub(s.memory_gas))
+						.unwrap_or_default();
+					(gas_cost.saturating_add(memory_cost), snapshot)
+				}
+				_ => return,
+			};
+			if let Some(log) = self.pending.as_mut() {
+				// Costs are recorded before being applied, so the first snapshot of a step
+				// holds the gas available to the opcode.
+				if log.gas_cost == 0 {
+					log.gas = snapshot.map(remaining_gas).unwrap_or_default();
+				}
+				log.gas_cost = log.gas_cost.saturating_add(cost);
+			}
+			if let (Some(frame), Some(snapshot)) = (self.frames.last_mut(), snapshot) {
+				frame.gas_used = snapshot
+					.used_gas
+					.saturating_add(snapshot.memory_gas)
+					.saturating_add(cost)
+					.into();
+			}
+		}
+	}
+
+	fn remaining_gas(snapshot: Snapshot) -> u64 {
+		snapshot
+			.gas_limit
+			.saturating_sub(snapshot.used_gas)
+			.saturating_sub(snapshot.memory_gas)
+	}
+
+	/// Geth-compatible description of an EVM error.
+	fn error_message(error: &ExitError) -> Vec<u8> {
+		let message: &[u8] = match error {
+			ExitError::StackUnderflow => b"stack underflow",
+			ExitError::StackOverflow => b"stack limit reached 1024",
+			ExitError::InvalidJump => b"invalid jump destination",
+			ExitError::InvalidRange => b"return data out of bounds",
+			ExitError::DesignatedInvalid => b"invalid opcode: INVALID",
+			ExitError::CallTooDeep => b"max call depth exceeded",
+			ExitError::CreateCollision => b"contract address collision",
+			ExitError::CreateContractLimit => b"max code size exceeded",
+			ExitError::InvalidCode(_) => b"invalid code: must not begin with 0xef",
+			ExitError::OutOfOffset => b"out of offset",
+			ExitError::OutOfGas => b"out of gas",
+			ExitError::OutOfFund => b"insufficient balance for transfer",
+			ExitError::PCUnderflow => b"pc underflow",
+			ExitError::CreateEmpty => b"create empty",
+			ExitError::Other(message) => return message.as_bytes().to_vec(),
+			_ => b"execution error",
+		};
+		message.to_vec()
+	}
+
+	fn fatal_message(fatal: &ExitFatal) -> Vec<u8> {
+		match fatal {
+			ExitFatal::CallErrorAsFatal(e) => error_message(e),
+			ExitFatal::Other(message) => message.as_bytes().to_vec(),
+			_ => b"fatal error".to_vec(),
+		}
+	}
+
+	struct Proxy(Rc<RefCell<Tracer>>);
+
+	impl Proxy {
+		fn with<F: FnOnce(&mut Tracer)>(&mut self, f: F) {
+			let mut tracer = self.0.borrow_mut();
+			// Anything executed after the traced transaction (e.g. later extrinsics of the
+			// closure) is ignored.
+			if !tracer.done {
+				f(&mut tracer)
+			}
+		}
+	}
+
+	impl EvmListener for Proxy {
+		fn event(&mut self, event: EvmEvent) {
+			self.with(|tracer| tracer.on_evm_event(event))
+		}
+	}
+
+	impl RuntimeListener for Proxy {
+		fn event(&mut self, event: RuntimeEvent) {
+			self.with(|tracer| tracer.on_runtime_event(event))
+		}
+	}
+
+	impl GasometerListener for Proxy {
+		fn event(&mut self, event: GasometerEvent) {
+			self.with(|tracer| tracer.on_gasometer_event(event))
+		}
+	}
+}
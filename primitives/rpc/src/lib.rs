@@ -26,6 +26,13 @@ use sp_core::{H160, H256, U256};
 use sp_runtime::{traits::Block as BlockT, Permill, RuntimeDebug};
 use sp_std::vec::Vec;
 
+mod proof;
+
+pub use self::proof::{
+	account_code_key, account_storage_key, read_proof_value, verify_account_code,
+	verify_account_info, verify_account_storage, ProofError,
+};
+
 #[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct TransactionStatus {
 	pub transaction_hash: H256,
@@ -39,7 +46,7 @@ pub struct TransactionStatus {
 
 sp_api::decl_runtime_apis! {
 	/// API necessary for Ethereum-compatibility layer.
-	#[api_version(4)]
+	#[api_version(8)]
 	pub trait EthereumRuntimeRPCApi {
 		/// Returns runtime defined pallet_evm::ChainId.
 		fn chain_id() -> u64;
@@ -54,6 +61,8 @@ sp_api::decl_runtime_apis! {
 		/// For a given account address and index, returns pallet_evm::AccountStorages.
 		fn storage_at(address: H160, index: U256) -> H256;
 		/// Returns a frame_ethereum::call response. If `estimate` is true,
+		/// the execution is run in estimate mode. `state_override` replaces the state
+		/// of the given accounts for the duration of the call.
 		#[changed_in(2)]
 		fn call(
 			from: H160,
@@ -77,6 +86,19 @@ sp_api::decl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 		) -> Result<fp_evm::CallInfo, sp_runtime::DispatchError>;
+		#[changed_in(6)]
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<fp_evm::CallInfo, sp_runtime::DispatchError>;
 		fn call(
 			from: H160,
 			to: H160,
@@ -88,6 +110,7 @@ sp_api::decl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, fp_evm::AccountOverride)>>,
 		) -> Result<fp_evm::CallInfo, sp_runtime::DispatchError>;
 		/// Returns a frame_ethereum::create response.
 		#[changed_in(2)]
@@ -111,6 +134,7 @@ sp_api::decl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 		) -> Result<fp_evm::CreateInfo, sp_runtime::DispatchError>;
+		#[changed_in(6)]
 		fn create(
 			from: H160,
 			data: Vec<u8>,
@@ -122,6 +146,18 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
 		) -> Result<fp_evm::CreateInfo, sp_runtime::DispatchError>;
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, fp_evm::AccountOverride)>>,
+		) -> Result<fp_evm::CreateInfo, sp_runtime::DispatchError>;
 		/// Return the current block. Legacy.
 		#[changed_in(2)]
 		fn current_block() -> Option<ethereum::BlockV0>;
@@ -164,6 +200,71 @@ sp_api::decl_runtime_apis! {
 		) -> Vec<ethereum::TransactionV2>;
 		/// Return the elasticity multiplier.
 		fn elasticity() -> Option<Permill>;
+		/// For a given account address, returns the storage key of its `frame_system::Account` entry.
+		fn account_info_key(address: H160) -> Vec<u8>;
+		/// Executes a call, or a create if `to` is `None`, and returns its result along with
+		/// the EIP-2930 access list of the addresses and storage keys it accessed.
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(fp_evm::CallInfo, Vec<(H160, Vec<H256>)>), sp_runtime::DispatchError>;
+		/// Same as `call`, without estimate mode, executed in the environment of the block
+		/// replaced by `block_override`.
+		fn call_with_block_override(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, fp_evm::AccountOverride)>>,
+			block_override: fp_evm::BlockOverride,
+		) -> Result<fp_evm::CallInfo, sp_runtime::DispatchError>;
+		/// Same as `create`, without estimate mode, executed in the environment of the block
+		/// replaced by `block_override`.
+		fn create_with_block_override(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, fp_evm::AccountOverride)>>,
+			block_override: fp_evm::BlockOverride,
+		) -> Result<fp_evm::CreateInfo, sp_runtime::DispatchError>;
+	}
+
+	/// API used by the `debug` RPC namespace to replay and trace Ethereum transactions.
+	///
+	/// Must be called on top of the parent state of the traced block, after
+	/// `Core::initialize_block` has been called with the header of the traced block.
+	/// Fails if the runtime is built without tracing support.
+	pub trait DebugRuntimeApi {
+		/// Apply `extrinsics` in order and return the trace of the Ethereum transaction
+		/// `transaction_hash`, if found. Extrinsics after the traced one are not applied.
+		fn trace_transaction(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			tracer: fp_evm::TracerKind,
+		) -> Result<Option<fp_evm::TransactionTrace>, sp_runtime::DispatchError>;
+		/// Apply `extrinsics` in order and return the trace of every Ethereum transaction,
+		/// keyed by transaction hash.
+		fn trace_block(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			tracer: fp_evm::TracerKind,
+		) -> Result<Vec<(H256, fp_evm::TransactionTrace)>, sp_runtime::DispatchError>;
 	}
 
 	#[api_version(2)]
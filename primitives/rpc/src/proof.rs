@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage keys and verification of the proofs returned by `eth_getProof`.
+//!
+//! Substrate does not store accounts in an Ethereum Merkle-Patricia trie, so the proofs
+//! are Substrate read proofs of the underlying storage entries, checked against the state
+//! root of the Substrate block header.
+
+use codec::Decode;
+use sp_core::{
+	hashing::{blake2_128, twox_128},
+	H160, H256, U256,
+};
+use sp_runtime::{traits::BlakeTwo256, RuntimeDebug};
+use sp_std::vec::Vec;
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
+
+use fp_storage::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM};
+
+/// Error returned when checking an `eth_getProof` proof.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum ProofError {
+	/// The proof is incomplete or does not match the state root.
+	InvalidProof,
+	/// The proven value cannot be decoded.
+	InvalidValue,
+}
+
+fn blake2_128_concat(data: &[u8]) -> Vec<u8> {
+	let mut key = blake2_128(data).to_vec();
+	key.extend_from_slice(data);
+	key
+}
+
+fn evm_storage_prefix(storage: &[u8]) -> Vec<u8> {
+	[twox_128(PALLET_EVM), twox_128(storage)].concat()
+}
+
+/// Storage key of `pallet_evm::AccountCodes` for `address`.
+pub fn account_code_key(address: H160) -> Vec<u8> {
+	let mut key = evm_storage_prefix(EVM_ACCOUNT_CODES);
+	key.extend(blake2_128_concat(address.as_bytes()));
+	key
+}
+
+/// Storage key of `pallet_evm::AccountStorages` for `address` and `index`.
+pub fn account_storage_key(address: H160, index: U256) -> Vec<u8> {
+	let mut slot = [0u8; 32];
+	index.to_big_endian(&mut slot);
+
+	let mut key = evm_storage_prefix(EVM_ACCOUNT_STORAGES);
+	key.extend(blake2_128_concat(address.as_bytes()));
+	key.extend(blake2_128_concat(&slot));
+	key
+}
+
+/// Read the raw value of `key` from a read proof, `None` if the proof shows the key is absent.
+pub fn read_proof_value(
+	state_root: H256,
+	proof: Vec<Vec<u8>>,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, ProofError> {
+	let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+	read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, &state_root, key, None, None)
+		.map_err(|_| ProofError::InvalidProof)
+}
+
+fn read_proof_decoded<T: Decode>(
+	state_root: H256,
+	proof: Vec<Vec<u8>>,
+	key: &[u8],
+) -> Result<Option<T>, ProofError> {
+	read_proof_value(state_root, proof, key)?
+		.map(|value| T::decode(&mut &value[..]).map_err(|_| ProofError::InvalidValue))
+		.transpose()
+}
+
+/// Verify the `frame_system` account entry of an `accountProof`.
+///
+/// `key` is the `frame_system::Account` storage key of the account the address maps to, and
+/// `T` the runtime's `frame_system::AccountInfo` type.
+pub fn verify_account_info<T: Decode>(
+	state_root: H256,
+	proof: Vec<Vec<u8>>,
+	key: &[u8],
+) -> Result<Option<T>, ProofError> {
+	read_proof_decoded(state_root, proof, key)
+}
+
+/// Verify the code of `address` from an `accountProof`. Accounts without code have empty code.
+pub fn verify_account_code(
+	state_root: H256,
+	proof: Vec<Vec<u8>>,
+	address: H160,
+) -> Result<Vec<u8>, ProofError> {
+	read_proof_decoded(state_root, proof, &account_code_key(address)).map(Option::unwrap_or_default)
+}
+
+/// Verify the value of the storage slot `index` of `address` from a `storageProof`.
+pub fn verify_account_storage(
+	state_root: H256,
+	proof: Vec<Vec<u8>>,
+	address: H160,
+	index: U256,
+) -> Result<H256, ProofError> {
+	read_proof_decoded(state_root, proof, &account_storage_key(address, index))
+		.map(Option::unwrap_or_default)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use sp_runtime::StateVersion;
+	use sp_state_machine::{prove_read, Backend, InMemoryBackend};
+
+	fn backend() -> InMemoryBackend<BlakeTwo256> {
+		let address = H160::repeat_byte(0xaa);
+		let entries = vec![
+			(account_code_key(address), vec![0x60u8, 0x00].encode()),
+			(
+				account_storage_key(address, U256::from(1)),
+				H256::repeat_byte(0x11).encode(),
+			),
+			(b"system_account".to_vec(), (7u32, 100u128).encode()),
+		];
+		let mut storage = std::collections::HashMap::new();
+		storage.insert(None, entries.into_iter().collect());
+		InMemoryBackend::from((storage, StateVersion::V1))
+	}
+
+	fn proof_for(keys: &[&[u8]]) -> Vec<Vec<u8>> {
+		prove_read(backend(), keys)
+			.unwrap()
+			.into_iter_nodes()
+			.collect()
+	}
+
+	#[test]
+	fn verifies_account_proof() {
+		let root = backend()
+			.storage_root(std::iter::empty(), StateVersion::V1)
+			.0;
+		let address = H160::repeat_byte(0xaa);
+		let code_key = account_code_key(address);
+		let proof = proof_for(&[&code_key[..], b"system_account"]);
+
+		assert_eq!(
+			verify_account_code(root, proof.clone(), address),
+			Ok(vec![0x60, 0x00])
+		);
+		assert_eq!(
+			verify_account_info::<(u32, u128)>(root, proof, b"system_account"),
+			Ok(Some((7, 100)))
+		);
+	}
+
+	#[test]
+	fn verifies_storage_proof() {
+		let root = backend()
+			.storage_root(std::iter::empty(), StateVersion::V1)
+			.0;
+		let address = H160::repeat_byte(0xaa);
+		let set = account_storage_key(address, U256::from(1));
+		let unset = account_storage_key(address, U256::from(2));
+		let proof = proof_for(&[&set[..], &unset[..]]);
+
+		assert_eq!(
+			verify_account_storage(root, proof.clone(), address, U256::from(1)),
+			Ok(H256::repeat_byte(0x11))
+		);
+		assert_eq!(
+			verify_account_storage(root, proof, address, U256::from(2)),
+			Ok(H256::zero())
+		);
+	}
+
+	#[test]
+	fn rejects_proof_for_another_root() {
+		let address = H160::repeat_byte(0xaa);
+		let code_key = account_code_key(address);
+		let proof = proof_for(&[&code_key[..]]);
+
+		assert_eq!(
+			verify_account_code(H256::repeat_byte(1), proof, address),
+			Err(ProofError::InvalidProof)
+		);
+	}
+}
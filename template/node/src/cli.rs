@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 /// Available Sealing methods.
 #[cfg(feature = "manual-seal")]
 #[derive(Debug, Copy, Clone, clap::ValueEnum)]
@@ -15,6 +17,17 @@ impl Default for Sealing {
 	}
 }
 
+/// Ethereum RPC namespaces which replay transactions, only served when enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum EthApi {
+	/// `debug_trace*` and raw data methods.
+	Debug,
+	/// Parity style `trace_*` methods.
+	Trace,
+	/// Otterscan `ots_*` methods.
+	Ots,
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, clap::Parser)]
 pub struct RunCmd {
@@ -30,14 +43,64 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub enable_dev_signer: bool,
 
+	/// Directory of Ethereum JSON v3 keystore files used to sign `eth_sendTransaction`.
+	#[arg(long, value_name = "PATH", requires = "eth_keystore_password_file")]
+	pub eth_keystore: Option<PathBuf>,
+
+	/// File containing the password of the `--eth-keystore` files.
+	#[arg(long, value_name = "PATH", requires = "eth_keystore")]
+	pub eth_keystore_password_file: Option<PathBuf>,
+
+	/// Clef compatible external signer used to sign `eth_sendTransaction`, given as an ipc
+	/// path or an `http://` url.
+	#[arg(long, value_name = "ENDPOINT")]
+	pub external_signer: Option<String>,
+
 	/// Maximum number of logs in a query.
 	#[arg(long, default_value = "10000")]
 	pub max_past_logs: u32,
 
+	/// Ethereum RPC namespaces replaying transactions to serve, e.g. `--ethapi=debug,trace,ots`.
+	#[arg(long, value_enum, value_delimiter = ',', ignore_case = true)]
+	pub ethapi: Vec<EthApi>,
+
+	/// Index the call traces of the canonical blocks by address to speed up `trace_filter`.
+	#[arg(long)]
+	pub enable_trace_index: bool,
+
+	/// Maximum number of blocks replayed by a `trace_filter` query.
+	#[arg(long, default_value = "1000")]
+	pub max_trace_filter_blocks: u64,
+
 	/// Maximum fee history cache size.
 	#[arg(long, default_value = "2048")]
 	pub fee_history_limit: u64,
 
+	/// Accepted error ratio of the `eth_estimateGas` binary search, 0 to disable the early exit.
+	/// Requires the `rpc_binary_search_estimate` feature.
+	#[arg(long, default_value = "0")]
+	pub estimate_gas_error_ratio: f64,
+
+	/// Longest wait of `eth_sendRawTransactionSync` for a receipt, in milliseconds.
+	#[arg(long, default_value = "12000")]
+	pub send_raw_transaction_sync_timeout: u64,
+
+	/// Number of recent blocks sampled by the `eth_gasPrice` oracle.
+	#[arg(long, default_value = "20")]
+	pub gpo_blocks: u64,
+
+	/// Percentile of the sampled priority fees suggested by the `eth_gasPrice` oracle.
+	#[arg(long, default_value = "60")]
+	pub gpo_percentile: u64,
+
+	/// Maximum priority fee suggested by the `eth_gasPrice` oracle, in wei.
+	#[arg(long, default_value = "500000000000")]
+	pub gpo_max_price: u64,
+
+	/// Priority fees below this one are ignored by the `eth_gasPrice` oracle, in wei.
+	#[arg(long, default_value = "2")]
+	pub gpo_ignore_price: u64,
+
 	/// The dynamic-fee pallet target gas price set by block author
 	#[arg(long, default_value = "1")]
 	pub target_gas_price: u64,
@@ -233,6 +233,15 @@ pub fn run() -> sc_cli::Result<()> {
 			})
 		}
 		None => {
+			if !cfg!(feature = "rpc_binary_search_estimate")
+				&& cli.run.estimate_gas_error_ratio != 0.0
+			{
+				return Err(
+					"`--estimate-gas-error-ratio` only applies to the binary search of \
+					`eth_estimateGas`. You can enable it with `--features rpc_binary_search_estimate`."
+						.into(),
+				);
+			}
 			let runner = cli.create_runner(&cli.run.base)?;
 			runner.run_node_until_exit(|config| async move {
 				service::new_full(config, &cli).map_err(sc_cli::Error::Service)
@@ -1,12 +1,13 @@
 //! A collection of node-specific RPC methods.
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
 use jsonrpsee::RpcModule;
 // Substrate
 use sc_client_api::{
 	backend::{AuxStore, Backend, StateBackend, StorageProvider},
 	client::BlockchainEvents,
+	BlockBackend, ProofProvider,
 };
 #[cfg(feature = "manual-seal")]
 use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
@@ -21,14 +22,17 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_runtime::traits::BlakeTwo256;
 // Frontier
 use fc_rpc::{
-	EthBlockDataCacheTask, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
-	SchemaV2Override, SchemaV3Override, StorageOverride,
+	EthBlockDataCacheTask, EthClefSigner, EthKeystoreSigner, OverrideHandle,
+	RuntimeApiStorageOverride, SchemaV1Override, SchemaV2Override, SchemaV3Override,
+	StorageOverride,
 };
-use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool, GasPriceOracleConfig};
 use fp_storage::EthereumStorageSchema;
 // Runtime
 use frontier_template_runtime::{opaque::Block, AccountId, Balance, Hash, Index};
 
+use crate::cli::EthApi;
+
 /// Full client dependencies.
 pub struct FullDeps<C, P, A: ChainApi> {
 	/// The client instance to use.
@@ -43,6 +47,10 @@ pub struct FullDeps<C, P, A: ChainApi> {
 	pub is_authority: bool,
 	/// Whether to enable dev signer
 	pub enable_dev_signer: bool,
+	/// Signer of the keystore files given on the command line.
+	pub keystore_signer: Option<EthKeystoreSigner>,
+	/// External signer given on the command line.
+	pub external_signer: Option<EthClefSigner>,
 	/// Network service
 	pub network: Arc<NetworkService<Block, Hash>>,
 	/// EthFilterApi pool.
@@ -51,10 +59,20 @@ pub struct FullDeps<C, P, A: ChainApi> {
 	pub backend: Arc<fc_db::Backend<Block>>,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
+	/// Enabled Ethereum RPC namespaces which replay transactions.
+	pub ethapi: Vec<EthApi>,
+	/// Maximum number of blocks replayed by a `trace_filter` query.
+	pub max_trace_filter_blocks: u64,
 	/// Fee history cache.
 	pub fee_history_cache: FeeHistoryCache,
 	/// Maximum fee history cache size.
 	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+	/// Accepted error ratio of the `eth_estimateGas` binary search.
+	pub estimate_gas_error_ratio: f64,
+	/// Configuration of the `eth_gasPrice` oracle.
+	pub gas_price_oracle: GasPriceOracleConfig,
+	/// Longest wait of `eth_sendRawTransactionSync` for a receipt.
+	pub send_raw_transaction_sync_timeout: Duration,
 	/// Ethereum data access overrides.
 	pub overrides: Arc<OverrideHandle<Block>>,
 	/// Cache for Ethereum block data.
@@ -108,20 +126,22 @@ where
 	BE: Backend<Block> + 'static,
 	BE::State: StateBackend<BlakeTwo256>,
 	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
-	C: BlockchainEvents<Block>,
+	C: BlockchainEvents<Block> + BlockBackend<Block> + ProofProvider<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
+	C::Api: fp_rpc::DebugRuntimeApi<Block>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 {
 	use fc_rpc::{
-		Eth, EthApiServer, EthDevSigner, EthFilter, EthFilterApiServer, EthPubSub,
-		EthPubSubApiServer, EthSigner, Net, NetApiServer, Web3, Web3ApiServer,
+		Debug, DebugApiServer, Eth, EthApiServer, EthDevSigner, EthFilter, EthFilterApiServer,
+		EthPubSub, EthPubSubApiServer, EthSigner, Net, NetApiServer, Ots, OtsApiServer, Trace,
+		TraceApiServer, TxPool, TxPoolApiServer, Web3, Web3ApiServer,
 	};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -134,12 +154,19 @@ where
 		deny_unsafe,
 		is_authority,
 		enable_dev_signer,
+		keystore_signer,
+		external_signer,
 		network,
 		filter_pool,
 		backend,
 		max_past_logs,
+		ethapi,
+		max_trace_filter_blocks,
 		fee_history_cache,
 		fee_history_cache_limit,
+		estimate_gas_error_ratio,
+		gas_price_oracle,
+		send_raw_transaction_sync_timeout,
 		overrides,
 		block_data_cache,
 		#[cfg(feature = "manual-seal")]
@@ -153,12 +180,18 @@ where
 	if enable_dev_signer {
 		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
 	}
+	if let Some(keystore_signer) = keystore_signer {
+		signers.push(Box::new(keystore_signer) as Box<dyn EthSigner>);
+	}
+	if let Some(external_signer) = external_signer {
+		signers.push(Box::new(external_signer) as Box<dyn EthSigner>);
+	}
 
 	io.merge(
 		Eth::new(
 			client.clone(),
 			pool.clone(),
-			graph,
+			graph.clone(),
 			Some(frontier_template_runtime::TransactionConverter),
 			network.clone(),
 			signers,
@@ -170,6 +203,9 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			10,
+			estimate_gas_error_ratio,
+			gas_price_oracle,
+			send_raw_transaction_sync_timeout,
 		)
 		.into_rpc(),
 	)?;
@@ -178,11 +214,11 @@ where
 		io.merge(
 			EthFilter::new(
 				client.clone(),
-				backend,
+				backend.clone(),
 				filter_pool,
 				500_usize, // max stored filters
 				max_past_logs,
-				block_data_cache,
+				block_data_cache.clone(),
 			)
 			.into_rpc(),
 		)?;
@@ -191,10 +227,11 @@ where
 	io.merge(
 		EthPubSub::new(
 			pool,
+			graph.clone(),
 			client.clone(),
 			network.clone(),
 			subscription_task_executor,
-			overrides,
+			overrides.clone(),
 		)
 		.into_rpc(),
 	)?;
@@ -209,7 +246,37 @@ where
 		.into_rpc(),
 	)?;
 
-	io.merge(Web3::new(client).into_rpc())?;
+	io.merge(Web3::new(client.clone()).into_rpc())?;
+
+	io.merge(TxPool::new(client.clone(), graph).into_rpc())?;
+
+	if ethapi.contains(&EthApi::Debug) {
+		io.merge(
+			Debug::new(
+				client.clone(),
+				backend.clone(),
+				overrides.clone(),
+				block_data_cache.clone(),
+			)
+			.into_rpc(),
+		)?;
+	}
+
+	if ethapi.contains(&EthApi::Trace) {
+		io.merge(
+			Trace::new(
+				client.clone(),
+				backend.clone(),
+				overrides.clone(),
+				max_trace_filter_blocks,
+			)
+			.into_rpc(),
+		)?;
+	}
+
+	if ethapi.contains(&EthApi::Ots) {
+		io.merge(Ots::new(client, backend, overrides).into_rpc())?;
+	}
 
 	#[cfg(feature = "manual-seal")]
 	if let Some(command_sink) = command_sink {
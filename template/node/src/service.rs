@@ -19,9 +19,9 @@ use sp_core::U256;
 // Frontier
 use fc_consensus::FrontierBlockImport;
 use fc_db::Backend as FrontierBackend;
-use fc_mapping_sync::{MappingSyncWorker, SyncStrategy};
-use fc_rpc::{EthTask, OverrideHandle};
-use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fc_mapping_sync::{IndexBackfillWorker, MappingSyncWorker, SyncStrategy, TraceIndexWorker};
+use fc_rpc::{EthClefSigner, EthKeystoreSigner, EthTask, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool, GasPriceOracleConfig};
 // Runtime
 use frontier_template_runtime::{opaque::Block, RuntimeApi};
 
@@ -259,6 +259,40 @@ fn remote_keystore(_url: &str) -> Result<Arc<LocalKeystore>, &'static str> {
 	Err("Remote Keystore not supported.")
 }
 
+/// Build the Ethereum signers selected on the command line.
+fn eth_signers(
+	cli: &Cli,
+) -> Result<(Option<EthKeystoreSigner>, Option<EthClefSigner>), ServiceError> {
+	let keystore_signer = match (&cli.run.eth_keystore, &cli.run.eth_keystore_password_file) {
+		(Some(dir), Some(password_file)) => {
+			let password = std::fs::read_to_string(password_file).map_err(|e| {
+				ServiceError::Other(format!("Cannot read {}: {}", password_file.display(), e))
+			})?;
+			let password = password.trim_end_matches(&['\r', '\n'][..]);
+			Some(EthKeystoreSigner::from_dir(dir, password).map_err(ServiceError::Other)?)
+		}
+		_ => None,
+	};
+	let external_signer = cli
+		.run
+		.external_signer
+		.as_deref()
+		.map(EthClefSigner::connect)
+		.transpose()
+		.map_err(ServiceError::Other)?;
+
+	Ok((keystore_signer, external_signer))
+}
+
+fn gas_price_oracle_config(cli: &Cli) -> GasPriceOracleConfig {
+	GasPriceOracleConfig {
+		blocks: cli.run.gpo_blocks,
+		percentile: cli.run.gpo_percentile,
+		max_price: U256::from(cli.run.gpo_max_price),
+		ignore_price: U256::from(cli.run.gpo_ignore_price),
+	}
+}
+
 /// Builds a new service for a full client.
 #[cfg(feature = "aura")]
 pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, ServiceError> {
@@ -358,12 +392,19 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 		let pool = transaction_pool.clone();
 		let is_authority = role.is_authority();
 		let enable_dev_signer = cli.run.enable_dev_signer;
+		let (keystore_signer, external_signer) = eth_signers(cli)?;
 		let network = network.clone();
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let overrides = overrides.clone();
 		let fee_history_cache = fee_history_cache.clone();
 		let max_past_logs = cli.run.max_past_logs;
+		let ethapi = cli.run.ethapi.clone();
+		let max_trace_filter_blocks = cli.run.max_trace_filter_blocks;
+		let estimate_gas_error_ratio = cli.run.estimate_gas_error_ratio;
+		let gas_price_oracle = gas_price_oracle_config(cli);
+		let send_raw_transaction_sync_timeout =
+			Duration::from_millis(cli.run.send_raw_transaction_sync_timeout);
 
 		Box::new(move |deny_unsafe, subscription_task_executor| {
 			let deps = crate::rpc::FullDeps {
@@ -373,12 +414,19 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 				deny_unsafe,
 				is_authority,
 				enable_dev_signer,
+				keystore_signer: keystore_signer.clone(),
+				external_signer: external_signer.clone(),
 				network: network.clone(),
 				filter_pool: filter_pool.clone(),
 				backend: frontier_backend.clone(),
 				max_past_logs,
+				ethapi: ethapi.clone(),
+				max_trace_filter_blocks,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
+				estimate_gas_error_ratio,
+				gas_price_oracle: gas_price_oracle.clone(),
+				send_raw_transaction_sync_timeout,
 				overrides: overrides.clone(),
 				block_data_cache: block_data_cache.clone(),
 			};
@@ -410,6 +458,7 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 		overrides,
 		fee_history_cache,
 		fee_history_cache_limit,
+		cli.run.enable_trace_index,
 	);
 
 	let (block_import, grandpa_link) = consensus_result;
@@ -584,12 +633,19 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 		let pool = transaction_pool.clone();
 		let is_authority = role.is_authority();
 		let enable_dev_signer = cli.run.enable_dev_signer;
+		let (keystore_signer, external_signer) = eth_signers(cli)?;
 		let network = network.clone();
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let overrides = overrides.clone();
 		let fee_history_cache = fee_history_cache.clone();
 		let max_past_logs = cli.run.max_past_logs;
+		let ethapi = cli.run.ethapi.clone();
+		let max_trace_filter_blocks = cli.run.max_trace_filter_blocks;
+		let estimate_gas_error_ratio = cli.run.estimate_gas_error_ratio;
+		let gas_price_oracle = gas_price_oracle_config(cli);
+		let send_raw_transaction_sync_timeout =
+			Duration::from_millis(cli.run.send_raw_transaction_sync_timeout);
 
 		Box::new(move |deny_unsafe, subscription_task_executor| {
 			let deps = crate::rpc::FullDeps {
@@ -599,12 +655,19 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 				deny_unsafe,
 				is_authority,
 				enable_dev_signer,
+				keystore_signer: keystore_signer.clone(),
+				external_signer: external_signer.clone(),
 				network: network.clone(),
 				filter_pool: filter_pool.clone(),
 				backend: frontier_backend.clone(),
 				max_past_logs,
+				ethapi: ethapi.clone(),
+				max_trace_filter_blocks,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
+				estimate_gas_error_ratio,
+				gas_price_oracle: gas_price_oracle.clone(),
+				send_raw_transaction_sync_timeout,
 				overrides: overrides.clone(),
 				block_data_cache: block_data_cache.clone(),
 				command_sink: Some(command_sink.clone()),
@@ -637,6 +700,7 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 		overrides,
 		fee_history_cache,
 		fee_history_cache_limit,
+		cli.run.enable_trace_index,
 	);
 
 	if role.is_authority() {
@@ -732,6 +796,7 @@ fn spawn_frontier_tasks(
 	overrides: Arc<OverrideHandle<Block>>,
 	fee_history_cache: FeeHistoryCache,
 	fee_history_cache_limit: FeeHistoryCacheLimit,
+	enable_trace_index: bool,
 ) {
 	task_manager.spawn_essential_handle().spawn(
 		"frontier-mapping-sync-worker",
@@ -741,7 +806,8 @@ fn spawn_frontier_tasks(
 			Duration::new(6, 0),
 			client.clone(),
 			backend,
-			frontier_backend,
+			overrides.clone(),
+			frontier_backend.clone(),
 			3,
 			0,
 			SyncStrategy::Normal,
@@ -749,6 +815,37 @@ fn spawn_frontier_tasks(
 		.for_each(|()| future::ready(())),
 	);
 
+	// Indexes the blocks synced before the log and address indexes existed, the task ends once
+	// caught up.
+	task_manager.spawn_handle().spawn(
+		"frontier-index-backfill",
+		None,
+		IndexBackfillWorker::<_, _, FullBackend>::new(
+			Duration::from_millis(100),
+			client.clone(),
+			overrides.clone(),
+			frontier_backend.clone(),
+			100,
+		)
+		.for_each(|()| future::ready(())),
+	);
+
+	if enable_trace_index {
+		task_manager.spawn_essential_handle().spawn(
+			"frontier-trace-index-worker",
+			None,
+			TraceIndexWorker::new(
+				client.import_notification_stream(),
+				Duration::new(6, 0),
+				client.clone(),
+				frontier_backend.clone(),
+				10,
+				0,
+			)
+			.for_each(|()| future::ready(())),
+		);
+	}
+
 	// Spawn Frontier EthFilterApi maintenance task.
 	if let Some(filter_pool) = filter_pool {
 		// Each filter is allowed to stay in the pool for 100 blocks.
@@ -767,6 +864,7 @@ fn spawn_frontier_tasks(
 		EthTask::fee_history_task(
 			client,
 			overrides,
+			frontier_backend,
 			fee_history_cache,
 			fee_history_cache_limit,
 		),
@@ -332,6 +332,7 @@ impl pallet_evm::Config for Runtime {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type FindAuthor = FindAuthorTruncated<Aura>;
+	type Hardforks = ();
 }
 
 impl pallet_ethereum::Config for Runtime {
@@ -370,6 +371,8 @@ impl pallet_base_fee::Config for Runtime {
 	type Threshold = BaseFeeThreshold;
 	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 	type DefaultElasticity = DefaultElasticity;
+	type BlockGasUsed = ();
+	type BlockGasLimit = BlockGasLimit;
 }
 
 impl pallet_hotfix_sufficients::Config for Runtime {
@@ -377,6 +380,17 @@ impl pallet_hotfix_sufficients::Config for Runtime {
 	type WeightInfo = pallet_hotfix_sufficients::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const Erc20Name: &'static str = "Unit";
+	pub const Erc20Symbol: &'static str = "UNIT";
+}
+
+impl pallet_evm_precompile_balances_erc20::Config for Runtime {
+	type Name = Erc20Name;
+	type Symbol = Erc20Symbol;
+	type Decimals = ConstU8<18>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -397,6 +411,7 @@ construct_runtime!(
 		DynamicFee: pallet_dynamic_fee,
 		BaseFee: pallet_base_fee,
 		HotfixSufficients: pallet_hotfix_sufficients,
+		BalancesErc20: pallet_evm_precompile_balances_erc20,
 	}
 );
 
@@ -520,6 +535,91 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	}
 }
 
+/// Gas used by the last Ethereum transaction applied in the block being built.
+#[cfg(feature = "evm-tracing")]
+fn last_transaction_used_gas() -> U256 {
+	let cumulative_gas_used = |receipt: &pallet_ethereum::Receipt| match receipt {
+		pallet_ethereum::Receipt::Legacy(d)
+		| pallet_ethereum::Receipt::EIP2930(d)
+		| pallet_ethereum::Receipt::EIP1559(d) => d.used_gas,
+	};
+	let mut receipts = Ethereum::pending()
+		.into_iter()
+		.rev()
+		.map(|(_, _, receipt)| cumulative_gas_used(&receipt));
+	let last = receipts.next().unwrap_or_default();
+	last.saturating_sub(receipts.next().unwrap_or_default())
+}
+
+/// Applies `extrinsics` in order and traces the Ethereum transaction `transaction_hash`.
+#[cfg(feature = "evm-tracing")]
+fn trace_ethereum_transaction(
+	extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+	transaction_hash: H256,
+	tracer: fp_evm::TracerKind,
+) -> Result<Option<fp_evm::TransactionTrace>, sp_runtime::DispatchError> {
+	for ext in extrinsics {
+		let traced = matches!(
+			&ext.0.function,
+			RuntimeCall::Ethereum(transact { transaction }) if transaction.hash() == transaction_hash
+		);
+		if traced {
+			let (_, tracer) = fp_evm::Tracer::new(tracer).trace(|| Executive::apply_extrinsic(ext));
+			let used_gas = last_transaction_used_gas();
+			return Ok(Some(tracer.into_trace(used_gas)));
+		}
+		let _ = Executive::apply_extrinsic(ext);
+	}
+	Ok(None)
+}
+
+/// Applies `extrinsics` in order and traces every Ethereum transaction.
+#[cfg(feature = "evm-tracing")]
+fn trace_ethereum_block(
+	extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+	tracer: fp_evm::TracerKind,
+) -> Result<Vec<(H256, fp_evm::TransactionTrace)>, sp_runtime::DispatchError> {
+	let mut traces = Vec::new();
+	for ext in extrinsics {
+		let transaction_hash = match &ext.0.function {
+			RuntimeCall::Ethereum(transact { transaction }) => Some(transaction.hash()),
+			_ => None,
+		};
+		match transaction_hash {
+			Some(transaction_hash) => {
+				let (_, trace) =
+					fp_evm::Tracer::new(tracer.clone()).trace(|| Executive::apply_extrinsic(ext));
+				let used_gas = last_transaction_used_gas();
+				traces.push((transaction_hash, trace.into_trace(used_gas)));
+			}
+			None => {
+				let _ = Executive::apply_extrinsic(ext);
+			}
+		}
+	}
+	Ok(traces)
+}
+
+#[cfg(not(feature = "evm-tracing"))]
+fn trace_ethereum_transaction(
+	_: Vec<<Block as BlockT>::Extrinsic>,
+	_: H256,
+	_: fp_evm::TracerKind,
+) -> Result<Option<fp_evm::TransactionTrace>, sp_runtime::DispatchError> {
+	Err(EVM_TRACING_DISABLED.into())
+}
+
+#[cfg(not(feature = "evm-tracing"))]
+fn trace_ethereum_block(
+	_: Vec<<Block as BlockT>::Extrinsic>,
+	_: fp_evm::TracerKind,
+) -> Result<Vec<(H256, fp_evm::TransactionTrace)>, sp_runtime::DispatchError> {
+	Err(EVM_TRACING_DISABLED.into())
+}
+
+#[cfg(not(feature = "evm-tracing"))]
+const EVM_TRACING_DISABLED: &str = "the runtime is built without the `evm-tracing` feature";
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -643,7 +743,13 @@ impl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, pallet_evm::AccountOverride)>>,
 		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			// Runtime API calls are not persisted, so the overrides only live for this call.
+			if let Some(state_override) = state_override {
+				pallet_evm::runner::stack::Runner::<Runtime>::apply_state_override(state_override);
+			}
+
 			let config = if estimate {
 				let mut config = <Runtime as pallet_evm::Config>::config().clone();
 				config.estimate = true;
@@ -681,7 +787,13 @@ impl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, pallet_evm::AccountOverride)>>,
 		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			// Runtime API calls are not persisted, so the overrides only live for this call.
+			if let Some(state_override) = state_override {
+				pallet_evm::runner::stack::Runner::<Runtime>::apply_state_override(state_override);
+			}
+
 			let config = if estimate {
 				let mut config = <Runtime as pallet_evm::Config>::config().clone();
 				config.estimate = true;
@@ -744,6 +856,130 @@ impl_runtime_apis! {
 		fn elasticity() -> Option<Permill> {
 			Some(BaseFee::elasticity())
 		}
+
+		fn account_info_key(address: H160) -> Vec<u8> {
+			use pallet_evm::AddressMapping;
+			let account_id = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address);
+			frame_system::Account::<Runtime>::hashed_key_for(account_id)
+		}
+
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(pallet_evm::CallInfo, Vec<(H160, Vec<H256>)>), sp_runtime::DispatchError> {
+			let validate = true;
+			pallet_evm::runner::stack::Runner::<Runtime>::create_access_list(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				validate,
+				<Runtime as pallet_evm::Config>::config(),
+			).map_err(|err| err.error.into())
+		}
+
+		fn call_with_block_override(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, pallet_evm::AccountOverride)>>,
+			block_override: pallet_evm::BlockOverride,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			// Runtime API calls are not persisted, so the overrides only live for this call.
+			if let Some(state_override) = state_override {
+				pallet_evm::runner::stack::Runner::<Runtime>::apply_state_override(state_override);
+			}
+
+			let is_transactional = false;
+			let validate = true;
+			EVM::with_block_override(block_override, || {
+				<Runtime as pallet_evm::Config>::Runner::call(
+					from,
+					to,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					is_transactional,
+					validate,
+					<Runtime as pallet_evm::Config>::config(),
+				)
+			}).map_err(|err| err.error.into())
+		}
+
+		fn create_with_block_override(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			state_override: Option<Vec<(H160, pallet_evm::AccountOverride)>>,
+			block_override: pallet_evm::BlockOverride,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			// Runtime API calls are not persisted, so the overrides only live for this call.
+			if let Some(state_override) = state_override {
+				pallet_evm::runner::stack::Runner::<Runtime>::apply_state_override(state_override);
+			}
+
+			let is_transactional = false;
+			let validate = true;
+			EVM::with_block_override(block_override, || {
+				<Runtime as pallet_evm::Config>::Runner::create(
+					from,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					is_transactional,
+					validate,
+					<Runtime as pallet_evm::Config>::config(),
+				)
+			}).map_err(|err| err.error.into())
+		}
+	}
+
+	impl fp_rpc::DebugRuntimeApi<Block> for Runtime {
+		fn trace_transaction(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			tracer: fp_evm::TracerKind,
+		) -> Result<Option<fp_evm::TransactionTrace>, sp_runtime::DispatchError> {
+			trace_ethereum_transaction(extrinsics, transaction_hash, tracer)
+		}
+
+		fn trace_block(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			tracer: fp_evm::TracerKind,
+		) -> Result<Vec<(H256, fp_evm::TransactionTrace)>, sp_runtime::DispatchError> {
+			trace_ethereum_block(extrinsics, tracer)
+		}
 	}
 
 	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
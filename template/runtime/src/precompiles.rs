@@ -1,56 +1,34 @@
-use pallet_evm::{Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
-use sp_core::H160;
-use sp_std::marker::PhantomData;
+use precompile_utils::precompile_set::{
+	AcceptDelegateCall, AddressU64, CallableByContract, PrecompileAt, PrecompileSetBuilder,
+};
 
+use pallet_evm_precompile_balances_erc20::BalancesErc20;
+use pallet_evm_precompile_blake2::Blake2F;
+use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 
-pub struct FrontierPrecompiles<R>(PhantomData<R>);
+/// The precompiles are pure functions, which can be called from anywhere.
+type PureChecks = (CallableByContract, AcceptDelegateCall);
 
-impl<R> FrontierPrecompiles<R>
-where
-	R: pallet_evm::Config,
-{
-	pub fn new() -> Self {
-		Self(Default::default())
-	}
-	pub fn used_addresses() -> [H160; 7] {
-		[
-			hash(1),
-			hash(2),
-			hash(3),
-			hash(4),
-			hash(5),
-			hash(1024),
-			hash(1025),
-		]
-	}
-}
-impl<R> PrecompileSet for FrontierPrecompiles<R>
-where
-	R: pallet_evm::Config,
-{
-	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
-		match handle.code_address() {
-			// Ethereum precompiles :
-			a if a == hash(1) => Some(ECRecover::execute(handle)),
-			a if a == hash(2) => Some(Sha256::execute(handle)),
-			a if a == hash(3) => Some(Ripemd160::execute(handle)),
-			a if a == hash(4) => Some(Identity::execute(handle)),
-			a if a == hash(5) => Some(Modexp::execute(handle)),
-			// Non-Frontier specific nor Ethereum precompiles :
-			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
-			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
-			_ => None,
-		}
-	}
-
-	fn is_precompile(&self, address: H160) -> bool {
-		Self::used_addresses().contains(&address)
-	}
-}
-
-fn hash(a: u64) -> H160 {
-	H160::from_low_u64_be(a)
-}
+pub type FrontierPrecompiles<R> = PrecompileSetBuilder<
+	R,
+	(
+		// Ethereum precompiles :
+		PrecompileAt<AddressU64<1>, ECRecover, PureChecks>,
+		PrecompileAt<AddressU64<2>, Sha256, PureChecks>,
+		PrecompileAt<AddressU64<3>, Ripemd160, PureChecks>,
+		PrecompileAt<AddressU64<4>, Identity, PureChecks>,
+		PrecompileAt<AddressU64<5>, Modexp, PureChecks>,
+		PrecompileAt<AddressU64<6>, Bn128Add, PureChecks>,
+		PrecompileAt<AddressU64<7>, Bn128Mul, PureChecks>,
+		PrecompileAt<AddressU64<8>, Bn128Pairing, PureChecks>,
+		PrecompileAt<AddressU64<9>, Blake2F, PureChecks>,
+		// Non-Frontier specific nor Ethereum precompiles :
+		PrecompileAt<AddressU64<1024>, Sha3FIPS256, PureChecks>,
+		PrecompileAt<AddressU64<1025>, ECRecoverPublicKey, PureChecks>,
+		// The native currency as an ERC-20 :
+		PrecompileAt<AddressU64<2048>, BalancesErc20<R>, CallableByContract>,
+	),
+>;
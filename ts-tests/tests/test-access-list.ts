@@ -0,0 +1,54 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import Storage from "../build/contracts/Storage.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Access List)", (context) => {
+	const STORAGE_ABI = Storage.abi as AbiItem[];
+	const SLOT = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+	let contractAddress;
+
+	before("create the contract", async function () {
+		this.timeout(15000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: Storage.bytecode,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		await createAndFinalizeBlock(context.web3);
+		contractAddress = (await context.web3.eth.getTransactionReceipt(tx.transactionHash)).contractAddress;
+	});
+
+	it("should create the access list of a call", async function () {
+		const contract = new context.web3.eth.Contract(STORAGE_ABI);
+		const request = {
+			from: GENESIS_ACCOUNT,
+			to: contractAddress,
+			data: contract.methods.getStorage(SLOT).encodeABI(),
+		};
+
+		const result = (await customRequest(context.web3, "eth_createAccessList", [request, "latest"])).result;
+		expect(result.accessList).to.deep.eq([{ address: contractAddress.toLowerCase(), storageKeys: [SLOT] }]);
+		expect(result).to.not.have.property("error");
+		expect(Number(result.gasUsed)).to.be.greaterThan(21000);
+	});
+
+	it("should report the failure of a call", async function () {
+		const result = (
+			await customRequest(context.web3, "eth_createAccessList", [
+				{ from: GENESIS_ACCOUNT, to: contractAddress, data: "0xdeadbeef" },
+				"latest",
+			])
+		).result;
+		expect(result.error).to.eq("execution reverted");
+	});
+});
@@ -0,0 +1,56 @@
+import { expect } from "chai";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Block Receipts)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	let hashes = [];
+
+	before("create a block with several transactions", async function () {
+		this.timeout(15000);
+		for (const nonce of [0, 1, 2]) {
+			const tx = await context.web3.eth.accounts.signTransaction(
+				{
+					from: GENESIS_ACCOUNT,
+					to: TEST_ACCOUNT,
+					value: "0x200",
+					gasPrice: "0x3B9ACA00",
+					gas: "0x100000",
+					nonce,
+				},
+				GENESIS_ACCOUNT_PRIVATE_KEY
+			);
+			hashes.push((await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result);
+		}
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should return the receipts of all the transactions of a block", async function () {
+		const receipts = (await customRequest(context.web3, "eth_getBlockReceipts", ["latest"])).result;
+		expect(receipts.length).to.eq(hashes.length);
+		for (const [index, hash] of hashes.entries()) {
+			const receipt = (await customRequest(context.web3, "eth_getTransactionReceipt", [hash])).result;
+			expect(receipts[index]).to.deep.eq(receipt);
+		}
+	});
+
+	it("should return the receipts of a block by hash", async function () {
+		const block = await context.web3.eth.getBlock("latest");
+		const byNumber = (
+			await customRequest(context.web3, "eth_getBlockReceipts", [context.web3.utils.numberToHex(block.number)])
+		).result;
+		const byHash = (await customRequest(context.web3, "eth_getBlockReceipts", [block.hash])).result;
+		expect(byHash).to.deep.eq(byNumber);
+	});
+
+	it("should return null for an unknown block", async function () {
+		const receipts = (
+			await customRequest(context.web3, "eth_getBlockReceipts", [
+				"0x0000000000000000000000000000000000000000000000000000000000000001",
+			])
+		).result;
+		expect(receipts).to.be.null;
+	});
+});
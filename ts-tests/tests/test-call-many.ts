@@ -0,0 +1,77 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import Storage from "../build/contracts/Storage.json";
+import { GENESIS_ACCOUNT } from "./config";
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Call Many)", (context) => {
+	const STORAGE_ABI = Storage.abi as AbiItem[];
+	const CONTRACT_ADDRESS = "0x1111111111111111111111111111111111111111";
+	const ENV_ADDRESS = "0x3333333333333333333333333333333333333333";
+	const COINBASE = "0x4444444444444444444444444444444444444444";
+	const SLOT = "0x0000000000000000000000000000000000000000000000000000000000000001";
+	const VALUE = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+	// Return NUMBER, TIMESTAMP, BASEFEE and COINBASE.
+	const ENV_CODE = "0x4360005242602052486040524160605260806000f3";
+
+	const contract = new context.web3.eth.Contract(STORAGE_ABI);
+	const setStorage = contract.methods.setStorage(SLOT, VALUE).encodeABI();
+	const getStorage = contract.methods.getStorage(SLOT).encodeABI();
+	const callContext = { blockNumber: "latest", transactionIndex: -1 };
+
+	it("should carry the state changes over to the next calls", async function () {
+		const result = await customRequest(context.web3, "eth_callMany", [
+			[
+				{
+					transactions: [
+						{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: getStorage },
+						{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: setStorage },
+					],
+				},
+				{ transactions: [{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: getStorage }] },
+			],
+			callContext,
+			{ [CONTRACT_ADDRESS]: { code: Storage.deployedBytecode } },
+		]);
+		expect(result.result.length).to.eq(2);
+		expect(result.result[0][0].value).to.eq("0x" + "00".repeat(32));
+		expect(result.result[0][1].error).to.be.undefined;
+		expect(Number(result.result[0][1].gasUsed)).to.be.greaterThan(21000);
+		expect(result.result[1][0].value).to.eq(VALUE);
+	});
+
+	it("should not persist the state changes", async function () {
+		expect(await context.web3.eth.getCode(CONTRACT_ADDRESS)).to.eq("0x");
+	});
+
+	it("should apply the block overrides", async function () {
+		const result = await customRequest(context.web3, "eth_callMany", [
+			[
+				{
+					transactions: [{ from: GENESIS_ACCOUNT, to: ENV_ADDRESS }],
+					blockOverride: {
+						blockNumber: "0x64",
+						timestamp: "0x6553f100",
+						baseFee: "0x7",
+						coinbase: COINBASE,
+					},
+				},
+			],
+			callContext,
+			{ [ENV_ADDRESS]: { code: ENV_CODE } },
+		]);
+		const value = result.result[0][0].value.slice(2);
+		expect(Number("0x" + value.slice(0, 64))).to.eq(100);
+		expect(Number("0x" + value.slice(64, 128))).to.eq(0x6553f100);
+		expect(Number("0x" + value.slice(128, 192))).to.eq(7);
+		expect("0x" + value.slice(216, 256)).to.eq(COINBASE);
+	});
+
+	it("should reject a transaction index", async function () {
+		const result = await customRequest(context.web3, "eth_callMany", [[], { blockNumber: "latest", transactionIndex: 0 }]);
+		expect(result.error.message).to.eq(
+			"eth_callMany only executes on top of the whole block, transactionIndex must be -1"
+		);
+	});
+});
@@ -0,0 +1,57 @@
+import { expect } from "chai";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Debug Raw Data)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	let rawTransaction;
+	let transactionHash;
+
+	before("send a transfer", async function () {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		rawTransaction = tx.rawTransaction;
+		transactionHash = (await customRequest(context.web3, "eth_sendRawTransaction", [rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should return the signed transaction", async function () {
+		expect((await customRequest(context.web3, "debug_getRawTransaction", [transactionHash])).result).to.eq(
+			rawTransaction
+		);
+		const unknown = "0x" + "11".repeat(32);
+		expect((await customRequest(context.web3, "debug_getRawTransaction", [unknown])).result).to.be.null;
+	});
+
+	it("should return the header hashing to the block hash", async function () {
+		const block = await context.web3.eth.getBlock("latest");
+		const header = (await customRequest(context.web3, "debug_getRawHeader", ["latest"])).result;
+		expect(context.web3.utils.keccak256(header)).to.eq(block.hash);
+		const byHash = (await customRequest(context.web3, "debug_getRawHeader", [block.hash])).result;
+		expect(byHash).to.eq(header);
+	});
+
+	it("should return the block with its header and transactions", async function () {
+		const header = (await customRequest(context.web3, "debug_getRawHeader", ["latest"])).result;
+		const block = (await customRequest(context.web3, "debug_getRawBlock", ["latest"])).result;
+		expect(block).to.include(header.slice(2));
+		expect(block).to.include(rawTransaction.slice(2));
+	});
+
+	it("should return the receipts of a block", async function () {
+		const receipts = (await customRequest(context.web3, "debug_getRawReceipts", ["latest"])).result;
+		expect(receipts.length).to.eq(1);
+		expect(receipts[0]).to.match(/^0x[0-9a-f]+$/);
+		expect((await customRequest(context.web3, "debug_getRawReceipts", ["pending"])).result).to.be.null;
+	});
+});
@@ -0,0 +1,97 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import ExplicitRevertReason from "../build/contracts/ExplicitRevertReason.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Debug Trace)", (context) => {
+	const TEST_CONTRACT_ABI = ExplicitRevertReason.abi as AbiItem[];
+
+	let contractAddress;
+	let deployHash;
+	let revertHash;
+
+	before("create the contract and a reverting transaction", async function () {
+		this.timeout(15000);
+		const deploy = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: ExplicitRevertReason.bytecode,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		deployHash = (await customRequest(context.web3, "eth_sendRawTransaction", [deploy.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+		contractAddress = (await context.web3.eth.getTransactionReceipt(deployHash)).contractAddress;
+
+		const contract = new context.web3.eth.Contract(TEST_CONTRACT_ABI, contractAddress);
+		const call = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: contractAddress,
+				data: contract.methods.max10(30).encodeABI(),
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		revertHash = (await customRequest(context.web3, "eth_sendRawTransaction", [call.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should trace a transaction with the struct logger", async function () {
+		const trace = (await customRequest(context.web3, "debug_traceTransaction", [deployHash])).result;
+		expect(trace.failed).to.be.false;
+		expect(trace.structLogs.length).to.be.greaterThan(0);
+		expect(trace.structLogs[0]).to.include({ pc: 0, op: "PUSH1", depth: 1 });
+		expect(trace.structLogs[0].stack).to.deep.eq([]);
+		expect(trace.structLogs[0]).to.not.have.property("memory");
+	});
+
+	it("should report the revert of a transaction with the struct logger", async function () {
+		const trace = (await customRequest(context.web3, "debug_traceTransaction", [revertHash])).result;
+		expect(trace.failed).to.be.true;
+		expect(trace.structLogs[trace.structLogs.length - 1].op).to.eq("REVERT");
+	});
+
+	it("should trace a transaction with the call tracer", async function () {
+		const trace = (
+			await customRequest(context.web3, "debug_traceTransaction", [revertHash, { tracer: "callTracer" }])
+		).result;
+		expect(trace.type).to.eq("CALL");
+		expect(trace.from).to.eq(GENESIS_ACCOUNT);
+		expect(trace.to).to.eq(contractAddress.toLowerCase());
+		expect(trace.error).to.eq("execution reverted");
+		expect(trace.revertReason).to.eq("Value must not be greater than 10.");
+	});
+
+	it("should trace all the transactions of a block", async function () {
+		const receipt = await context.web3.eth.getTransactionReceipt(revertHash);
+		const byNumber = (
+			await customRequest(context.web3, "debug_traceBlockByNumber", [
+				context.web3.utils.numberToHex(receipt.blockNumber),
+				{ tracer: "callTracer" },
+			])
+		).result;
+		const byHash = (
+			await customRequest(context.web3, "debug_traceBlockByHash", [receipt.blockHash, { tracer: "callTracer" }])
+		).result;
+		expect(byNumber).to.deep.eq(byHash);
+		expect(byNumber.length).to.eq(1);
+		expect(byNumber[0].txHash).to.eq(revertHash);
+		expect(byNumber[0].result.type).to.eq("CALL");
+	});
+
+	it("should reject unknown tracers", async function () {
+		const response = await customRequest(context.web3, "debug_traceTransaction", [
+			revertHash,
+			{ tracer: "prestateTracer" },
+		]);
+		expect(response.error.message).to.eq("tracer prestateTracer is not supported");
+	});
+});
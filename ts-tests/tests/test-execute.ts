@@ -1,8 +1,9 @@
 import { assert, expect } from "chai";
 import { step } from "mocha-steps";
-import { BLOCK_GAS_LIMIT, GENESIS_ACCOUNT } from "./config";
+import { AbiItem } from "web3-utils";
+import { BLOCK_GAS_LIMIT, GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, FIRST_CONTRACT_ADDRESS } from "./config";
 
-import { describeWithFrontier, customRequest } from "./util";
+import { describeWithFrontier, customRequest, createAndFinalizeBlock } from "./util";
 
 import Test from "../build/contracts/Test.json";
 
@@ -89,3 +90,53 @@ describeWithFrontier("Frontier RPC (RPC execution)", (context) => {
 		);
 	});
 });
+
+describeWithFrontier("Frontier RPC (estimateGas block parameter)", (context) => {
+	const TEST_CONTRACT_ABI = Test.abi as AbiItem[];
+
+	before("create the contract", async function () {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: TEST_CONTRACT_BYTECODE,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	const estimateMultiply = async (block: string) => {
+		const contract = new context.web3.eth.Contract(TEST_CONTRACT_ABI, FIRST_CONTRACT_ADDRESS);
+		return customRequest(context.web3, "eth_estimateGas", [
+			{
+				from: GENESIS_ACCOUNT,
+				to: FIRST_CONTRACT_ADDRESS,
+				data: contract.methods.multiply(3).encodeABI(),
+			},
+			block,
+		]);
+	};
+
+	it("should estimate against the given block", async function () {
+		// The contract has no code before its creation block.
+		expect((await estimateMultiply("0x0")).result).to.be.equal("0x5208");
+		expect(Number((await estimateMultiply("0x1")).result)).to.be.above(21000);
+	});
+
+	it("should estimate against block hashes and tags", async function () {
+		const latest = (await estimateMultiply("latest")).result;
+		const genesisHash = (await context.web3.eth.getBlock(0)).hash;
+		expect((await estimateMultiply(genesisHash)).result).to.be.equal("0x5208");
+		expect((await estimateMultiply("pending")).result).to.be.equal(latest);
+		expect((await estimateMultiply("finalized")).result).to.be.equal(latest);
+	});
+
+	it("should fail for unknown blocks", async function () {
+		const result = await estimateMultiply("0x100");
+		expect((result as any).error.message).to.be.equal("header not found");
+	});
+});
@@ -0,0 +1,60 @@
+import { ethers } from "ethers";
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, CHAIN_ID } from "./config";
+import { createAndFinalizeBlock, describeWithFrontier, customRequest } from "./util";
+
+describeWithFrontier("Frontier RPC (Gas Price Oracle)", (context) => {
+	let nonce = 0;
+
+	async function createBlock(priority_fees) {
+		let signer = new ethers.Wallet(GENESIS_ACCOUNT_PRIVATE_KEY, context.ethersjs);
+		for (const priority_fee of priority_fees) {
+			await signer.sendTransaction({
+				from: GENESIS_ACCOUNT,
+				to: "0x0000000000000000000000000000000000000000",
+				data: "0x",
+				value: "0x00",
+				maxFeePerGas: "0x77359400",
+				maxPriorityFeePerGas: context.web3.utils.numberToHex(priority_fee),
+				accessList: [],
+				nonce: nonce,
+				gasLimit: "0x5208",
+				chainId: CHAIN_ID,
+			});
+			nonce++;
+		}
+		await createAndFinalizeBlock(context.web3);
+	}
+
+	async function suggestedPriorityFee() {
+		const gasPrice = BigInt((await customRequest(context.web3, "eth_gasPrice", [])).result);
+		const baseFee = BigInt((await context.web3.eth.getBlock("latest")).baseFeePerGas);
+		return Number(gasPrice - baseFee);
+	}
+
+	step("should suggest the base fee without recent transactions", async function () {
+		await createAndFinalizeBlock(context.web3);
+		expect(await suggestedPriorityFee()).to.be.eq(0);
+	});
+
+	// - Create 20 blocks, each with 10 txns of monotonically increasing tips.
+	// - The oracle samples the 3 lowest tips of at least 2 wei of each block.
+	// - The 60th percentile of the 60 samples is the third tip of the 12th block.
+	step("should add the percentile 60 of the lowest recent tips", async function () {
+		this.timeout(100000);
+
+		let priority_fee = 0;
+		for (let i = 0; i < 20; i++) {
+			let priority_fees = [];
+			for (let j = 0; j < 10; j++) {
+				priority_fees.push(priority_fee);
+				priority_fee++;
+			}
+			await createBlock(priority_fees);
+		}
+
+		expect(await suggestedPriorityFee()).to.be.eq(112);
+	});
+});
@@ -0,0 +1,133 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import Test from "../build/contracts/Test.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Otterscan)", (context) => {
+	const TEST_CONTRACT_ABI = Test.abi as AbiItem[];
+
+	let contractAddress;
+	let deployHash;
+	let callHash;
+
+	before("create the contract and call it", async function () {
+		this.timeout(15000);
+		const deploy = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: Test.bytecode,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		deployHash = (await customRequest(context.web3, "eth_sendRawTransaction", [deploy.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+		contractAddress = (await context.web3.eth.getTransactionReceipt(deployHash)).contractAddress.toLowerCase();
+
+		const contract = new context.web3.eth.Contract(TEST_CONTRACT_ABI, contractAddress);
+		const call = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: contractAddress,
+				data: contract.methods.multiply(3).encodeABI(),
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		callHash = (await customRequest(context.web3, "eth_sendRawTransaction", [call.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should return the api level", async function () {
+		expect((await customRequest(context.web3, "ots_getApiLevel", [])).result).to.eq(8);
+	});
+
+	it("should tell whether an address has code", async function () {
+		expect((await customRequest(context.web3, "ots_hasCode", [contractAddress, "latest"])).result).to.be.true;
+		expect((await customRequest(context.web3, "ots_hasCode", [GENESIS_ACCOUNT, "latest"])).result).to.be.false;
+	});
+
+	it("should search the transactions of an address, newest first", async function () {
+		const page = (await customRequest(context.web3, "ots_searchTransactionsBefore", [GENESIS_ACCOUNT, 0, 10]))
+			.result;
+		expect(page.txs.map((tx) => tx.hash)).to.deep.eq([callHash, deployHash]);
+		expect(page.receipts.map((receipt) => receipt.transactionHash)).to.deep.eq([callHash, deployHash]);
+		expect(page.firstPage).to.be.true;
+		expect(page.lastPage).to.be.true;
+
+		const after = (await customRequest(context.web3, "ots_searchTransactionsAfter", [contractAddress, 1, 10]))
+			.result;
+		expect(after.txs.map((tx) => tx.hash)).to.deep.eq([callHash]);
+		expect(after.firstPage).to.be.true;
+		expect(after.lastPage).to.be.false;
+	});
+
+	it("should find a transaction by sender and nonce", async function () {
+		expect(
+			(await customRequest(context.web3, "ots_getTransactionBySenderAndNonce", [GENESIS_ACCOUNT, "0x0"])).result
+		).to.eq(deployHash);
+		expect(
+			(await customRequest(context.web3, "ots_getTransactionBySenderAndNonce", [GENESIS_ACCOUNT, "0x1"])).result
+		).to.eq(callHash);
+		expect(
+			(await customRequest(context.web3, "ots_getTransactionBySenderAndNonce", [GENESIS_ACCOUNT, "0x2"])).result
+		).to.be.null;
+	});
+
+	it("should return the creator of a contract", async function () {
+		expect((await customRequest(context.web3, "ots_getContractCreator", [contractAddress])).result).to.deep.eq({
+			hash: deployHash,
+			creator: GENESIS_ACCOUNT,
+		});
+		expect((await customRequest(context.web3, "ots_getContractCreator", [GENESIS_ACCOUNT])).result).to.be.null;
+	});
+
+	it("should return the details of a block", async function () {
+		const receipt = await context.web3.eth.getTransactionReceipt(callHash);
+		const details = (
+			await customRequest(context.web3, "ots_getBlockDetails", [context.web3.utils.numberToHex(receipt.blockNumber)])
+		).result;
+		expect(details.block.hash).to.eq(receipt.blockHash);
+		expect(details.block.transactionCount).to.eq(1);
+		expect(details.block.transactions).to.deep.eq([]);
+		expect(details.totalFees).to.eq(context.web3.utils.numberToHex(receipt.gasUsed * 1000000000));
+	});
+
+	it("should return a page of the transactions of a block", async function () {
+		const receipt = await context.web3.eth.getTransactionReceipt(callHash);
+		const page = (
+			await customRequest(context.web3, "ots_getBlockTransactions", [
+				context.web3.utils.numberToHex(receipt.blockNumber),
+				0,
+				10,
+			])
+		).result;
+		expect(page.fullblock.hash).to.eq(receipt.blockHash);
+		expect(page.fullblock.transactionCount).to.eq(1);
+		expect(page.fullblock.transactions.map((tx) => tx.hash)).to.deep.eq([callHash]);
+		// The input is cropped to the function selector.
+		expect(page.fullblock.transactions[0].input.length).to.eq(10);
+		expect(page.receipts.map((receipt) => receipt.transactionHash)).to.deep.eq([callHash]);
+		expect(page.receipts[0].logs).to.deep.eq([]);
+
+		const details = (await customRequest(context.web3, "ots_getBlockDetailsByHash", [receipt.blockHash])).result;
+		expect(details.block.hash).to.eq(receipt.blockHash);
+	});
+
+	it("should return the internal operations and the error of a transaction", async function () {
+		expect((await customRequest(context.web3, "ots_getInternalOperations", [callHash])).result).to.deep.eq([]);
+		expect((await customRequest(context.web3, "ots_getTransactionError", [callHash])).result).to.eq("0x");
+	});
+
+	it("should trace a transaction", async function () {
+		const traces = (await customRequest(context.web3, "ots_traceTransaction", [callHash])).result;
+		expect(traces.length).to.eq(1);
+		expect(traces[0]).to.include({ type: "CALL", depth: 0, from: GENESIS_ACCOUNT, to: contractAddress });
+	});
+});
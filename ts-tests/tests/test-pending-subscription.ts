@@ -0,0 +1,76 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontierWs } from "./util";
+
+describeWithFrontierWs("Frontier RPC (Pending Transactions Subscription)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	async function sendTransfer(nonce: number, gasPrice: string) {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice,
+				gas: "0x100000",
+				nonce,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		return tx.transactionHash;
+	}
+
+	// Subscribe and collect the notifications of the subscription.
+	async function subscribe(params: any[]) {
+		const id = (await customRequest(context.web3, "eth_subscribe", params)).result;
+		const notifications = [];
+		(context.web3.currentProvider as any).on("data", (message) => {
+			if (message.params && message.params.subscription === id) {
+				notifications.push(message.params.result);
+			}
+		});
+		return { id, notifications };
+	}
+
+	async function waitFor(notifications: any[], count: number) {
+		while (notifications.length < count) {
+			await new Promise((resolve) => setTimeout(resolve, 100));
+		}
+	}
+
+	step("should stream full pending transactions", async function () {
+		const { id, notifications } = await subscribe(["newPendingTransactions", true]);
+
+		const nonce = await context.web3.eth.getTransactionCount(GENESIS_ACCOUNT);
+		const hash = await sendTransfer(nonce, "0x3B9ACA00");
+		await waitFor(notifications, 1);
+
+		expect(notifications[0].hash).to.eq(hash);
+		expect(notifications[0].from).to.eq(GENESIS_ACCOUNT.toLowerCase());
+		expect(notifications[0].to).to.eq(TEST_ACCOUNT);
+		expect(notifications[0].blockHash).to.be.null;
+
+		await customRequest(context.web3, "eth_unsubscribe", [id]);
+		await createAndFinalizeBlock(context.web3);
+	}).timeout(20000);
+
+	step("should report replaced and included transactions", async function () {
+		const { id, notifications } = await subscribe(["pendingTransactionStatus"]);
+
+		const nonce = await context.web3.eth.getTransactionCount(GENESIS_ACCOUNT);
+		const replaced = await sendTransfer(nonce, "0x3B9ACA00");
+		const replacement = await sendTransfer(nonce, "0x77359400");
+		await waitFor(notifications, 1);
+		expect(notifications[0]).to.deep.eq({ hash: replaced, status: "replaced", replacedBy: replacement });
+
+		await createAndFinalizeBlock(context.web3);
+		await waitFor(notifications, 2);
+		const block = await context.web3.eth.getBlock("latest");
+		expect(notifications[1]).to.deep.eq({ hash: replacement, status: "included", blockHash: block.hash });
+
+		await customRequest(context.web3, "eth_unsubscribe", [id]);
+	}).timeout(20000);
+});
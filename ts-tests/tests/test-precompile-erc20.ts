@@ -0,0 +1,73 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+import { AbiItem } from "web3-utils";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Balances ERC-20 Precompile)", (context) => {
+	const ERC20_ADDRESS = "0x0000000000000000000000000000000000000800";
+	const ERC20_ABI = [
+		{ name: "name", type: "function", inputs: [], outputs: [{ name: "", type: "string" }] },
+		{ name: "symbol", type: "function", inputs: [], outputs: [{ name: "", type: "string" }] },
+		{ name: "decimals", type: "function", inputs: [], outputs: [{ name: "", type: "uint8" }] },
+		{
+			name: "balanceOf",
+			type: "function",
+			inputs: [{ name: "owner", type: "address" }],
+			outputs: [{ name: "", type: "uint256" }],
+		},
+		{
+			name: "transfer",
+			type: "function",
+			inputs: [
+				{ name: "to", type: "address" },
+				{ name: "value", type: "uint256" },
+			],
+			outputs: [{ name: "", type: "bool" }],
+		},
+	] as AbiItem[];
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	let erc20;
+
+	before(async () => {
+		erc20 = new context.web3.eth.Contract(ERC20_ABI, ERC20_ADDRESS);
+	});
+
+	step("should return the metadata of the native currency", async function () {
+		expect(await erc20.methods.name().call()).to.eq("Unit");
+		expect(await erc20.methods.symbol().call()).to.eq("UNIT");
+		expect(await erc20.methods.decimals().call()).to.eq("18");
+	});
+
+	step("should return the native balance", async function () {
+		expect(await erc20.methods.balanceOf(GENESIS_ACCOUNT).call()).to.eq(
+			await context.web3.eth.getBalance(GENESIS_ACCOUNT)
+		);
+	});
+
+	step("should transfer the native currency", async function () {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: ERC20_ADDRESS,
+				data: erc20.methods.transfer(TEST_ACCOUNT, 4096).encodeABI(),
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		const hash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+
+		const receipt = await context.web3.eth.getTransactionReceipt(hash);
+		expect(receipt.status).to.be.true;
+		expect(receipt.logs.length).to.eq(1);
+		expect(receipt.logs[0].address.toLowerCase()).to.eq(ERC20_ADDRESS);
+		expect(receipt.logs[0].topics[0]).to.eq(context.web3.utils.sha3("Transfer(address,address,uint256)"));
+		expect(await context.web3.eth.getBalance(TEST_ACCOUNT)).to.eq("4096");
+		expect(await erc20.methods.balanceOf(TEST_ACCOUNT).call()).to.eq("4096");
+	});
+});
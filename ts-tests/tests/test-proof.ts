@@ -0,0 +1,78 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import Test from "../build/contracts/Storage.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Proof)", (context) => {
+	const TEST_CONTRACT_BYTECODE = Test.bytecode;
+	const TEST_CONTRACT_ABI = Test.abi as AbiItem[];
+	const SLOT = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+	const VALUE = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+	const EMPTY_CODE_HASH = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+	let contractAddress;
+
+	before("create a contract and set a storage slot", async function () {
+		this.timeout(15000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: TEST_CONTRACT_BYTECODE,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		await createAndFinalizeBlock(context.web3);
+		contractAddress = (await context.web3.eth.getTransactionReceipt(tx.transactionHash)).contractAddress;
+
+		const contract = new context.web3.eth.Contract(TEST_CONTRACT_ABI);
+		const tx1 = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: contractAddress,
+				data: contract.methods.setStorage(SLOT, VALUE).encodeABI(),
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x500000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx1.rawTransaction]);
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should return the proof of an externally owned account", async function () {
+		const proof = (await customRequest(context.web3, "eth_getProof", [GENESIS_ACCOUNT, [], "latest"])).result;
+		const block = await context.web3.eth.getBlock("latest");
+
+		expect(proof.address).to.eq(GENESIS_ACCOUNT.toLowerCase());
+		expect(BigInt(proof.balance).toString()).to.eq(await context.web3.eth.getBalance(GENESIS_ACCOUNT, block.number));
+		expect(Number(proof.nonce)).to.eq(2);
+		expect(proof.codeHash).to.eq(EMPTY_CODE_HASH);
+		expect(proof.accountProof.length).to.be.greaterThan(0);
+		expect(proof.storageProof).to.deep.eq([]);
+	});
+
+	it("should return the proof of contract code and storage", async function () {
+		const proof = (await customRequest(context.web3, "eth_getProof", [contractAddress, [SLOT, "0x0"], "latest"]))
+			.result;
+		const code = await context.web3.eth.getCode(contractAddress);
+
+		expect(proof.codeHash).to.eq(context.web3.utils.keccak256(code));
+		expect(proof.storageProof.length).to.eq(2);
+		expect(proof.storageProof[0].key).to.eq(SLOT);
+		expect(proof.storageProof[0].value).to.eq(VALUE);
+		expect(proof.storageProof[0].proof.length).to.be.greaterThan(0);
+		expect(proof.storageProof[1].value).to.eq("0x0");
+	});
+
+	it("should reject pending proofs", async function () {
+		const response = await customRequest(context.web3, "eth_getProof", [GENESIS_ACCOUNT, [], "pending"]);
+		expect(response.error.message).to.eq("proofs of the pending state are not supported");
+	});
+});
@@ -0,0 +1,48 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (eth_sendRawTransactionSync)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	async function signTransfer(nonce: number) {
+		return context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+				nonce,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+	}
+
+	step("should report a timeout with the transaction hash", async function () {
+		const tx = await signTransfer(0);
+		const response = await customRequest(context.web3, "eth_sendRawTransactionSync", [tx.rawTransaction, 100]);
+		expect(response.error.code).to.eq(4);
+		expect(response.error.data).to.eq(tx.transactionHash);
+
+		// The transaction stays in the pool.
+		await createAndFinalizeBlock(context.web3);
+		expect((await context.web3.eth.getTransactionReceipt(tx.transactionHash)).status).to.be.true;
+	});
+
+	step("should return the receipt once the transaction is included", async function () {
+		this.timeout(15000);
+		const tx = await signTransfer(1);
+		const request = customRequest(context.web3, "eth_sendRawTransactionSync", [tx.rawTransaction]);
+		// Let the transaction reach the pool before sealing.
+		await new Promise((resolve) => setTimeout(resolve, 500));
+		await createAndFinalizeBlock(context.web3);
+
+		const receipt = (await request).result;
+		expect(receipt.transactionHash).to.eq(tx.transactionHash);
+		expect(receipt.status).to.eq("0x1");
+		expect(receipt.blockNumber).to.eq(context.web3.utils.numberToHex(await context.web3.eth.getBlockNumber()));
+	});
+});
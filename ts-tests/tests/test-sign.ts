@@ -0,0 +1,82 @@
+import { expect } from "chai";
+import { ethers } from "ethers";
+
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Sign)", (context) => {
+	const MESSAGE = "0x68656c6c6f"; // "hello"
+
+	const domain = {
+		name: "Ether Mail",
+		version: "1",
+		chainId: 1,
+		verifyingContract: "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+	};
+	const types = {
+		Person: [
+			{ name: "name", type: "string" },
+			{ name: "wallet", type: "address" },
+		],
+		Mail: [
+			{ name: "from", type: "Person" },
+			{ name: "to", type: "Person" },
+			{ name: "contents", type: "string" },
+		],
+	};
+	const message = {
+		from: { name: "Cow", wallet: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+		to: { name: "Bob", wallet: "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+		contents: "Hello, Bob!",
+	};
+	const typedData = {
+		types: {
+			EIP712Domain: [
+				{ name: "name", type: "string" },
+				{ name: "version", type: "string" },
+				{ name: "chainId", type: "uint256" },
+				{ name: "verifyingContract", type: "address" },
+			],
+			...types,
+		},
+		primaryType: "Mail",
+		domain,
+		message,
+	};
+
+	let account;
+
+	before("get the dev signer account", async function () {
+		account = (await customRequest(context.web3, "eth_accounts", [])).result[0];
+	});
+
+	it("should sign a personal message with eth_sign", async function () {
+		const signature = (await customRequest(context.web3, "eth_sign", [account, MESSAGE])).result;
+		expect(ethers.utils.verifyMessage(ethers.utils.arrayify(MESSAGE), signature)).to.eq(
+			ethers.utils.getAddress(account)
+		);
+	});
+
+	it("should sign the same message with personal_sign", async function () {
+		const signature = (await customRequest(context.web3, "eth_sign", [account, MESSAGE])).result;
+		const personal = (await customRequest(context.web3, "personal_sign", [MESSAGE, account])).result;
+		expect(personal).to.eq(signature);
+	});
+
+	it("should sign typed data with eth_signTypedData_v4", async function () {
+		const signature = (await customRequest(context.web3, "eth_signTypedData_v4", [account, typedData])).result;
+		expect(ethers.utils.verifyTypedData(domain, types, message, signature)).to.eq(ethers.utils.getAddress(account));
+
+		const fromString = (
+			await customRequest(context.web3, "eth_signTypedData_v4", [account, JSON.stringify(typedData)])
+		).result;
+		expect(fromString).to.eq(signature);
+	});
+
+	it("should reject accounts without signer", async function () {
+		const response = await customRequest(context.web3, "eth_sign", [
+			"0x0000000000000000000000000000000000000001",
+			MESSAGE,
+		]);
+		expect(response.error.message).to.eq("no signer available");
+	});
+});
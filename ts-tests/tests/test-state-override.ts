@@ -0,0 +1,69 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import Storage from "../build/contracts/Storage.json";
+import { GENESIS_ACCOUNT } from "./config";
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (State Override)", (context) => {
+	const STORAGE_ABI = Storage.abi as AbiItem[];
+	const CONTRACT_ADDRESS = "0x1111111111111111111111111111111111111111";
+	const POOR_ACCOUNT = "0x2222222222222222222222222222222222222222";
+	const SLOT = "0x0000000000000000000000000000000000000000000000000000000000000001";
+	const VALUE = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+	const getStorage = () => {
+		const contract = new context.web3.eth.Contract(STORAGE_ABI);
+		return contract.methods.getStorage(SLOT).encodeABI();
+	};
+
+	it("should call injected code with overridden storage", async function () {
+		const result = await customRequest(context.web3, "eth_call", [
+			{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: getStorage() },
+			"latest",
+			{
+				[CONTRACT_ADDRESS]: {
+					code: Storage.deployedBytecode,
+					stateDiff: { [SLOT]: VALUE },
+				},
+			},
+		]);
+		expect(result.result).to.eq(VALUE);
+	});
+
+	it("should not persist the overrides", async function () {
+		expect(await context.web3.eth.getCode(CONTRACT_ADDRESS)).to.eq("0x");
+	});
+
+	it("should use the overridden balance", async function () {
+		const request = { from: POOR_ACCOUNT, to: GENESIS_ACCOUNT, value: "0x1000" };
+
+		const withoutOverride = await customRequest(context.web3, "eth_call", [request, "latest"]);
+		expect(withoutOverride.error).to.not.be.undefined;
+
+		const withOverride = await customRequest(context.web3, "eth_call", [
+			request,
+			"latest",
+			{ [POOR_ACCOUNT]: { balance: "0x1000000000" } },
+		]);
+		expect(withOverride.result).to.eq("0x");
+	});
+
+	it("should estimate gas with overrides", async function () {
+		const result = await customRequest(context.web3, "eth_estimateGas", [
+			{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: getStorage() },
+			"latest",
+			{ [CONTRACT_ADDRESS]: { code: Storage.deployedBytecode } },
+		]);
+		expect(Number(result.result)).to.be.greaterThan(21000);
+	});
+
+	it("should reject both state and stateDiff", async function () {
+		const result = await customRequest(context.web3, "eth_call", [
+			{ from: GENESIS_ACCOUNT, to: CONTRACT_ADDRESS, data: getStorage() },
+			"latest",
+			{ [CONTRACT_ADDRESS]: { state: { [SLOT]: VALUE }, stateDiff: { [SLOT]: VALUE } } },
+		]);
+		expect(result.error.message).to.eq(`account ${CONTRACT_ADDRESS} has both 'state' and 'stateDiff'`);
+	});
+});
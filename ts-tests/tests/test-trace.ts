@@ -0,0 +1,106 @@
+import { expect } from "chai";
+import { AbiItem } from "web3-utils";
+
+import ExplicitRevertReason from "../build/contracts/ExplicitRevertReason.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Trace)", (context) => {
+	const TEST_CONTRACT_ABI = ExplicitRevertReason.abi as AbiItem[];
+
+	let contractAddress;
+	let deployHash;
+	let revertHash;
+
+	before("create the contract and a reverting transaction", async function () {
+		this.timeout(15000);
+		const deploy = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: ExplicitRevertReason.bytecode,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		deployHash = (await customRequest(context.web3, "eth_sendRawTransaction", [deploy.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+		contractAddress = (await context.web3.eth.getTransactionReceipt(deployHash)).contractAddress.toLowerCase();
+
+		const contract = new context.web3.eth.Contract(TEST_CONTRACT_ABI, contractAddress);
+		const call = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: contractAddress,
+				data: contract.methods.max10(30).encodeABI(),
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		revertHash = (await customRequest(context.web3, "eth_sendRawTransaction", [call.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+	});
+
+	it("should trace a contract creation", async function () {
+		const traces = (await customRequest(context.web3, "trace_transaction", [deployHash])).result;
+		const receipt = await context.web3.eth.getTransactionReceipt(deployHash);
+		expect(traces.length).to.eq(1);
+		expect(traces[0].type).to.eq("create");
+		expect(traces[0].action.from).to.eq(GENESIS_ACCOUNT);
+		expect(traces[0].result.address).to.eq(contractAddress);
+		expect(traces[0]).to.include({
+			subtraces: 0,
+			transactionHash: deployHash,
+			transactionPosition: 0,
+			blockHash: receipt.blockHash,
+			blockNumber: receipt.blockNumber,
+		});
+		expect(traces[0].traceAddress).to.deep.eq([]);
+	});
+
+	it("should report the revert of a call", async function () {
+		const traces = (await customRequest(context.web3, "trace_transaction", [revertHash])).result;
+		expect(traces.length).to.eq(1);
+		expect(traces[0].type).to.eq("call");
+		expect(traces[0].action).to.include({ callType: "call", from: GENESIS_ACCOUNT, to: contractAddress });
+		expect(traces[0].error).to.eq("Reverted");
+		expect(traces[0]).to.not.have.property("result");
+	});
+
+	it("should trace all the transactions of a block", async function () {
+		const receipt = await context.web3.eth.getTransactionReceipt(revertHash);
+		const traces = (
+			await customRequest(context.web3, "trace_block", [context.web3.utils.numberToHex(receipt.blockNumber)])
+		).result;
+		expect(traces.length).to.eq(1);
+		expect(traces[0].transactionHash).to.eq(revertHash);
+	});
+
+	it("should get a trace by its address", async function () {
+		const trace = (await customRequest(context.web3, "trace_get", [revertHash, []])).result;
+		expect(trace.transactionHash).to.eq(revertHash);
+		expect((await customRequest(context.web3, "trace_get", [revertHash, ["0x0"]])).result).to.be.null;
+	});
+
+	it("should filter the traces by address", async function () {
+		this.timeout(15000);
+		// Let the trace index worker catch up.
+		await new Promise((resolve) => setTimeout(resolve, 7000));
+
+		const all = (await customRequest(context.web3, "trace_filter", [{ fromAddress: [GENESIS_ACCOUNT] }])).result;
+		expect(all.map((trace) => trace.transactionHash)).to.deep.eq([deployHash, revertHash]);
+
+		const toContract = (
+			await customRequest(context.web3, "trace_filter", [{ toAddress: [contractAddress] }])
+		).result;
+		expect(toContract.map((trace) => trace.transactionHash)).to.deep.eq([deployHash, revertHash]);
+
+		const paged = (
+			await customRequest(context.web3, "trace_filter", [{ fromAddress: [GENESIS_ACCOUNT], after: 1, count: 1 }])
+		).result;
+		expect(paged.map((trace) => trace.transactionHash)).to.deep.eq([revertHash]);
+	});
+});
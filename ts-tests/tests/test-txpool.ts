@@ -0,0 +1,60 @@
+import { expect } from "chai";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (TxPool)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	let pendingHash;
+	let queuedHash;
+
+	async function sendTransfer(nonce: number) {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+				nonce,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		return (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+	}
+
+	before("send a pending and a queued transaction", async function () {
+		this.timeout(15000);
+		pendingHash = await sendTransfer(0);
+		// Nonce 1 is missing, so this one waits in the future queue.
+		queuedHash = await sendTransfer(2);
+	});
+
+	it("txpool_status should count pending and queued transactions", async function () {
+		const status = (await customRequest(context.web3, "txpool_status", [])).result;
+		expect(status).to.deep.eq({ pending: "0x1", queued: "0x1" });
+	});
+
+	it("txpool_content should group transactions by sender and nonce", async function () {
+		const content = (await customRequest(context.web3, "txpool_content", [])).result;
+		const sender = GENESIS_ACCOUNT.toLowerCase();
+		expect(Object.keys(content.pending)).to.deep.eq([sender]);
+		expect(content.pending[sender]["0"]).to.include({ hash: pendingHash, to: TEST_ACCOUNT, blockNumber: null });
+		expect(content.queued[sender]["2"]).to.include({ hash: queuedHash, nonce: "0x2" });
+	});
+
+	it("txpool_inspect should summarize transactions", async function () {
+		const inspect = (await customRequest(context.web3, "txpool_inspect", [])).result;
+		const sender = GENESIS_ACCOUNT.toLowerCase();
+		expect(inspect.pending[sender]["0"]).to.eq(`${TEST_ACCOUNT}: 512 wei + 1048576 gas × 1000000000 wei`);
+		expect(inspect.queued[sender]["2"]).to.eq(`${TEST_ACCOUNT}: 512 wei + 1048576 gas × 1000000000 wei`);
+	});
+
+	it("should empty the pending part once included", async function () {
+		this.timeout(15000);
+		await createAndFinalizeBlock(context.web3);
+		const status = (await customRequest(context.web3, "txpool_status", [])).result;
+		expect(status).to.deep.eq({ pending: "0x0", queued: "0x1" });
+	});
+});
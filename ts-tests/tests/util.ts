@@ -78,6 +78,9 @@ export async function startFrontierNode(provider?: string): Promise<{
 		`--sealing=Manual`,
 		`--no-grandpa`,
 		`--force-authoring`,
+		`--enable-dev-signer`,
+		`--enable-trace-index`,
+		`--ethapi=debug,trace,ots`,
 		`-l${FRONTIER_LOG}`,
 		`--port=${PORT}`,
 		`--rpc-port=${RPC_PORT}`,